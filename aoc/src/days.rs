@@ -0,0 +1,102 @@
+use anyhow::Result;
+use common::puzzle::{self, Puzzle};
+use common::Rendered;
+use std::time::{Duration, Instant};
+
+/// An answer along with how long it took to get there.
+pub struct Solution {
+    pub answer: String,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+pub type Solver = fn(&str) -> Result<Solution>;
+
+/// Every problem with an input, each rendered with the line it is on.
+pub type Checker = fn(&str) -> Vec<String>;
+
+pub struct Day {
+    pub number: u8,
+    pub title: &'static str,
+    pub part_1: Solver,
+    pub part_2: Solver,
+    pub check: Checker,
+}
+
+impl Day {
+    pub fn solver(&self, part: u8) -> Solver {
+        match part {
+            1 => self.part_1,
+            _ => self.part_2,
+        }
+    }
+}
+
+fn solve<P: Puzzle, const PART: u8>(data: &str) -> Result<Solution> {
+    let start = Instant::now();
+    let input = puzzle::parse::<P>(data).rendered(data)?;
+    let parsed = Instant::now();
+    let answer = puzzle::solve::<P>(&input, PART)?;
+    Ok(Solution {
+        answer,
+        parse_time: parsed - start,
+        solve_time: parsed.elapsed(),
+    })
+}
+
+fn check<P: Puzzle>(data: &str) -> Vec<String> {
+    P::check(data).iter().map(|e| e.render(data)).collect()
+}
+
+const fn day<P: Puzzle>() -> Day {
+    Day {
+        number: P::DAY,
+        title: P::TITLE,
+        part_1: solve::<P, 1>,
+        part_2: solve::<P, 2>,
+        check: check::<P>,
+    }
+}
+
+pub static DAYS: [Day; 15] = [
+    day::<day_1::SonarSweep>(),
+    day::<day_2::Dive>(),
+    day::<day_3::BinaryDiagnostic>(),
+    day::<day_4::GiantSquid>(),
+    day::<day_5::HydrothermalVenture>(),
+    day::<day_6::Lanternfish>(),
+    day::<day_7::TreacheryOfWhales>(),
+    day::<day_8::SevenSegmentSearch>(),
+    day::<day_9::SmokeBasin>(),
+    day::<day_10::SyntaxScoring>(),
+    day::<day_11::DumboOctopus>(),
+    day::<day_12::PassagePathing>(),
+    day::<day_13::TransparentOrigami>(),
+    day::<day_14::ExtendedPolymerization>(),
+    day::<day_15::Chiton>(),
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_days_in_order() {
+        for (i, day) in DAYS.iter().enumerate() {
+            assert_eq!(usize::from(day.number), i + 1, "{}", day.title);
+        }
+    }
+
+    #[test]
+    fn test_check() {
+        let problems = (find(1).unwrap().check)("199\n2o0\n208\n-1");
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0].contains("--> line 2, column 1"));
+        assert!(problems[1].ends_with("4 | -1\n  | ^^"));
+        assert!((find(14).unwrap().check)(include_str!("../../day-14/data/test.txt")).is_empty());
+    }
+}
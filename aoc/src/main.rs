@@ -0,0 +1,658 @@
+use anyhow::{anyhow, Context, Result};
+use clap::{Args, Parser, Subcommand};
+use common::input::Source;
+use day_1::anomaly::Detector;
+use rayon::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+mod days;
+mod repl;
+mod report;
+mod sonar;
+mod stream;
+mod trace;
+mod verify;
+
+use days::{Day, DAYS};
+use report::{Format, InputKind, Record};
+use verify::{Outcome, Registry};
+
+/// Runs the Advent of Code 2021 solutions.
+#[derive(Parser)]
+#[command(name = "aoc")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Trace the parsers and solvers, writing to stderr (or to the trace
+    /// output for folded stacks)
+    #[arg(long, global = true, value_enum)]
+    trace: Option<trace::TraceFormat>,
+    /// Which spans to trace, as `info` for parse and solve, `debug` for the
+    /// inner phases and counters, `trace` for every step; or per crate like
+    /// `info,day_15=trace`
+    #[arg(long, global = true, default_value = "debug")]
+    trace_filter: String,
+    /// Where folded stacks go
+    #[arg(long, global = true, default_value = "trace.folded")]
+    trace_output: PathBuf,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the solution for one day, or for every day
+    Run(RunArgs),
+    /// Check the solutions against the recorded answers
+    Verify(VerifyArgs),
+    /// Report every problem with a day's input, without solving it
+    Check(CheckArgs),
+    /// Generate a synthetic input for a day
+    Gen(GenArgs),
+    /// Animate a day's simulation in the terminal
+    Animate(AnimateArgs),
+    /// Draw a day's grid as a PNG or PPM image
+    Image(ImageArgs),
+    /// Load a day's input and step through it at a prompt
+    Repl(ReplArgs),
+    /// Solve a day a line at a time, for inputs too big to load
+    Stream(StreamArgs),
+    /// Analyze day 1's sonar readings
+    #[command(subcommand)]
+    Sonar(SonarCommand),
+    /// List the available days
+    List,
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// Day to run
+    #[arg(short, long, required_unless_present = "all", conflicts_with = "all")]
+    day: Option<u8>,
+    /// Only run this part
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Input file, or `-` for stdin. Defaults to `<input dir>/day-NN.txt`
+    #[arg(short, long, requires = "day")]
+    input: Option<String>,
+    /// Report the input as an example or a real one. By default an input
+    /// matching one of the day's examples in the answers file is an
+    /// example, and any other input is real
+    #[arg(long, value_enum)]
+    kind: Option<InputKind>,
+    /// File listing the examples, to tell them apart from real inputs
+    #[arg(long, default_value = "answers.toml")]
+    answers: PathBuf,
+    /// Directory holding the `day-NN.txt` inputs
+    #[arg(long, env = common::input::INPUT_DIR_VAR, default_value = common::input::DEFAULT_INPUT_DIR)]
+    input_dir: PathBuf,
+    /// Run every day
+    #[arg(short, long)]
+    all: bool,
+    /// Run the days at the same time, on every core
+    #[arg(long)]
+    parallel: bool,
+    /// How to print the results
+    #[arg(short, long, value_enum, default_value_t = Format::Human)]
+    format: Format,
+}
+
+#[derive(Args)]
+struct VerifyArgs {
+    /// Only verify this day
+    #[arg(short, long)]
+    day: Option<u8>,
+    /// File holding the expected answers
+    #[arg(long, default_value = "answers.toml")]
+    answers: PathBuf,
+    /// Directory holding the `day-NN.txt` inputs
+    #[arg(long, env = common::input::INPUT_DIR_VAR, default_value = common::input::DEFAULT_INPUT_DIR)]
+    input_dir: PathBuf,
+}
+
+#[derive(Args)]
+struct CheckArgs {
+    /// Only check this day's input
+    #[arg(short, long)]
+    day: Option<u8>,
+    /// Input file, or `-` for stdin. Defaults to `<input dir>/day-NN.txt`
+    #[arg(short, long, requires = "day")]
+    input: Option<String>,
+    /// Directory holding the `day-NN.txt` inputs
+    #[arg(long, env = common::input::INPUT_DIR_VAR, default_value = common::input::DEFAULT_INPUT_DIR)]
+    input_dir: PathBuf,
+}
+
+#[derive(Args)]
+struct GenArgs {
+    /// Day to generate an input for
+    #[arg(short, long)]
+    day: u8,
+    /// Seed for the random number generator
+    #[arg(short, long, default_value_t = 0)]
+    seed: u64,
+    /// Lines, entries or grid side, depending on the day. Defaults to about
+    /// the size of the real input
+    #[arg(long)]
+    size: Option<usize>,
+    /// Write the input here instead of to stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct AnimateArgs {
+    /// Day to animate
+    #[arg(short, long)]
+    day: u8,
+    /// Input file, or `-` for stdin. Defaults to `<input dir>/day-NN.txt`
+    #[arg(short, long)]
+    input: Option<String>,
+    /// Directory holding the `day-NN.txt` inputs
+    #[arg(long, env = common::input::INPUT_DIR_VAR, default_value = common::input::DEFAULT_INPUT_DIR)]
+    input_dir: PathBuf,
+    /// Frames per second to start at
+    #[arg(long, default_value_t = visualize::player::DEFAULT_FPS, value_parser = visualize::parse_fps)]
+    fps: f64,
+    /// Start paused on the first frame
+    #[arg(long)]
+    paused: bool,
+}
+
+#[derive(Args)]
+struct ImageArgs {
+    /// Day to draw
+    #[arg(short, long)]
+    day: u8,
+    /// Input file, or `-` for stdin. Defaults to `<input dir>/day-NN.txt`
+    #[arg(short, long)]
+    input: Option<String>,
+    /// Directory holding the `day-NN.txt` inputs
+    #[arg(long, env = common::input::INPUT_DIR_VAR, default_value = common::input::DEFAULT_INPUT_DIR)]
+    input_dir: PathBuf,
+    /// Image to write, a `.png` or `.ppm`
+    #[arg(short, long)]
+    output: PathBuf,
+    /// Pixels per grid cell along each side
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u16).range(1..=64))]
+    scale: u16,
+    /// Colours to use: heat, gray or viridis
+    #[arg(long, default_value = "heat")]
+    palette: visualize::Palette,
+    /// Draw the state for this part (days 5 and 13)
+    #[arg(short, long, default_value_t = 2, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+    /// Steps to run before drawing (day 11)
+    #[arg(long, default_value_t = 100, value_parser = clap::value_parser!(u64).range(..=common::puzzle::MAX_REPEAT))]
+    steps: u64,
+}
+
+#[derive(Args)]
+struct ReplArgs {
+    /// Day to load
+    #[arg(short, long)]
+    day: u8,
+    /// Input file. Defaults to `<input dir>/day-NN.txt`
+    #[arg(short, long)]
+    input: Option<String>,
+    /// Directory holding the `day-NN.txt` inputs
+    #[arg(long, env = common::input::INPUT_DIR_VAR, default_value = common::input::DEFAULT_INPUT_DIR)]
+    input_dir: PathBuf,
+}
+
+#[derive(Args)]
+struct StreamArgs {
+    /// Day to solve
+    #[arg(short, long)]
+    day: u8,
+    /// Only solve this part
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Input file, or `-` for stdin. Defaults to `<input dir>/day-NN.txt`.
+    /// Solving both parts of stdin needs it twice, so pick a part
+    #[arg(short, long)]
+    input: Option<String>,
+    /// Directory holding the `day-NN.txt` inputs
+    #[arg(long, env = common::input::INPUT_DIR_VAR, default_value = common::input::DEFAULT_INPUT_DIR)]
+    input_dir: PathBuf,
+}
+
+#[derive(Subcommand)]
+enum SonarCommand {
+    /// Count how windows of readings change against earlier windows
+    Analyze(AnalyzeArgs),
+    /// Watch readings as they arrive, printing running counts and alerts
+    Monitor(MonitorArgs),
+    /// Split the readings into rising, falling and flat runs
+    Trends(TrendsArgs),
+    /// Flag sudden drops, spikes and readings far from the recent mean
+    Anomalies(AnomaliesArgs),
+}
+
+#[derive(Args)]
+struct AnalyzeArgs {
+    /// Readings in each window
+    #[arg(short, long, default_value_t = 1)]
+    window: usize,
+    /// How a window becomes one value: sum, mean, min, max or median
+    #[arg(short, long, default_value = "sum")]
+    aggregate: day_1::Aggregate,
+    /// How many windows back each window is compared with
+    #[arg(short, long, default_value_t = 1)]
+    lag: usize,
+    /// Input file, or `-` for stdin. Defaults to `<input dir>/day-01.txt`
+    #[arg(short, long)]
+    input: Option<String>,
+    /// Directory holding the `day-NN.txt` inputs
+    #[arg(long, env = common::input::INPUT_DIR_VAR, default_value = common::input::DEFAULT_INPUT_DIR)]
+    input_dir: PathBuf,
+    #[command(flatten)]
+    log: LogArgs,
+}
+
+/// How to read a sonar log with more than one depth per line.
+#[derive(Args)]
+struct LogArgs {
+    /// What separates the columns; a space splits on any whitespace
+    #[arg(long, default_value_t = ',')]
+    delimiter: char,
+    /// The depth column, by 1-based number or by name in the header
+    #[arg(long, default_value = "1")]
+    column: day_1::log::Column,
+    /// The timestamp column, by number or name
+    #[arg(long)]
+    time_column: Option<day_1::log::Column>,
+    /// Skip the first line, which names the columns
+    #[arg(long)]
+    header: bool,
+    /// What to do about blank or NaN depths: skip, interpolate or break
+    #[arg(long, default_value = "skip")]
+    gaps: day_1::log::Gaps,
+}
+
+impl LogArgs {
+    fn format(&self) -> day_1::log::Format {
+        day_1::log::Format {
+            delimiter: self.delimiter,
+            depth: self.column.clone(),
+            time: self.time_column.clone(),
+            header: self.header,
+        }
+    }
+}
+
+#[derive(Args)]
+struct MonitorArgs {
+    /// Readings in each window
+    #[arg(short, long, default_value_t = 1)]
+    window: usize,
+    /// How a window becomes one value: sum, mean, min, max or median
+    #[arg(short, long, default_value = "sum")]
+    aggregate: day_1::Aggregate,
+    /// How many windows back each window is compared with
+    #[arg(short, long, default_value_t = 1)]
+    lag: usize,
+    /// Alert when a window is at least this much deeper than the one it is
+    /// compared with
+    #[arg(long)]
+    rise: Option<f64>,
+    /// Alert after this many increases in a row
+    #[arg(long)]
+    streak: Option<usize>,
+    /// Input file, or `-` for stdin
+    #[arg(short, long, default_value = "-")]
+    input: String,
+    #[command(flatten)]
+    log: LogArgs,
+}
+
+#[derive(Args)]
+struct TrendsArgs {
+    /// Readings in each window, to smooth the readings before splitting them
+    #[arg(short, long, default_value_t = 1)]
+    window: usize,
+    /// How a window becomes one value: sum, mean, min, max or median
+    #[arg(short, long, default_value = "mean")]
+    aggregate: day_1::Aggregate,
+    /// List every run after the summary
+    #[arg(long)]
+    runs: bool,
+    /// Input file, or `-` for stdin. Defaults to `<input dir>/day-01.txt`
+    #[arg(short, long)]
+    input: Option<String>,
+    /// Directory holding the `day-NN.txt` inputs
+    #[arg(long, env = common::input::INPUT_DIR_VAR, default_value = common::input::DEFAULT_INPUT_DIR)]
+    input_dir: PathBuf,
+    #[command(flatten)]
+    log: LogArgs,
+}
+
+#[derive(Args)]
+struct AnomaliesArgs {
+    /// Flag readings at least this much shallower than the one before
+    #[arg(long)]
+    drop: Option<f64>,
+    /// Flag readings at least this far above or below both neighbours
+    #[arg(long)]
+    spike: Option<f64>,
+    /// Flag readings more than this many standard deviations from the mean
+    /// of the readings before them
+    #[arg(long)]
+    sigma: Option<f64>,
+    /// How many readings before each one the mean is taken over
+    #[arg(long, default_value_t = 10)]
+    rolling: usize,
+    /// Input file, or `-` for stdin. Defaults to `<input dir>/day-01.txt`
+    #[arg(short, long)]
+    input: Option<String>,
+    /// Directory holding the `day-NN.txt` inputs
+    #[arg(long, env = common::input::INPUT_DIR_VAR, default_value = common::input::DEFAULT_INPUT_DIR)]
+    input_dir: PathBuf,
+    #[command(flatten)]
+    log: LogArgs,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let guard = match cli.trace {
+        Some(format) => Some(trace::init(format, &cli.trace_filter, &cli.trace_output)?),
+        None => None,
+    };
+    let result = dispatch(cli.command);
+    if let Some(guard) = guard {
+        guard.flush()?;
+    }
+    result
+}
+
+fn dispatch(command: Command) -> Result<()> {
+    match command {
+        Command::Run(args) => run(&args),
+        Command::Verify(args) => verify(&args),
+        Command::Check(args) => check(&args),
+        Command::Gen(args) => gen(&args),
+        Command::Animate(args) => animate(&args),
+        Command::Image(args) => image(&args),
+        Command::Repl(args) => {
+            let source = match &args.input {
+                Some(arg) => Source::from_arg(arg),
+                None => Source::in_dir(&args.input_dir, args.day),
+            };
+            repl::start(args.day, &source.read()?)
+        }
+        Command::Stream(args) => stream(&args),
+        Command::Sonar(command) => sonar(command),
+        Command::List => {
+            for day in DAYS.iter() {
+                println!("{:>2}  {}", day.number, day.title);
+            }
+            Ok(())
+        }
+    }
+}
+
+fn run(args: &RunArgs) -> Result<()> {
+    let selected: Vec<&Day> = match args.day {
+        Some(number) => {
+            vec![days::find(number).ok_or_else(|| anyhow!("no solution for day {}", number))?]
+        }
+        None => DAYS.iter().collect(),
+    };
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    // without an answers file every input given is taken to be real
+    let registry = match (&args.input, args.kind) {
+        (Some(_), None) => Registry::load(&args.answers).ok(),
+        _ => None,
+    };
+    let base = args.answers.parent().unwrap_or_else(|| Path::new(""));
+    let start = Instant::now();
+    let run_day = |day: &Day| -> Result<(Vec<Record>, Duration)> {
+        let started = Instant::now();
+        let source = match &args.input {
+            Some(arg) => Source::from_arg(arg),
+            None => Source::in_dir(&args.input_dir, day.number),
+        };
+        let data = source.read()?;
+        let kind = match (&args.input, args.kind, &registry) {
+            (Some(_), Some(kind), _) => kind,
+            (Some(_), None, Some(registry)) => registry.kind_of(day.number, &data, base),
+            _ => InputKind::Real,
+        };
+        let fingerprint = report::fingerprint(&data);
+        let mut records = Vec::new();
+        for &part in parts.iter() {
+            let solution = day.solver(part)(&data)
+                .with_context(|| format!("day {} part {} failed", day.number, part))?;
+            records.push(Record::new(day.number, part, solution, &fingerprint, kind));
+        }
+        Ok((records, started.elapsed()))
+    };
+    let print = |records: &[Record]| {
+        if args.format == Format::Human {
+            for record in records {
+                print_answer(record.day, record.part, &record.answer);
+            }
+        }
+    };
+    // answers come out in day order either way; in parallel they wait until
+    // every day is done
+    let mut days = Vec::new();
+    if args.parallel {
+        days = selected
+            .par_iter()
+            .map(|day| run_day(day))
+            .collect::<Result<_>>()?;
+        days.iter().for_each(|(records, _)| print(records));
+    } else {
+        for day in selected {
+            let (records, took) = run_day(day)?;
+            print(&records);
+            days.push((records, took));
+        }
+    }
+    if args.format == Format::Human && days.len() > 1 {
+        println!();
+        for (records, took) in days.iter() {
+            if let Some(record) = records.first() {
+                println!("Day {:>2} took {:.2?}", record.day, took);
+            }
+        }
+        println!("{} days took {:.2?}", days.len(), start.elapsed());
+    }
+    let records: Vec<Record> = days.into_iter().flat_map(|(records, _)| records).collect();
+    report::write(&records, args.format, std::io::stdout().lock())
+}
+
+fn verify(args: &VerifyArgs) -> Result<()> {
+    let registry = Registry::load(&args.answers)?;
+    let base = args.answers.parent().unwrap_or_else(|| Path::new(""));
+    let (mut total, mut passed) = (0, 0);
+    for expected in registry
+        .answers
+        .iter()
+        .filter(|e| args.day.is_none_or(|day| e.day == day))
+    {
+        for check in verify::check(expected, base, &args.input_dir) {
+            println!("{}", check);
+            total += 1;
+            if let Outcome::Pass = check.outcome {
+                passed += 1;
+            }
+        }
+    }
+    println!("{} of {} checks passed", passed, total);
+    if passed < total {
+        return Err(anyhow!("{} checks did not pass", total - passed));
+    }
+    Ok(())
+}
+
+fn check(args: &CheckArgs) -> Result<()> {
+    let selected: Vec<&Day> = match args.day {
+        Some(number) => {
+            vec![days::find(number).ok_or_else(|| anyhow!("no solution for day {}", number))?]
+        }
+        None => DAYS.iter().collect(),
+    };
+    let mut total = 0;
+    for day in selected {
+        let source = match &args.input {
+            Some(arg) => Source::from_arg(arg),
+            None => Source::in_dir(&args.input_dir, day.number),
+        };
+        let problems = (day.check)(&source.read()?);
+        match problems.len() {
+            0 => println!("Day {:>2}: {} is fine", day.number, source),
+            1 => println!("Day {:>2}: 1 problem in {}", day.number, source),
+            n => println!("Day {:>2}: {} problems in {}", day.number, n, source),
+        }
+        for problem in problems.iter() {
+            println!("{}\n", problem);
+        }
+        total += problems.len();
+    }
+    match total {
+        0 => Ok(()),
+        1 => Err(anyhow!("found 1 problem")),
+        n => Err(anyhow!("found {} problems", n)),
+    }
+}
+
+fn gen(args: &GenArgs) -> Result<()> {
+    let text = generate::generate(args.day, args.seed, args.size)
+        .ok_or_else(|| anyhow!("no generator for day {}", args.day))?;
+    match &args.output {
+        Some(path) => fs::write(path, text + "\n")
+            .with_context(|| format!("failed to write {}", path.display()))?,
+        None => println!("{}", text),
+    }
+    Ok(())
+}
+
+fn animate(args: &AnimateArgs) -> Result<()> {
+    let source = match &args.input {
+        Some(arg) => Source::from_arg(arg),
+        None => Source::in_dir(&args.input_dir, args.day),
+    };
+    let frames = visualize::frames(args.day, &source.read()?)?;
+    visualize::play(frames, visualize::Controls::new(args.fps, args.paused))
+}
+
+fn image(args: &ImageArgs) -> Result<()> {
+    let source = match &args.input {
+        Some(arg) => Source::from_arg(arg),
+        None => Source::in_dir(&args.input_dir, args.day),
+    };
+    let options = visualize::ImageOptions {
+        scale: usize::from(args.scale),
+        palette: args.palette,
+        part: args.part,
+        steps: args.steps,
+    };
+    let image = visualize::image(args.day, &source.read()?, &options)?;
+    image.save(&args.output)?;
+    println!(
+        "wrote {} ({}x{})",
+        args.output.display(),
+        image.width(),
+        image.height()
+    );
+    Ok(())
+}
+
+fn stream(args: &StreamArgs) -> Result<()> {
+    stream::check_day(args.day)?;
+    let source = match &args.input {
+        Some(arg) => Source::from_arg(arg),
+        None => Source::in_dir(&args.input_dir, args.day),
+    };
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None if source == Source::Stdin => {
+            return Err(anyhow!("stdin can only be read once, pick a part"))
+        }
+        None => vec![1, 2],
+    };
+    // each part reads the input afresh, so only a line is held at a time
+    for part in parts {
+        let answer = stream::solve(args.day, part, source.open()?)
+            .with_context(|| format!("day {} part {} failed", args.day, part))?;
+        print_answer(args.day, part, &answer);
+    }
+    Ok(())
+}
+
+fn sonar(command: SonarCommand) -> Result<()> {
+    match command {
+        SonarCommand::Analyze(args) => {
+            let analysis = day_1::Analysis::new(args.window, args.aggregate, args.lag)?;
+            let source = match &args.input {
+                Some(arg) => Source::from_arg(arg),
+                None => Source::in_dir(&args.input_dir, 1),
+            };
+            let readings = sonar::readings(&source.read()?, &args.log.format())?;
+            println!("{}", sonar::analyze(analysis, &readings, args.log.gaps));
+        }
+        SonarCommand::Monitor(args) => {
+            let analysis = day_1::Analysis::new(args.window, args.aggregate, args.lag)?;
+            let thresholds = day_1::Thresholds {
+                rise: args.rise,
+                streak: args.streak,
+            };
+            let mut monitor = day_1::Monitor::new(analysis, thresholds);
+            let reader = Source::from_arg(&args.input).open()?;
+            sonar::monitor(
+                &mut monitor,
+                reader,
+                &args.log.format(),
+                args.log.gaps,
+                &mut std::io::stdout(),
+                &mut std::io::stderr(),
+            )?;
+        }
+        SonarCommand::Trends(args) => {
+            // a window is numbered by its first reading
+            let analysis = day_1::Analysis::new(args.window, args.aggregate, 1)?;
+            let source = match &args.input {
+                Some(arg) => Source::from_arg(arg),
+                None => Source::in_dir(&args.input_dir, 1),
+            };
+            let readings = sonar::readings(&source.read()?, &args.log.format())?;
+            let segments = args.log.gaps.apply_lines(&readings);
+            println!("{}", sonar::trends(analysis, &segments, args.runs));
+        }
+        SonarCommand::Anomalies(args) => {
+            let detectors: Vec<Detector> = [
+                args.drop.map(Detector::drop),
+                args.spike.map(Detector::spike),
+                args.sigma.map(|k| Detector::deviation(args.rolling, k)),
+            ]
+            .into_iter()
+            .flatten()
+            .collect::<Result<_, _>>()?;
+            if detectors.is_empty() {
+                return Err(anyhow!("pick a detector: --drop, --spike or --sigma"));
+            }
+            let source = match &args.input {
+                Some(arg) => Source::from_arg(arg),
+                None => Source::in_dir(&args.input_dir, 1),
+            };
+            let readings = sonar::readings(&source.read()?, &args.log.format())?;
+            let segments = args.log.gaps.apply_lines(&readings);
+            println!("{}", sonar::anomalies(&segments, &detectors));
+        }
+    }
+    Ok(())
+}
+
+fn print_answer(day: u8, part: u8, answer: &str) {
+    // multi-line answers (e.g. the day 13 letters) start on their own line
+    if answer.contains('\n') {
+        println!("Day {:>2}, part {}:\n{}", day, part, answer);
+    } else {
+        println!("Day {:>2}, part {}: {}", day, part, answer);
+    }
+}
@@ -0,0 +1,336 @@
+//! An interactive prompt for poking at a day's parsed state: step it, fold
+//! it, call numbers on it, look at it, and undo what went wrong.
+use anyhow::{anyhow, Result};
+use common::Rendered;
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+use std::collections::VecDeque;
+
+mod models;
+
+/// States kept for `undo`; older ones are dropped.
+const UNDO_LIMIT: usize = 1000;
+
+/// A command a model understands, beyond the ones every model has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Command {
+    pub name: &'static str,
+    pub usage: &'static str,
+    pub help: &'static str,
+    /// Whether the command changes the state, and so can be undone.
+    pub changes: bool,
+}
+
+/// A day's state as the prompt sees it.
+pub trait Model: Clone {
+    fn commands(&self) -> &'static [Command];
+    fn show(&self) -> String;
+    fn score(&self) -> String;
+    /// Runs one of [`Model::commands`], returning what to print.
+    fn run(&mut self, command: &str, args: &[&str]) -> Result<String>;
+}
+
+/// The commands every model has.
+const SHARED: [Command; 5] = [
+    Command {
+        name: "show",
+        usage: "show",
+        help: "print the current state",
+        changes: false,
+    },
+    Command {
+        name: "score",
+        usage: "score",
+        help: "print what the puzzle asks about the current state",
+        changes: false,
+    },
+    Command {
+        name: "undo",
+        usage: "undo",
+        help: "go back to the state before the last change",
+        changes: false,
+    },
+    Command {
+        name: "help",
+        usage: "help",
+        help: "list the commands",
+        changes: false,
+    },
+    Command {
+        name: "quit",
+        usage: "quit",
+        help: "leave (so does ctrl-d)",
+        changes: false,
+    },
+];
+
+/// What the prompt should do after a line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reply {
+    Print(String),
+    Nothing,
+    Quit,
+}
+
+/// A model and the states it was in before each change.
+pub struct Session<M> {
+    model: M,
+    history: VecDeque<M>,
+}
+
+impl<M: Model> Session<M> {
+    pub fn new(model: M) -> Self {
+        Session {
+            model,
+            history: VecDeque::new(),
+        }
+    }
+
+    /// The names of every command, for completion.
+    pub fn command_names(&self) -> Vec<&'static str> {
+        self.model
+            .commands()
+            .iter()
+            .chain(SHARED.iter())
+            .map(|c| c.name)
+            .collect()
+    }
+
+    /// Runs one line of input.
+    pub fn execute(&mut self, line: &str) -> Result<Reply> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let Some((&name, args)) = words.split_first() else {
+            return Ok(Reply::Nothing);
+        };
+        let reply = match name {
+            "show" => self.model.show(),
+            "score" => self.model.score(),
+            "undo" => match self.history.pop_back() {
+                Some(previous) => {
+                    self.model = previous;
+                    String::from("undone")
+                }
+                None => return Err(anyhow!("nothing to undo")),
+            },
+            "help" => self.help(),
+            "quit" | "exit" => return Ok(Reply::Quit),
+            _ => {
+                let command = self
+                    .model
+                    .commands()
+                    .iter()
+                    .find(|c| c.name == name)
+                    .ok_or_else(|| anyhow!("unknown command {:?}, try help", name))?;
+                if !command.changes {
+                    return self.model.run(name, args).map(Reply::Print);
+                }
+                let before = self.model.clone();
+                match self.model.run(name, args) {
+                    Ok(reply) => {
+                        if self.history.len() == UNDO_LIMIT {
+                            self.history.pop_front();
+                        }
+                        self.history.push_back(before);
+                        reply
+                    }
+                    Err(e) => {
+                        // a command that fails part way leaves no trace
+                        self.model = before;
+                        return Err(e);
+                    }
+                }
+            }
+        };
+        Ok(Reply::Print(reply))
+    }
+
+    fn help(&self) -> String {
+        let commands: Vec<_> = self.model.commands().iter().chain(SHARED.iter()).collect();
+        let width = commands.iter().map(|c| c.usage.len()).max().unwrap_or(0);
+        commands
+            .iter()
+            .map(|c| format!("{:width$}  {}", c.usage, c.help, width = width))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Completes the command name at the start of the line.
+struct CommandHelper {
+    names: Vec<&'static str>,
+}
+
+impl CommandHelper {
+    fn candidates(&self, line: &str, pos: usize) -> (usize, Vec<String>) {
+        let before = &line[..pos];
+        let start = before.len() - before.trim_start().len();
+        // only the first word is a command
+        if before[start..].contains(char::is_whitespace) {
+            return (pos, Vec::new());
+        }
+        let prefix = &before[start..];
+        let names = self
+            .names
+            .iter()
+            .filter(|name| name.starts_with(prefix))
+            .map(|name| name.to_string())
+            .collect();
+        (start, names)
+    }
+}
+
+impl Completer for CommandHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        Ok(self.candidates(line, pos))
+    }
+}
+
+impl Hinter for CommandHelper {
+    type Hint = String;
+}
+
+impl Highlighter for CommandHelper {}
+
+impl Validator for CommandHelper {}
+
+impl Helper for CommandHelper {}
+
+/// Runs the prompt until the user quits.
+fn prompt<M: Model>(day: u8, model: M) -> Result<()> {
+    let mut session = Session::new(model);
+    let mut editor = Editor::new()?;
+    editor.set_helper(Some(CommandHelper {
+        names: session.command_names(),
+    }));
+    println!("day {} loaded, type help for the commands", day);
+    let prompt = format!("day {}> ", day);
+    loop {
+        let line = match editor.readline(&prompt) {
+            Ok(line) => line,
+            // ctrl-c drops the line, ctrl-d leaves
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+        if !line.trim().is_empty() {
+            editor.add_history_entry(line.as_str())?;
+        }
+        match session.execute(&line) {
+            Ok(Reply::Print(text)) => println!("{}", text),
+            Ok(Reply::Nothing) => {}
+            Ok(Reply::Quit) => return Ok(()),
+            Err(e) => println!("error: {}", e),
+        }
+    }
+}
+
+/// The days with a prompt.
+pub const DAYS: [u8; 7] = [4, 6, 9, 11, 13, 14, 15];
+
+/// Parses `data` as the input for `day` and starts a prompt on it.
+pub fn start(day: u8, data: &str) -> Result<()> {
+    match day {
+        4 => prompt(day, models::Bingo::new(day_4::parse(data).rendered(data)?)),
+        6 => prompt(day, models::Ocean::new(day_6::parse(data).rendered(data)?)),
+        9 => prompt(
+            day,
+            models::HeightMap::new(day_9::parse(data).rendered(data)?),
+        ),
+        11 => prompt(
+            day,
+            models::Octopi::new(day_11::parse(data).rendered(data)?),
+        ),
+        13 => prompt(day, models::Paper::new(day_13::parse(data).rendered(data)?)),
+        14 => prompt(
+            day,
+            models::Polymer::new(day_14::parse(data).rendered(data)?),
+        ),
+        15 => prompt(
+            day,
+            models::RiskMap::new(day_15::parse(data).rendered(data)?),
+        ),
+        _ => {
+            let days: Vec<_> = DAYS.iter().map(u8::to_string).collect();
+            Err(anyhow!(
+                "no prompt for day {}, only for days {}",
+                day,
+                days.join(", ")
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn print(session: &mut Session<impl Model>, line: &str) -> String {
+        match session.execute(line).unwrap() {
+            Reply::Print(text) => text,
+            reply => panic!("expected text for {:?}, got {:?}", line, reply),
+        }
+    }
+
+    #[test]
+    fn test_undo() {
+        let ocean = day_6::parse("3,4,3,1,2").unwrap();
+        let mut session = Session::new(models::Ocean::new(ocean));
+        assert_eq!(print(&mut session, "score"), "day 0, 5 fish");
+        print(&mut session, "step 18");
+        print(&mut session, "step");
+        assert_eq!(print(&mut session, "score"), "day 19, 29 fish");
+        print(&mut session, "undo");
+        assert_eq!(print(&mut session, "score"), "day 18, 26 fish");
+        print(&mut session, "undo");
+        assert!(session.execute("undo").is_err());
+        assert_eq!(print(&mut session, "score"), "day 0, 5 fish");
+    }
+
+    #[test]
+    fn test_errors() {
+        let ocean = day_6::parse("3,4,3,1,2").unwrap();
+        let mut session = Session::new(models::Ocean::new(ocean));
+        assert_eq!(session.execute("   ").unwrap(), Reply::Nothing);
+        assert_eq!(session.execute("quit").unwrap(), Reply::Quit);
+        let err = session.execute("fold").unwrap_err();
+        assert_eq!(err.to_string(), "unknown command \"fold\", try help");
+        assert!(session.execute("step lots").is_err());
+        // a failed command is not an undo point
+        assert!(session.execute("undo").is_err());
+        assert!(print(&mut session, "help").starts_with("step [n]"));
+    }
+
+    #[test]
+    fn test_completion() {
+        let helper = CommandHelper {
+            names: vec!["step", "score", "show"],
+        };
+        assert_eq!(
+            helper.candidates("s", 1),
+            (
+                0,
+                vec![
+                    String::from("step"),
+                    String::from("score"),
+                    String::from("show")
+                ]
+            )
+        );
+        assert_eq!(
+            helper.candidates("  sc", 4),
+            (2, vec![String::from("score")])
+        );
+        assert_eq!(helper.candidates("step 1", 6), (6, Vec::new()));
+    }
+}
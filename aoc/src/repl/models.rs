@@ -0,0 +1,538 @@
+//! The prompt's view of each day's state.
+use super::{Command, Model};
+use anyhow::{anyhow, Result};
+use common::puzzle::MAX_REPEAT;
+use day_13::FoldInstruction;
+use day_4::CallNumber;
+use grid::Grid;
+use std::collections::BTreeMap;
+
+const STEP: Command = Command {
+    name: "step",
+    usage: "step [n]",
+    help: "move on n steps, 1 if not given",
+    changes: true,
+};
+
+const NEIGHBORS: Command = Command {
+    name: "neighbors",
+    usage: "neighbors <index>",
+    help: "print the cells next to the cell at a grid index",
+    changes: false,
+};
+
+/// The optional repeat count that `step` and `fold` take.
+fn repeat(args: &[&str]) -> Result<u64> {
+    match args {
+        [] => Ok(1),
+        [n] => match n.parse::<u64>() {
+            Ok(n @ 1..=MAX_REPEAT) => Ok(n),
+            _ => Err(anyhow!(
+                "expected a count from 1 to {}, got {:?}",
+                MAX_REPEAT,
+                n
+            )),
+        },
+        _ => Err(anyhow!("expected at most one count")),
+    }
+}
+
+/// One grid index, checked against the grid.
+fn index<T>(grid: &Grid<T>, args: &[&str]) -> Result<usize> {
+    let arg = match args {
+        [arg] => arg,
+        _ => return Err(anyhow!("expected one grid index")),
+    };
+    match arg.parse::<usize>() {
+        Ok(i) if i < grid.len() => Ok(i),
+        _ => Err(anyhow!(
+            "expected an index below {} ({} by {}), got {:?}",
+            grid.len(),
+            grid.row_len(),
+            grid.col_len(),
+            arg
+        )),
+    }
+}
+
+fn show_grid(grid: &Grid<u32>) -> String {
+    grid.rows()
+        .map(|row| row.iter().map(|v| v.to_string()).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The cell at `at` followed by each of `neighbors`, with their positions.
+fn show_neighbors(grid: &Grid<u32>, at: usize, neighbors: impl Iterator<Item = usize>) -> String {
+    let describe = |i: usize| {
+        let (x, y) = grid.to_xy(i);
+        format!("{} at {},{}: {}", i, x, y, grid.cells()[i])
+    };
+    let mut lines = vec![describe(at)];
+    lines.extend(neighbors.map(|i| format!("  {}", describe(i))));
+    lines.join("\n")
+}
+
+/// Day 4: the boards, with numbers called from the draw order or by hand.
+#[derive(Clone)]
+pub struct Bingo {
+    bingo: day_4::Bingo,
+    /// Where the draw order has got to.
+    next: usize,
+    called: Vec<i32>,
+}
+
+impl Bingo {
+    pub fn new(bingo: day_4::Bingo) -> Self {
+        Bingo {
+            bingo,
+            next: 0,
+            called: Vec::new(),
+        }
+    }
+}
+
+impl Model for Bingo {
+    fn commands(&self) -> &'static [Command] {
+        &[Command {
+            name: "call",
+            usage: "call [number]",
+            help: "call a number, the next in the draw order if not given",
+            changes: true,
+        }]
+    }
+
+    fn show(&self) -> String {
+        let mut out = match self.bingo.numbers.get(self.next) {
+            Some(next) => format!("{} called, next in the draw is {}", self.called.len(), next),
+            None => format!("{} called, the draw is over", self.called.len()),
+        };
+        for (i, board) in self.bingo.boards.iter().enumerate() {
+            out += &format!("\n\nboard {}", i + 1);
+            if let Some(score) = board.score() {
+                out += &format!(", won with score {}", score);
+            }
+            for row in board.rows() {
+                out.push('\n');
+                for square in row {
+                    let mark = if square.is_checked { '*' } else { ' ' };
+                    out += &format!("{:>3}{}", square.number, mark);
+                }
+            }
+        }
+        out
+    }
+
+    fn score(&self) -> String {
+        let won: Vec<_> = self
+            .bingo
+            .boards
+            .iter()
+            .enumerate()
+            .filter_map(|(i, board)| Some(format!("board {}: {}", i + 1, board.score()?)))
+            .collect();
+        match won.is_empty() {
+            true => String::from("no board has won yet"),
+            false => won.join("\n"),
+        }
+    }
+
+    fn run(&mut self, _command: &str, args: &[&str]) -> Result<String> {
+        let number = match args {
+            [] => *self
+                .bingo
+                .numbers
+                .get(self.next)
+                .ok_or_else(|| anyhow!("every number in the draw has been called"))?,
+            [n] => n
+                .parse()
+                .map_err(|_| anyhow!("expected a number to call, got {:?}", n))?,
+            _ => return Err(anyhow!("expected at most one number")),
+        };
+        if self.bingo.numbers.get(self.next) == Some(&number) {
+            self.next += 1;
+        }
+        self.called.push(number);
+        let mut out = format!("called {}", number);
+        for (i, board) in self.bingo.boards.iter_mut().enumerate() {
+            let had_won = board.is_winner();
+            board.call_number(number);
+            if let (false, Some(score)) = (had_won, board.score()) {
+                out += &format!("\nboard {} wins with score {}", i + 1, score);
+            }
+        }
+        Ok(out)
+    }
+}
+
+/// Day 6: the count of fish at each timer value.
+#[derive(Clone)]
+pub struct Ocean {
+    ocean: day_6::Ocean,
+    day: u64,
+}
+
+impl Ocean {
+    pub fn new(ocean: day_6::Ocean) -> Self {
+        Ocean { ocean, day: 0 }
+    }
+}
+
+impl Model for Ocean {
+    fn commands(&self) -> &'static [Command] {
+        &[STEP]
+    }
+
+    fn show(&self) -> String {
+        (0..9)
+            .map(|age| format!("timer {}: {}", age, self.ocean.count(age)))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn score(&self) -> String {
+        format!("day {}, {} fish", self.day, self.ocean.total())
+    }
+
+    fn run(&mut self, _command: &str, args: &[&str]) -> Result<String> {
+        for _ in 0..repeat(args)? {
+            if self.ocean.checked_step_time().is_none() {
+                return Err(anyhow!("the fish outgrow a u64 after day {}", self.day));
+            }
+            self.day += 1;
+        }
+        Ok(self.score())
+    }
+}
+
+/// Day 9: the height map, which only answers questions.
+#[derive(Clone)]
+pub struct HeightMap {
+    cave: day_9::Cave,
+}
+
+impl HeightMap {
+    pub fn new(cave: day_9::Cave) -> Self {
+        HeightMap { cave }
+    }
+}
+
+impl Model for HeightMap {
+    fn commands(&self) -> &'static [Command] {
+        &[NEIGHBORS]
+    }
+
+    fn show(&self) -> String {
+        show_grid(self.cave.floor())
+    }
+
+    fn score(&self) -> String {
+        format!(
+            "risk level {}, largest basins multiply to {}",
+            day_9::part_1(&self.cave),
+            day_9::part_2(&self.cave)
+        )
+    }
+
+    fn run(&mut self, _command: &str, args: &[&str]) -> Result<String> {
+        let floor = self.cave.floor();
+        let at = index(floor, args)?;
+        Ok(show_neighbors(floor, at, floor.neighbors4(at)))
+    }
+}
+
+/// Day 11: the octopi, with a tally of their flashes.
+#[derive(Clone)]
+pub struct Octopi {
+    octopi: day_11::Octopi,
+    step: u64,
+    flashes: u64,
+    /// The first step every octopus flashed on.
+    synced: Option<u64>,
+}
+
+impl Octopi {
+    pub fn new(octopi: day_11::Octopi) -> Self {
+        Octopi {
+            octopi,
+            step: 0,
+            flashes: 0,
+            synced: None,
+        }
+    }
+}
+
+impl Model for Octopi {
+    fn commands(&self) -> &'static [Command] {
+        &[STEP, NEIGHBORS]
+    }
+
+    fn show(&self) -> String {
+        show_grid(&self.octopi.energy_grid())
+    }
+
+    fn score(&self) -> String {
+        let mut out = format!("step {}, {} flashes", self.step, self.flashes);
+        if let Some(step) = self.synced {
+            out += &format!(", all flashed together first at step {}", step);
+        }
+        out
+    }
+
+    fn run(&mut self, command: &str, args: &[&str]) -> Result<String> {
+        if command == NEIGHBORS.name {
+            let energy = self.octopi.energy_grid();
+            let at = index(&energy, args)?;
+            return Ok(show_neighbors(&energy, at, energy.neighbors8(at)));
+        }
+        let mut last = 0;
+        for _ in 0..repeat(args)? {
+            last = self.octopi.step();
+            self.step += 1;
+            self.flashes += last;
+            if last == self.octopi.len() as u64 && self.synced.is_none() {
+                self.synced = Some(self.step);
+            }
+        }
+        Ok(format!("{}, {} in the last step", self.score(), last))
+    }
+}
+
+/// Day 13: the paper and the folds still to make.
+#[derive(Clone)]
+pub struct Paper {
+    paper: day_13::Paper,
+    folds: usize,
+}
+
+impl Paper {
+    pub fn new(paper: day_13::Paper) -> Self {
+        Paper { paper, folds: 0 }
+    }
+}
+
+impl Model for Paper {
+    fn commands(&self) -> &'static [Command] {
+        &[Command {
+            name: "fold",
+            usage: "fold [n]",
+            help: "make the next n folds, 1 if not given",
+            changes: true,
+        }]
+    }
+
+    fn show(&self) -> String {
+        self.paper.render()
+    }
+
+    fn score(&self) -> String {
+        format!(
+            "{} dots after {} folds, {} to go",
+            self.paper.count_dots(),
+            self.folds,
+            self.paper.instructions.len()
+        )
+    }
+
+    fn run(&mut self, _command: &str, args: &[&str]) -> Result<String> {
+        let mut out = Vec::new();
+        for _ in 0..repeat(args)? {
+            let fold = match self.paper.instructions.front() {
+                Some(FoldInstruction::FoldY(y)) => format!("folded up along y={}", y),
+                Some(FoldInstruction::FoldX(x)) => format!("folded left along x={}", x),
+                None => return Err(anyhow!("there are no folds left")),
+            };
+            self.paper.fold();
+            self.folds += 1;
+            out.push(fold);
+        }
+        out.push(self.score());
+        Ok(out.join("\n"))
+    }
+}
+
+/// Day 14: the pair counts of the polymer.
+#[derive(Clone)]
+pub struct Polymer {
+    polymer: day_14::Polymer,
+    step: u64,
+}
+
+impl Polymer {
+    pub fn new(polymer: day_14::Polymer) -> Self {
+        Polymer { polymer, step: 0 }
+    }
+
+    /// How many times each element occurs. Every element but the last
+    /// starts exactly one pair.
+    fn elements(&self) -> BTreeMap<char, u64> {
+        let mut elements = BTreeMap::from([(self.polymer.end, 1)]);
+        for (pair, &count) in self.polymer.counter.iter() {
+            if let Some(first) = pair.chars().next() {
+                *elements.entry(first).or_insert(0) += count;
+            }
+        }
+        elements
+    }
+}
+
+impl Model for Polymer {
+    fn commands(&self) -> &'static [Command] {
+        &[STEP]
+    }
+
+    fn show(&self) -> String {
+        self.elements()
+            .iter()
+            .filter(|(_, &count)| count > 0)
+            .map(|(element, count)| format!("{}: {}", element, count))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn score(&self) -> String {
+        format!("step {}, score {}", self.step, self.polymer.score())
+    }
+
+    fn run(&mut self, _command: &str, args: &[&str]) -> Result<String> {
+        for _ in 0..repeat(args)? {
+            if self.polymer.checked_step().is_none() {
+                return Err(anyhow!(
+                    "the polymer outgrows a u64 after step {}",
+                    self.step
+                ));
+            }
+            self.step += 1;
+        }
+        Ok(self.score())
+    }
+}
+
+/// Day 15: the risk levels, which only answer questions.
+#[derive(Clone)]
+pub struct RiskMap {
+    cave: day_15::Cave,
+}
+
+impl RiskMap {
+    pub fn new(cave: day_15::Cave) -> Self {
+        RiskMap { cave }
+    }
+}
+
+impl Model for RiskMap {
+    fn commands(&self) -> &'static [Command] {
+        &[NEIGHBORS]
+    }
+
+    fn show(&self) -> String {
+        show_grid(self.cave.floor())
+    }
+
+    fn score(&self) -> String {
+        match self.cave.dijkstra_alg(0) {
+            Some(risk) => format!("lowest total risk {}", risk),
+            None => String::from("the far corner can't be reached"),
+        }
+    }
+
+    fn run(&mut self, _command: &str, args: &[&str]) -> Result<String> {
+        let floor = self.cave.floor();
+        let at = index(floor, args)?;
+        Ok(show_neighbors(floor, at, floor.neighbors4(at)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::{Reply, Session};
+    use super::*;
+
+    fn print(session: &mut Session<impl Model>, line: &str) -> String {
+        match session.execute(line).unwrap() {
+            Reply::Print(text) => text,
+            reply => panic!("expected text for {:?}, got {:?}", line, reply),
+        }
+    }
+
+    #[test]
+    fn test_bingo() {
+        let bingo = day_4::parse(include_str!("../../../day-4/src/test.txt")).unwrap();
+        let mut session = Session::new(Bingo::new(bingo));
+        assert_eq!(print(&mut session, "call"), "called 7");
+        for _ in 0..10 {
+            print(&mut session, "call");
+        }
+        assert_eq!(
+            print(&mut session, "call"),
+            "called 24\nboard 3 wins with score 4512"
+        );
+        assert_eq!(print(&mut session, "score"), "board 3: 4512");
+        assert!(print(&mut session, "show").contains(" 14* 21* 17* 24*  4*"));
+        print(&mut session, "undo");
+        assert_eq!(print(&mut session, "score"), "no board has won yet");
+        assert_eq!(print(&mut session, "call 99"), "called 99");
+        assert!(session.execute("call x").is_err());
+    }
+
+    #[test]
+    fn test_octopi() {
+        let octopi = day_11::parse(include_str!("../../../day-11/data/test.txt")).unwrap();
+        let mut session = Session::new(Octopi::new(octopi));
+        assert_eq!(
+            print(&mut session, "step 10"),
+            "step 10, 204 flashes, 29 in the last step"
+        );
+        print(&mut session, "step 185");
+        assert!(print(&mut session, "score").ends_with("first at step 195"));
+        assert_eq!(
+            print(&mut session, "neighbors 0"),
+            "0 at 0,0: 0\n  1 at 1,0: 0\n  10 at 0,1: 0\n  11 at 1,1: 0"
+        );
+        assert!(session.execute("neighbors 100").is_err());
+    }
+
+    #[test]
+    fn test_paper() {
+        let paper = day_13::parse(include_str!("../../../day-13/data/test.txt")).unwrap();
+        let mut session = Session::new(Paper::new(paper));
+        assert_eq!(
+            print(&mut session, "fold"),
+            "folded up along y=7\n17 dots after 1 folds, 1 to go"
+        );
+        print(&mut session, "fold");
+        assert!(print(&mut session, "show").starts_with("#####\n#...#"));
+        let err = session.execute("fold").unwrap_err();
+        assert_eq!(err.to_string(), "there are no folds left");
+    }
+
+    #[test]
+    fn test_polymer() {
+        let polymer = day_14::parse(include_str!("../../../day-14/data/test.txt")).unwrap();
+        let mut session = Session::new(Polymer::new(polymer));
+        assert_eq!(print(&mut session, "show"), "B: 1\nC: 1\nN: 2");
+        assert_eq!(print(&mut session, "step 10"), "step 10, score 1588");
+        print(&mut session, "step 30");
+        assert_eq!(print(&mut session, "score"), "step 40, score 2188189693529");
+        assert!(session.execute("step 100").is_err());
+        assert_eq!(print(&mut session, "score"), "step 40, score 2188189693529");
+    }
+
+    #[test]
+    fn test_grids() {
+        let cave = day_9::parse(include_str!("../../../day-9/data/test.txt")).unwrap();
+        let mut session = Session::new(HeightMap::new(cave));
+        assert_eq!(
+            print(&mut session, "score"),
+            "risk level 15, largest basins multiply to 1134"
+        );
+        assert_eq!(
+            print(&mut session, "neighbors 11"),
+            "11 at 1,1: 9\n  10 at 0,1: 3\n  12 at 2,1: 8\n  1 at 1,0: 1\n  21 at 1,2: 8"
+        );
+        let cave = day_15::parse(include_str!("../../../day-15/data/test.txt")).unwrap();
+        let mut session = Session::new(RiskMap::new(cave));
+        assert_eq!(print(&mut session, "score"), "lowest total risk 40");
+        assert!(session.execute("step").is_err());
+    }
+}
@@ -0,0 +1,132 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::io::Write;
+
+use crate::days::Solution;
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// One line per answer, for people
+    Human,
+    /// A JSON array with one object per answer
+    Json,
+    /// CSV with a header row and one row per answer
+    Csv,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum InputKind {
+    /// One of the puzzle's worked examples
+    Example,
+    /// A full puzzle input
+    Real,
+}
+
+/// Everything the dashboards need to know about one solved part.
+#[derive(Debug, Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub parse_time_ns: u64,
+    pub solve_time_ns: u64,
+    pub input_sha256: String,
+    pub input: InputKind,
+}
+
+impl Record {
+    pub fn new(day: u8, part: u8, solution: Solution, fingerprint: &str, input: InputKind) -> Self {
+        let nanos = |d: std::time::Duration| u64::try_from(d.as_nanos()).unwrap_or(u64::MAX);
+        Record {
+            day,
+            part,
+            answer: solution.answer,
+            parse_time_ns: nanos(solution.parse_time),
+            solve_time_ns: nanos(solution.solve_time),
+            input_sha256: fingerprint.to_string(),
+            input,
+        }
+    }
+}
+
+/// Hex encoded SHA-256 of an input, so results can be matched to inputs.
+pub fn fingerprint(data: &str) -> String {
+    format!("{:x}", Sha256::digest(data.as_bytes()))
+}
+
+/// Writes `records` as JSON or CSV. Human output is printed as the answers
+/// come in instead, so there is nothing left to write for it here.
+pub fn write<W: Write>(records: &[Record], format: Format, mut out: W) -> Result<()> {
+    match format {
+        Format::Human => {}
+        Format::Json => {
+            serde_json::to_writer_pretty(&mut out, records)?;
+            writeln!(out)?;
+        }
+        Format::Csv => {
+            let mut writer = csv::Writer::from_writer(out);
+            for record in records {
+                writer.serialize(record)?;
+            }
+            writer.flush()?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    fn record() -> Record {
+        let solution = Solution {
+            answer: String::from("7"),
+            parse_time: Duration::from_micros(3),
+            solve_time: Duration::from_nanos(1500),
+        };
+        Record::new(
+            1,
+            1,
+            solution,
+            &fingerprint("199\n200\n"),
+            InputKind::Example,
+        )
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        assert_eq!(
+            fingerprint(""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let mut out = Vec::new();
+        write(&[record()], Format::Json, &mut out).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(value[0]["day"], 1);
+        assert_eq!(value[0]["answer"], "7");
+        assert_eq!(value[0]["parse_time_ns"], 3000);
+        assert_eq!(value[0]["solve_time_ns"], 1500);
+        assert_eq!(value[0]["input"], "example");
+    }
+
+    #[test]
+    fn test_csv() {
+        let mut out = Vec::new();
+        write(&[record()], Format::Csv, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let mut lines = out.lines();
+        assert_eq!(
+            lines.next(),
+            Some("day,part,answer,parse_time_ns,solve_time_ns,input_sha256,input")
+        );
+        assert!(lines.next().unwrap().starts_with("1,1,7,3000,1500,"));
+    }
+}
@@ -0,0 +1,399 @@
+//! Looking at day 1's sonar readings with the survey team's own settings,
+//! rather than just the two puzzle answers.
+use anyhow::Result;
+use common::Rendered;
+use day_1::anomaly::{self, Detector};
+use day_1::log::{self, Fill, Format, GapFiller, Gaps, Reading};
+use day_1::trend::{self, Summary};
+use day_1::{Analysis, Changes, Monitor, StreamError};
+use std::io::{BufRead, Write};
+
+/// Reads the readings in a depth log.
+pub fn readings(data: &str, format: &Format) -> Result<Vec<Reading>> {
+    log::parse_log(data, format).rendered(data)
+}
+
+/// How the windows over the depths in `readings` change, with `gaps` dealt
+/// with as asked, as a small table.
+pub fn analyze(analysis: Analysis, readings: &[Reading], gaps: Gaps) -> String {
+    let segments = gaps.apply(readings);
+    let changes: Changes = segments
+        .iter()
+        .map(|s| analysis.run(s.iter().copied()))
+        .sum();
+    let window = match analysis.window() {
+        1 => "single readings".to_string(),
+        n => format!("windows of {} readings ({})", n, analysis.aggregate()),
+    };
+    let against = match analysis.lag() {
+        1 => "the one before".to_string(),
+        n => format!("the one {} back", n),
+    };
+    let missing = readings.iter().filter(|r| r.depth.is_none()).count();
+    let mut out = format!(
+        "{} readings, {} against {}",
+        readings.len() - missing,
+        window,
+        against
+    );
+    if missing > 0 {
+        let dealt = match gaps {
+            Gaps::Skip => "skipped".to_string(),
+            Gaps::Interpolate => "interpolated".to_string(),
+            Gaps::Break => format!("broken into {} segments", segments.len()),
+        };
+        out.push_str(&format!("\n{} gaps, {}", missing, dealt));
+    }
+    let mut times = readings.iter().filter_map(|r| r.time.as_deref());
+    let first = times.next();
+    if let (Some(first), Some(last)) = (first, times.next_back().or(first)) {
+        out.push_str(&format!("\nfrom {} to {}", first, last));
+    }
+    out.push_str(&format!(
+        "\nincreases  {:>8}\n\
+         decreases  {:>8}\n\
+         unchanged  {:>8}",
+        changes.increases, changes.decreases, changes.unchanged
+    ));
+    out
+}
+
+/// The runs in each of `segments`, smoothed by `analysis`, as a summary
+/// table followed by every run if `list` is set. Readings are shown by
+/// their line in the log, and a window by the line of its first reading.
+/// No run spans a gap, so each segment gets its own table.
+pub fn trends(analysis: Analysis, segments: &[Vec<(usize, f64)>], list: bool) -> String {
+    let table = |segment: &[(usize, f64)]| {
+        let depths: Vec<f64> = segment.iter().map(|&(_, depth)| depth).collect();
+        let values = analysis.values(&depths);
+        let lines: Vec<usize> = segment[..values.len()]
+            .iter()
+            .map(|&(line, _)| line)
+            .collect();
+        let mut out = Summary::numbered(&values, &lines).to_string();
+        if list {
+            out.push_str("\n\nchange        from      to");
+            for run in trend::runs(&values) {
+                out.push_str(&format!(
+                    "\n{:<10} {:>7} {:>7}",
+                    run.change, lines[run.start], lines[run.end]
+                ));
+            }
+        }
+        out
+    };
+    match segments {
+        [] => table(&[]),
+        [segment] => table(segment),
+        _ => segments
+            .iter()
+            .enumerate()
+            .map(|(i, segment)| {
+                format!(
+                    "segment {} of {}, {} readings from line {}\n{}",
+                    i + 1,
+                    segments.len(),
+                    segment.len(),
+                    segment[0].0,
+                    table(segment)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+    }
+}
+
+/// Every reading any of `detectors` flags, by its line in the log, and what
+/// counting the increases would give without them. Detectors look within
+/// one segment at a time.
+pub fn anomalies(segments: &[Vec<(usize, f64)>], detectors: &[Detector]) -> String {
+    let depths: Vec<Vec<f64>> = segments
+        .iter()
+        .map(|segment| segment.iter().map(|&(_, depth)| depth).collect())
+        .collect();
+    let found: Vec<_> = depths
+        .iter()
+        .map(|depths| anomaly::detect_all(depths, detectors))
+        .collect();
+    let mut out = format!(
+        "{:>7} {:>10}  {:<10} {:>8}",
+        "line", "depth", "detector", "score"
+    );
+    for (segment, flagged) in segments.iter().zip(found.iter()) {
+        for a in flagged {
+            let (line, depth) = segment[a.index];
+            out.push_str(&format!(
+                "\n{:>7} {:>10}  {:<10} {:>8.2}",
+                line, depth, a.kind, a.score
+            ));
+        }
+    }
+    let increases = |segments: Vec<Vec<f64>>| {
+        let changes: Changes = segments
+            .into_iter()
+            .map(|depths| Analysis::default().run(depths))
+            .sum();
+        changes.increases
+    };
+    let cleaned: Vec<_> = depths
+        .iter()
+        .zip(found.iter())
+        .map(|(depths, flagged)| anomaly::without(depths, flagged))
+        .collect();
+    let readings: usize = depths.iter().map(Vec::len).sum();
+    // a reading more than one detector flags counts once
+    let flagged = readings - cleaned.iter().map(Vec::len).sum::<usize>();
+    out.push_str(&format!(
+        "\n{} of {} readings flagged\nincreases {}, or {} without them",
+        flagged,
+        readings,
+        increases(depths),
+        increases(cleaned)
+    ));
+    out
+}
+
+/// Feeds `monitor` each depth in a log as it arrives, with `gaps` dealt
+/// with as asked, writing a line to `out` for every reading, alert and
+/// break. Lines that aren't readings are reported on `errors` and skipped,
+/// so one bad reading doesn't stop a live feed.
+pub fn monitor<R, W, E>(
+    monitor: &mut Monitor,
+    reader: R,
+    format: &Format,
+    gaps: Gaps,
+    out: &mut W,
+    errors: &mut E,
+) -> Result<()>
+where
+    R: BufRead,
+    W: Write,
+    E: Write,
+{
+    let readings = log::read_log(reader, format)?;
+    let mut filler = GapFiller::new(gaps);
+    writeln!(out, "reading\tdepth\twindow\tchange\tincreases")?;
+    for reading in readings {
+        let reading = match reading {
+            Ok(reading) => reading,
+            Err(StreamError::Parse(e)) => {
+                writeln!(errors, "skipping {}", e)?;
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        };
+        for fill in filler.push(reading.depth) {
+            let depth = match fill {
+                Fill::Depth(depth) => depth,
+                Fill::Break => {
+                    monitor.restart();
+                    writeln!(out, "break\tline {}", reading.line)?;
+                    continue;
+                }
+            };
+            let update = monitor.push(depth);
+            writeln!(out, "{}", update)?;
+            for alert in update.alerts.iter() {
+                writeln!(out, "alert\t{}\t{}", update.reading, alert)?;
+            }
+        }
+        out.flush()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use day_1::log::Column;
+    use day_1::{Aggregate, Thresholds};
+
+    #[test]
+    fn test_analyze() {
+        let data = include_str!("../../day-1/src/depths_test.csv");
+        let plain = readings(data, &Format::default()).unwrap();
+        let analysis = Analysis::new(3, Aggregate::Sum, 1).unwrap();
+        let report = analyze(analysis, &plain, Gaps::Skip);
+        let lines: Vec<_> = report.lines().collect();
+        assert_eq!(
+            lines[0],
+            "10 readings, windows of 3 readings (sum) against the one before"
+        );
+        assert_eq!(lines[1], "increases         5");
+        assert_eq!(lines[3], "unchanged         1");
+        assert!(readings("199\nfive\n", &Format::default()).is_err());
+        let format = Format {
+            delimiter: ' ',
+            time: Some(Column::Index(1)),
+            depth: Column::Index(3),
+            header: false,
+        };
+        let log = "t1 a 1.5\nt2 b NaN\nt3 c -2\nt4 d 4.5\n";
+        let report = analyze(
+            Analysis::default(),
+            &readings(log, &format).unwrap(),
+            Gaps::Break,
+        );
+        let lines: Vec<_> = report.lines().collect();
+        assert_eq!(
+            lines[..4],
+            [
+                "3 readings, single readings against the one before",
+                "1 gaps, broken into 2 segments",
+                "from t1 to t4",
+                "increases         1",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_monitor() {
+        let thresholds = Thresholds {
+            rise: None,
+            streak: Some(2),
+        };
+        let mut watch = Monitor::new(Analysis::default(), thresholds);
+        let (mut out, mut errors) = (Vec::new(), Vec::new());
+        let input = "199\n200\nabc\n208\n";
+        let format = Format::default();
+        monitor(
+            &mut watch,
+            input.as_bytes(),
+            &format,
+            Gaps::Skip,
+            &mut out,
+            &mut errors,
+        )
+        .unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<_> = out.lines().collect();
+        assert_eq!(
+            lines[1..],
+            [
+                "1\t199\t199\t-\t0",
+                "2\t200\t200\tincrease\t1",
+                "3\t208\t208\tincrease\t2",
+                "alert\t3\t2 increases in a row",
+            ]
+        );
+        let skipped = String::from_utf8(errors.clone()).unwrap();
+        assert!(skipped.starts_with("skipping line 3, column 1: invalid reading"));
+
+        // a gap restarts the windows but keeps the count
+        let mut watch = Monitor::new(Analysis::default(), Thresholds::default());
+        let mut out = Vec::new();
+        let input = "time,depth\nt1,1.5\nt2,2.5\nt3,NaN\nt4,3\nt5,4\n";
+        let format = Format {
+            depth: Column::Name("depth".to_string()),
+            ..Format::default()
+        };
+        monitor(
+            &mut watch,
+            input.as_bytes(),
+            &format,
+            Gaps::Break,
+            &mut out,
+            &mut errors,
+        )
+        .unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<_> = out.lines().collect();
+        assert_eq!(
+            lines[2..],
+            [
+                "2\t2.5\t2.5\tincrease\t1",
+                "break\tline 4",
+                "3\t3\t3\t-\t1",
+                "4\t4\t4\tincrease\t2",
+            ]
+        );
+        let mut watch = Monitor::new(Analysis::default(), Thresholds::default());
+        let mut out = Vec::new();
+        let unknown = Format {
+            depth: Column::Name("sonar".to_string()),
+            ..Format::default()
+        };
+        let result = monitor(
+            &mut watch,
+            input.as_bytes(),
+            &unknown,
+            Gaps::Skip,
+            &mut out,
+            &mut errors,
+        );
+        assert!(result.is_err() && out.is_empty());
+    }
+
+    fn lined(data: &str, gaps: Gaps) -> Vec<Vec<(usize, f64)>> {
+        gaps.apply_lines(&readings(data, &Format::default()).unwrap())
+    }
+
+    #[test]
+    fn test_trends() {
+        let readings = lined(include_str!("../../day-1/src/depths_test.csv"), Gaps::Skip);
+        let table = trends(Analysis::default(), &readings, true);
+        let lines: Vec<_> = table.lines().collect();
+        assert_eq!(lines[1], "increase        3      7  1..=4 (3 steps)");
+        assert_eq!(
+            lines[6..8],
+            ["change        from      to", "increase         1       4"]
+        );
+        assert_eq!(lines.len(), 12);
+        let table = trends(Analysis::default(), &readings, false);
+        assert_eq!(table.lines().count(), 5);
+        // skipped lines still count, and the second segment carries on
+        // from the log rather than starting again
+        let broken = lined("1\n2\n\n5\n\n4\n3\n", Gaps::Break);
+        let table = trends(Analysis::default(), &broken, true);
+        let lines: Vec<_> = table.lines().collect();
+        assert_eq!(lines[0], "segment 1 of 3, 2 readings from line 1");
+        assert_eq!(lines[10], "segment 2 of 3, 1 readings from line 4");
+        assert_eq!(lines[18], "segment 3 of 3, 2 readings from line 6");
+        assert_eq!(lines[21], "decrease        1      1  6..=7 (1 step)");
+        let skipped = lined("1\n2\n\n5\n\n4\n3\n", Gaps::Skip);
+        let table = trends(Analysis::default(), &skipped, true);
+        assert!(table.ends_with("increase         1       4\ndecrease         4       7"));
+        let smoothed = trends(
+            Analysis::new(2, Aggregate::Mean, 1).unwrap(),
+            &skipped,
+            true,
+        );
+        assert!(smoothed.contains("steepest step 1.5 -> 3.5 (+2) at 2"));
+    }
+
+    #[test]
+    fn test_anomalies() {
+        let readings = lined(include_str!("../../day-1/src/depths_test.csv"), Gaps::Skip);
+        let detectors = [Detector::Drop(8.0), Detector::Spike(5.0)];
+        let report = anomalies(&readings, &detectors);
+        let lines: Vec<_> = report.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "   line      depth  detector      score",
+                "      5        200  drop          10.00",
+                "      5        200  spike          7.00",
+                "      8        269  spike          9.00",
+                "      9        260  drop           9.00",
+                "3 of 10 readings flagged",
+                "increases 7, or 5 without them",
+            ]
+        );
+        // no drop is seen across the gap, and readings are reported by
+        // their line in the log
+        for gaps in [Gaps::Skip, Gaps::Break] {
+            let report = anomalies(
+                &lined("10\n20\n\n25\n15\n1\n", gaps),
+                &[Detector::Drop(8.0)],
+            );
+            let lines: Vec<_> = report.lines().collect();
+            assert_eq!(lines[1], "      5         15  drop          10.00");
+            assert_eq!(lines[2], "      6          1  drop          14.00");
+        }
+        let broken = lined("10\n20\n\n5\n15\n1\n", Gaps::Break);
+        let report = anomalies(&broken, &[Detector::Drop(8.0)]);
+        let lines: Vec<_> = report.lines().collect();
+        assert_eq!(lines[1], "      6          1  drop          14.00");
+        assert_eq!(lines[2], "1 of 5 readings flagged");
+    }
+}
@@ -0,0 +1,62 @@
+//! Solving the days that can read their input a line at a time, so huge
+//! generated inputs and pipes never have to fit in memory.
+use anyhow::{anyhow, Result};
+use common::Answer;
+use std::io::BufRead;
+
+/// The days that can be streamed.
+pub const DAYS: [u8; 4] = [1, 2, 8, 10];
+
+/// Fails for the days that can't be streamed.
+pub fn check_day(day: u8) -> Result<()> {
+    if DAYS.contains(&day) {
+        return Ok(());
+    }
+    let days: Vec<_> = DAYS.iter().map(u8::to_string).collect();
+    Err(anyhow!(
+        "day {} can't be streamed, only days {}",
+        day,
+        days.join(", ")
+    ))
+}
+
+/// Solves one part of `day`, reading the input from `reader` as it goes.
+pub fn solve<R: BufRead>(day: u8, part: u8, reader: R) -> Result<String> {
+    check_day(day)?;
+    let answer = match (day, part) {
+        (1, 1) => day_1::part_1_streamed(reader)?.answer(),
+        (1, _) => day_1::part_2_streamed(reader)?.answer(),
+        (2, 1) => day_2::follow_course_streamed(reader)?.product().answer(),
+        (2, _) => day_2::follow_course_with_aim_streamed(reader)?
+            .product()
+            .answer(),
+        (8, 1) => day_8::part_1_streamed(reader)?.answer(),
+        (8, _) => day_8::part_2_streamed(reader)?.answer(),
+        (10, 1) => day_10::part_1_streamed(reader)?.answer(),
+        _ => day_10::part_2_streamed(reader)?.answer(),
+    };
+    answer.ok_or_else(|| anyhow!("part {} has no answer for this input", part))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn test_matches_run() {
+        let examples = [
+            (1, include_str!("../../day-1/src/depths_test.csv")),
+            (2, include_str!("../../day-2/src/test_course.txt")),
+            (8, include_str!("../../day-8/data/test.txt")),
+            (10, include_str!("../../day-10/data/test.txt")),
+        ];
+        for (day, data) in examples {
+            for part in 1..=2 {
+                let expected = days::find(day).unwrap().solver(part)(data).unwrap();
+                assert_eq!(solve(day, part, data.as_bytes()).unwrap(), expected.answer);
+            }
+        }
+        assert!(solve(3, 1, "".as_bytes()).is_err());
+    }
+}
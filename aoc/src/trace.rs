@@ -0,0 +1,164 @@
+//! Optional tracing of where the time goes. Every day is traced through
+//! `parse` and `solve` spans, and the slower days add spans and counters of
+//! their own inside those, at debug and trace level.
+use anyhow::{anyhow, Context, Result};
+use clap::ValueEnum;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+use tracing::Subscriber;
+use tracing_flame::{FlameLayer, FlushGuard};
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::{EnvFilter, Registry};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TraceFormat {
+    /// One line per finished span, with its timings and counters
+    Human,
+    /// The same as JSON objects, one per line
+    Json,
+    /// Folded stacks for flamegraph tools, written to the trace output file
+    Folded,
+}
+
+/// Keeps the folded stacks flowing to their file until it is dropped.
+pub struct Guard(Option<FlushGuard<BufWriter<File>>>);
+
+impl Guard {
+    pub fn flush(self) -> Result<()> {
+        if let Some(guard) = self.0 {
+            guard.flush()?;
+        }
+        Ok(())
+    }
+}
+
+type BoxedSubscriber = Box<dyn Subscriber + Send + Sync>;
+
+/// Builds a subscriber showing the spans `filter` lets through. Human and
+/// JSON output goes to `writer`, folded stacks to the file at `folded`.
+pub fn subscriber<W>(
+    format: TraceFormat,
+    filter: &str,
+    writer: W,
+    folded: &Path,
+) -> Result<(BoxedSubscriber, Guard)>
+where
+    W: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+{
+    let filter = EnvFilter::try_new(filter)
+        .map_err(|e| anyhow!("invalid trace filter {:?}: {}", filter, e))?;
+    let registry = Registry::default().with(filter);
+    let fmt = tracing_subscriber::fmt::layer()
+        .with_writer(writer)
+        .with_span_events(FmtSpan::CLOSE);
+    Ok(match format {
+        TraceFormat::Human => (Box::new(registry.with(fmt.with_ansi(false))), Guard(None)),
+        TraceFormat::Json => (Box::new(registry.with(fmt.json())), Guard(None)),
+        TraceFormat::Folded => {
+            let (flame, guard) = FlameLayer::with_file(folded)
+                .with_context(|| format!("failed to create {}", folded.display()))?;
+            let flame = flame.with_threads_collapsed(true).with_file_and_line(false);
+            (Box::new(registry.with(flame)), Guard(Some(guard)))
+        }
+    })
+}
+
+/// Installs a subscriber for the rest of the run, writing to stderr so the
+/// answers on stdout stay as they are.
+pub fn init(format: TraceFormat, filter: &str, folded: &Path) -> Result<Guard> {
+    let (subscriber, guard) = subscriber(format, filter, std::io::stderr, folded)?;
+    tracing::subscriber::set_global_default(subscriber)?;
+    Ok(guard)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::days;
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    /// Collects everything written to it.
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for Buffer {
+        type Writer = Buffer;
+
+        fn make_writer(&'a self) -> Buffer {
+            self.clone()
+        }
+    }
+
+    impl Buffer {
+        fn text(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    fn solve_traced(format: TraceFormat, filter: &str, folded: &Path) -> (String, Guard) {
+        let buffer = Buffer::default();
+        let (subscriber, guard) = subscriber(format, filter, buffer.clone(), folded).unwrap();
+        tracing::subscriber::with_default(subscriber, || {
+            let data = include_str!("../../day-15/data/test.txt");
+            let solution = days::find(15).unwrap().solver(1)(data).unwrap();
+            assert_eq!(solution.answer, "40");
+        });
+        (buffer.text(), guard)
+    }
+
+    #[test]
+    fn test_human() {
+        let (text, _) = solve_traced(TraceFormat::Human, "debug", Path::new(""));
+        let lines: Vec<_> = text.lines().collect();
+        assert!(lines[0].contains("parse{day=15 bytes=110}: common::puzzle: close"));
+        assert!(lines[1].contains("solve{day=15 part=1}:dijkstra{cells=100 pops="));
+        assert!(lines[1].contains("pushes="));
+        assert!(lines[2].contains("solve{day=15 part=1}: common::puzzle: close"));
+        // the inner spans are left out at info level
+        let (text, _) = solve_traced(TraceFormat::Human, "info", Path::new(""));
+        assert_eq!(text.lines().count(), 2);
+    }
+
+    #[test]
+    fn test_json() {
+        let (text, _) = solve_traced(TraceFormat::Json, "debug", Path::new(""));
+        let dijkstra: serde_json::Value =
+            serde_json::from_str(text.lines().nth(1).unwrap()).unwrap();
+        assert_eq!(dijkstra["span"]["name"], "dijkstra");
+        assert_eq!(dijkstra["span"]["cells"], 100);
+        assert!(dijkstra["span"]["settled"].as_u64().unwrap() <= 100);
+    }
+
+    #[test]
+    fn test_folded() {
+        let path = std::env::temp_dir().join(format!("aoc-trace-{}.folded", std::process::id()));
+        let (_, guard) = solve_traced(TraceFormat::Folded, "debug", &path);
+        guard.flush().unwrap();
+        let folded = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        // `stack;of;spans microseconds`, one per line
+        assert!(folded
+            .lines()
+            .any(|l| l.starts_with("all-threads; common::puzzle::solve; day_15::dijkstra ")));
+    }
+
+    #[test]
+    fn test_bad_filter() {
+        let built = subscriber(TraceFormat::Human, "=[", std::io::stderr, Path::new(""));
+        assert!(built.is_err());
+    }
+}
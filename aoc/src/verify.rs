@@ -0,0 +1,206 @@
+use anyhow::{anyhow, Context, Result};
+use common::input::Source;
+use serde::Deserialize;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::days;
+use crate::report::{self, InputKind};
+
+/// The expected answers, as read from `answers.toml`.
+#[derive(Debug, Deserialize)]
+pub struct Registry {
+    #[serde(rename = "answer", default)]
+    pub answers: Vec<Expected>,
+}
+
+/// The known answers for one day on one input.
+#[derive(Debug, Deserialize)]
+pub struct Expected {
+    pub day: u8,
+    /// Example input, relative to the registry. `None` means the real input.
+    pub input: Option<PathBuf>,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Expected {
+    fn part(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            _ => self.part_2.as_deref(),
+        }
+    }
+
+    fn label(&self) -> String {
+        match &self.input {
+            Some(path) => format!("example {}", path.display()),
+            None => String::from("real"),
+        }
+    }
+}
+
+impl Registry {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("failed to read answers from {}", path.display()))?;
+        toml::from_str(&text).with_context(|| format!("invalid answers in {}", path.display()))
+    }
+
+    /// Whether `data` is one of `day`'s examples, matched by fingerprint
+    /// against the example files resolved against `base`. Anything else is
+    /// taken to be a real input.
+    pub fn kind_of(&self, day: u8, data: &str, base: &Path) -> InputKind {
+        let fingerprint = report::fingerprint(data);
+        let is_example = self
+            .answers
+            .iter()
+            .filter(|e| e.day == day)
+            .filter_map(|e| fs::read_to_string(base.join(e.input.as_ref()?)).ok())
+            .any(|example| report::fingerprint(&example) == fingerprint);
+        if is_example {
+            InputKind::Example
+        } else {
+            InputKind::Real
+        }
+    }
+}
+
+pub enum Outcome {
+    Pass,
+    Mismatch { expected: String, actual: String },
+    Fail(anyhow::Error),
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Pass => write!(f, "pass"),
+            Outcome::Mismatch { expected, actual }
+                if expected.contains('\n') || actual.contains('\n') =>
+            {
+                write!(f, "mismatch\nexpected:\n{}\nactual:\n{}", expected, actual)
+            }
+            Outcome::Mismatch { expected, actual } => {
+                write!(f, "mismatch, expected {}, actual {}", expected, actual)
+            }
+            Outcome::Fail(err) => write!(f, "fail, {:#}", err),
+        }
+    }
+}
+
+pub struct Check {
+    pub day: u8,
+    pub part: u8,
+    pub label: String,
+    pub outcome: Outcome,
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Day {:>2}, part {}, {}: {}",
+            self.day, self.part, self.label, self.outcome
+        )
+    }
+}
+
+/// Runs every recorded part of `expected`. Example paths are resolved against
+/// `base`, real inputs are read from `input_dir`.
+pub fn check(expected: &Expected, base: &Path, input_dir: &Path) -> Vec<Check> {
+    let source = match &expected.input {
+        Some(path) => Source::File(base.join(path)),
+        None => Source::in_dir(input_dir, expected.day),
+    };
+    let data = days::find(expected.day)
+        .ok_or_else(|| anyhow!("no solution for day {}", expected.day))
+        .and_then(|day| Ok((day, source.read()?)));
+    [1, 2]
+        .into_iter()
+        .filter_map(|part| Some((part, expected.part(part)?)))
+        .map(|(part, answer)| {
+            let outcome = match &data {
+                Ok((day, data)) => match day.solver(part)(data) {
+                    // answers are compared without trailing whitespace
+                    Ok(actual) if actual.answer.trim_end() == answer.trim_end() => Outcome::Pass,
+                    Ok(actual) => Outcome::Mismatch {
+                        expected: answer.trim_end().to_string(),
+                        actual: actual.answer.trim_end().to_string(),
+                    },
+                    Err(err) => Outcome::Fail(err),
+                },
+                Err(err) => Outcome::Fail(anyhow!("{:#}", err)),
+            };
+            Check {
+                day: expected.day,
+                part,
+                label: expected.label(),
+                outcome,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn registry() -> Registry {
+        toml::from_str(include_str!("../../answers.toml")).unwrap()
+    }
+
+    #[test]
+    fn test_examples() {
+        let base = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        for expected in registry().answers.iter().filter(|e| e.input.is_some()) {
+            for check in check(expected, &base, Path::new("")) {
+                assert!(matches!(check.outcome, Outcome::Pass), "{}", check);
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_day_has_answers() {
+        let registry = registry();
+        for day in days::DAYS.iter() {
+            let entries = registry.answers.iter().filter(|e| e.day == day.number);
+            assert_eq!(entries.clone().filter(|e| e.input.is_none()).count(), 1);
+            assert!(entries.clone().any(|e| e.input.is_some()));
+        }
+    }
+
+    #[test]
+    fn test_kind_of() {
+        let base = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        let registry = registry();
+        let example = include_str!("../../day-1/src/depths_test.csv");
+        assert_eq!(registry.kind_of(1, example, &base), InputKind::Example);
+        assert_eq!(registry.kind_of(2, example, &base), InputKind::Real);
+        // a real input given by path is counted, and solved, as the real one
+        let real = Source::File(base.join("inputs/day-01.txt")).read().unwrap();
+        assert_eq!(registry.kind_of(1, &real, &base), InputKind::Real);
+        let expected = registry
+            .answers
+            .iter()
+            .find(|e| e.day == 1 && e.input.is_none())
+            .unwrap();
+        let solution = days::find(1).unwrap().solver(1)(&real).unwrap();
+        assert_eq!(Some(solution.answer.as_str()), expected.part(1));
+    }
+
+    #[test]
+    fn test_mismatch() {
+        let expected: Expected =
+            toml::from_str("day = 1\ninput = \"day-1/src/depths_test.csv\"\npart_1 = \"8\"")
+                .unwrap();
+        let base = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        let checks = check(&expected, &base, Path::new(""));
+        assert_eq!(checks.len(), 1);
+        assert_eq!(
+            checks[0].to_string(),
+            "Day  1, part 1, example day-1/src/depths_test.csv: mismatch, expected 8, actual 7"
+        );
+    }
+}
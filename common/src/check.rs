@@ -0,0 +1,37 @@
+//! Helpers for finding every problem with an input at once, where parsing
+//! stops at the first.
+use crate::ParseError;
+
+/// Parses each line of `data` with `parse`, keeping the values of the lines
+/// that parse and the errors of those that don't, placed on their lines.
+pub fn each_line<'a, T, K, F>(data: &'a str, mut parse: F) -> (Vec<T>, Vec<ParseError<K>>)
+where
+    F: FnMut(&'a str) -> Result<T, ParseError<K>>,
+{
+    let mut values = Vec::new();
+    let mut problems = Vec::new();
+    for line in data.lines() {
+        match parse(line) {
+            Ok(value) => values.push(value),
+            Err(e) => problems.push(e.within(data, line)),
+        }
+    }
+    (values, problems)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_each_line() {
+        let data = "1\nx\n3\ny";
+        let (values, problems) = each_line(data, |line| {
+            line.parse::<u8>()
+                .map_err(|_| ParseError::locate("not a number", line, line))
+        });
+        assert_eq!(values, vec![1, 3]);
+        let lines: Vec<_> = problems.iter().map(|e| (e.line, e.column)).collect();
+        assert_eq!(lines, vec![(2, 1), (4, 1)]);
+    }
+}
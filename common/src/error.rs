@@ -0,0 +1,175 @@
+use std::fmt;
+
+/// An error found while parsing puzzle input, pointing at the text that
+/// caused it. `K` is the day specific kind of problem.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError<K> {
+    pub kind: K,
+    /// 1-based line number, 0 when the error is about the input as a whole.
+    pub line: usize,
+    /// 1-based column of the first offending char.
+    pub column: usize,
+    /// The offending text.
+    pub text: String,
+}
+
+impl<K> ParseError<K> {
+    pub fn new(kind: K, line: usize, column: usize, text: impl Into<String>) -> Self {
+        ParseError {
+            kind,
+            line,
+            column,
+            text: text.into(),
+        }
+    }
+
+    /// An error about `part`, which must be a slice of `source`. The line and
+    /// column are worked out from where `part` sits in `source`.
+    pub fn locate(kind: K, source: &str, part: &str) -> Self {
+        let start = part.as_ptr() as usize;
+        let base = source.as_ptr() as usize;
+        if start < base || start + part.len() > base + source.len() {
+            return ParseError::new(kind, 0, 0, part);
+        }
+        let before = &source[..start - base];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let column = before[line_start..].chars().count() + 1;
+        ParseError::new(kind, line, column, part)
+    }
+
+    /// An error that isn't tied to any particular line, e.g. empty input.
+    pub fn whole_input(kind: K) -> Self {
+        ParseError::new(kind, 0, 0, "")
+    }
+
+    /// Moves an error found while parsing a single line onto line `line` of
+    /// the whole input.
+    pub fn on_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self.column = self.column.max(1);
+        self
+    }
+
+    /// Re-anchors an error found while parsing `part` onto `source`, where
+    /// `part` is a slice of `source`.
+    pub fn within(self, source: &str, part: &str) -> Self {
+        let anchor = ParseError::locate((), source, part);
+        if anchor.line == 0 || self.line == 0 {
+            return self;
+        }
+        let column = if self.line == 1 {
+            anchor.column + self.column - 1
+        } else {
+            self.column
+        };
+        ParseError {
+            line: anchor.line + self.line - 1,
+            column,
+            ..self
+        }
+    }
+
+    pub fn map_kind<L, F>(self, f: F) -> ParseError<L>
+    where
+        F: FnOnce(K) -> L,
+    {
+        ParseError {
+            kind: f(self.kind),
+            line: self.line,
+            column: self.column,
+            text: self.text,
+        }
+    }
+}
+
+impl<K: fmt::Display> ParseError<K> {
+    /// Renders the error with the offending line of `source` and a caret
+    /// under the offending text.
+    pub fn render(&self, source: &str) -> String {
+        let mut out = format!("error: {}", self.kind);
+        let line = match self.line.checked_sub(1).and_then(|i| source.lines().nth(i)) {
+            Some(line) => line,
+            None => return out,
+        };
+        let gutter = " ".repeat(self.line.to_string().len());
+        let underline = self.text.chars().count().max(1);
+        out.push_str(&format!(
+            "\n{} --> line {}, column {}\n{} |\n{} | {}\n{} | {}{}",
+            gutter,
+            self.line,
+            self.column,
+            gutter,
+            self.line,
+            line,
+            gutter,
+            " ".repeat(self.column.saturating_sub(1)),
+            "^".repeat(underline),
+        ));
+        out
+    }
+}
+
+impl<K: fmt::Display> fmt::Display for ParseError<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line == 0 {
+            write!(f, "{}", self.kind)
+        } else {
+            write!(
+                f,
+                "line {}, column {}: {} ({:?})",
+                self.line, self.column, self.kind, self.text
+            )
+        }
+    }
+}
+
+impl<K: fmt::Debug + fmt::Display> std::error::Error for ParseError<K> {}
+
+/// Turns a parse result into an `anyhow` result whose message is the
+/// rendered error, for binaries that want to show the offending input.
+pub trait Rendered<T> {
+    fn rendered(self, source: &str) -> anyhow::Result<T>;
+}
+
+impl<T, K: fmt::Display> Rendered<T> for Result<T, ParseError<K>> {
+    fn rendered(self, source: &str) -> anyhow::Result<T> {
+        self.map_err(|e| anyhow::Error::msg(e.render(source)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_locate() {
+        let source = "abc\ndef ghi\n";
+        let part = &source[8..11];
+        let err = ParseError::locate("bad", source, part);
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 5, "ghi"));
+
+        let err = ParseError::locate("bad", source, "elsewhere");
+        assert_eq!((err.line, err.column), (0, 0));
+    }
+
+    #[test]
+    fn test_within() {
+        let source = "abc\ndef ghi\n";
+        let part = &source[8..11];
+        let err = ParseError::locate("bad", part, &part[1..]).within(source, part);
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 6, "hi"));
+    }
+
+    #[test]
+    fn test_render() {
+        let source = "abc\ndef ghi\n";
+        let err = ParseError::locate("not a number", source, &source[8..11]);
+        assert_eq!(
+            err.render(source),
+            "error: not a number\n  --> line 2, column 5\n  |\n2 | def ghi\n  |     ^^^"
+        );
+        let err = ParseError::whole_input("empty input");
+        assert_eq!(err.render(source), "error: empty input");
+    }
+}
@@ -0,0 +1,110 @@
+use anyhow::{Context, Result};
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+/// Environment variable naming the directory that holds `day-NN.txt` inputs.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+/// Input directory used when `AOC_INPUT_DIR` is not set.
+pub const DEFAULT_INPUT_DIR: &str = "inputs";
+
+/// Where a puzzle input is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    /// `-` means stdin, anything else is a path.
+    pub fn from_arg(arg: &str) -> Self {
+        if arg == "-" {
+            Source::Stdin
+        } else {
+            Source::File(PathBuf::from(arg))
+        }
+    }
+
+    /// The conventional input file for `day` inside `dir`.
+    pub fn in_dir(dir: &Path, day: u8) -> Self {
+        Source::File(dir.join(file_name(day)))
+    }
+
+    /// The conventional input file for `day` inside `$AOC_INPUT_DIR`.
+    pub fn default_for(day: u8) -> Self {
+        Source::in_dir(&input_dir(), day)
+    }
+
+    pub fn read(&self) -> Result<String> {
+        match self {
+            Source::File(path) => std::fs::read_to_string(path)
+                .with_context(|| format!("could not read input {}", path.display())),
+            Source::Stdin => {
+                let mut data = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut data)
+                    .context("could not read input from stdin")?;
+                Ok(data)
+            }
+        }
+    }
+
+    /// Opens the input for reading a bit at a time, rather than all at once.
+    pub fn open(&self) -> Result<Box<dyn BufRead>> {
+        match self {
+            Source::File(path) => {
+                let file = std::fs::File::open(path)
+                    .with_context(|| format!("could not open input {}", path.display()))?;
+                Ok(Box::new(BufReader::new(file)))
+            }
+            Source::Stdin => Ok(Box::new(std::io::stdin().lock())),
+        }
+    }
+}
+
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::File(path) => write!(f, "{}", path.display()),
+            Source::Stdin => write!(f, "<stdin>"),
+        }
+    }
+}
+
+/// `day-NN.txt`, zero padded so the inputs sort in order.
+pub fn file_name(day: u8) -> String {
+    format!("day-{:02}.txt", day)
+}
+
+pub fn input_dir() -> PathBuf {
+    std::env::var_os(INPUT_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_INPUT_DIR))
+}
+
+/// Reads the input for a day binary: the first command line argument is a
+/// path (or `-` for stdin), otherwise the conventional file is used.
+pub fn from_args(day: u8) -> Result<String> {
+    match std::env::args().nth(1) {
+        Some(arg) => Source::from_arg(&arg).read(),
+        None => Source::default_for(day).read(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sources() {
+        assert_eq!(Source::from_arg("-"), Source::Stdin);
+        assert_eq!(
+            Source::from_arg("some/file.txt"),
+            Source::File(PathBuf::from("some/file.txt"))
+        );
+        assert_eq!(
+            Source::in_dir(Path::new("inputs"), 7),
+            Source::File(PathBuf::from("inputs/day-07.txt"))
+        );
+        assert_eq!(file_name(12), "day-12.txt");
+    }
+}
@@ -0,0 +1,9 @@
+pub mod check;
+pub mod error;
+pub mod input;
+pub mod puzzle;
+pub mod stream;
+
+pub use error::{ParseError, Rendered};
+pub use puzzle::{Answer, Puzzle};
+pub use stream::{Lines, StreamError};
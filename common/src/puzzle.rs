@@ -0,0 +1,125 @@
+use crate::error::{ParseError, Rendered};
+use anyhow::{anyhow, Result};
+use std::fmt;
+
+/// What a part returns, turned into the text to show. Parts that may find
+/// nothing return an `Option`, which has no answer when empty.
+pub trait Answer {
+    fn answer(&self) -> Option<String>;
+}
+
+macro_rules! display_answer {
+    ($($ty:ty),*) => {
+        $(impl Answer for $ty {
+            fn answer(&self) -> Option<String> {
+                Some(self.to_string())
+            }
+        })*
+    };
+}
+
+display_answer!(i32, i64, u32, u64, usize, String);
+
+impl<T: Answer> Answer for Option<T> {
+    fn answer(&self) -> Option<String> {
+        self.as_ref().and_then(Answer::answer)
+    }
+}
+
+/// The most steps a single request may ask a simulation for, so a typo
+/// can't hang whoever asked.
+pub const MAX_REPEAT: u64 = 1_000_000;
+
+/// One day's puzzle: how to parse its input and solve both parts, so tools
+/// can drive any day the same way.
+pub trait Puzzle {
+    const DAY: u8;
+    const TITLE: &'static str;
+
+    /// The parsed input, shared by both parts.
+    type Input;
+    /// The day specific kind of parse error.
+    type ErrorKind: fmt::Display;
+    type Answer1: Answer;
+    type Answer2: Answer;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError<Self::ErrorKind>>;
+    /// Every problem with `data`, in order, without solving anything. Days
+    /// whose parser stops at the first problem only report that one unless
+    /// they look further.
+    fn check(data: &str) -> Vec<ParseError<Self::ErrorKind>> {
+        Self::parse(data).err().into_iter().collect()
+    }
+    fn part1(input: &Self::Input) -> Self::Answer1;
+    fn part2(input: &Self::Input) -> Self::Answer2;
+}
+
+/// Parses `data` for `P` inside a `parse` span, so traces show how the
+/// time splits between parsing and solving.
+pub fn parse<P: Puzzle>(data: &str) -> Result<P::Input, ParseError<P::ErrorKind>> {
+    let _span = tracing::info_span!("parse", day = P::DAY, bytes = data.len()).entered();
+    P::parse(data)
+}
+
+/// Solves one part of `P` on already parsed input, inside a `solve` span.
+pub fn solve<P: Puzzle>(input: &P::Input, part: u8) -> Result<String> {
+    let _span = tracing::info_span!("solve", day = P::DAY, part).entered();
+    let answer = match part {
+        1 => P::part1(input).answer(),
+        2 => P::part2(input).answer(),
+        _ => return Err(anyhow!("there is no part {}", part)),
+    };
+    answer.ok_or_else(|| anyhow!("part {} has no answer for this input", part))
+}
+
+/// The body of every day binary: reads the input named on the command line,
+/// or the conventional file, and prints both answers.
+pub fn run<P: Puzzle>() -> Result<()> {
+    let data = crate::input::from_args(P::DAY)?;
+    let input = parse::<P>(&data).rendered(&data)?;
+    for part in [1, 2] {
+        println!("part {}: {}", part, solve::<P>(&input, part)?);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Halves;
+
+    impl Puzzle for Halves {
+        const DAY: u8 = 0;
+        const TITLE: &'static str = "Halves";
+        type Input = u32;
+        type ErrorKind = &'static str;
+        type Answer1 = u32;
+        type Answer2 = Option<u32>;
+
+        fn parse(data: &str) -> Result<u32, ParseError<&'static str>> {
+            data.parse()
+                .map_err(|_| ParseError::locate("not a number", data, data))
+        }
+
+        fn part1(input: &u32) -> u32 {
+            input / 2
+        }
+
+        fn part2(input: &u32) -> Option<u32> {
+            input.is_multiple_of(2).then_some(input / 2)
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(solve::<Halves>(&7, 1).unwrap(), "3");
+        assert_eq!(solve::<Halves>(&8, 2).unwrap(), "4");
+        let err = solve::<Halves>(&7, 2).unwrap_err();
+        assert_eq!(err.to_string(), "part 2 has no answer for this input");
+        assert!(solve::<Halves>(&7, 3).is_err());
+        assert!(Halves::parse("x").is_err());
+        assert_eq!(Halves::check("x").len(), 1);
+        assert!(Halves::check("8").is_empty());
+    }
+}
@@ -0,0 +1,173 @@
+//! Reading input a line at a time from any [`BufRead`], for inputs too big to
+//! hold in memory at once.
+use crate::ParseError;
+use std::fmt;
+use std::io::{self, BufRead};
+
+/// An error while streaming input: either reading failed, or a line did not
+/// parse.
+#[derive(Debug)]
+pub enum StreamError<K> {
+    Io(io::Error),
+    Parse(ParseError<K>),
+}
+
+impl<K> From<io::Error> for StreamError<K> {
+    fn from(e: io::Error) -> Self {
+        StreamError::Io(e)
+    }
+}
+
+impl<K> From<ParseError<K>> for StreamError<K> {
+    fn from(e: ParseError<K>) -> Self {
+        StreamError::Parse(e)
+    }
+}
+
+impl<K: fmt::Display> fmt::Display for StreamError<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StreamError::Io(e) => write!(f, "could not read input: {}", e),
+            StreamError::Parse(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl<K: fmt::Debug + fmt::Display> std::error::Error for StreamError<K> {}
+
+/// Reads lines one at a time into a single buffer, so memory use depends on
+/// the longest line rather than the size of the input.
+pub struct Lines<R> {
+    reader: R,
+    buffer: String,
+    number: usize,
+}
+
+impl<R: BufRead> Lines<R> {
+    pub fn new(reader: R) -> Self {
+        Lines {
+            reader,
+            buffer: String::new(),
+            number: 0,
+        }
+    }
+
+    /// The next line without its line ending, along with its 1-based line
+    /// number, or `None` at the end of the input.
+    pub fn next_line(&mut self) -> Option<io::Result<(usize, &str)>> {
+        self.buffer.clear();
+        match self.reader.read_line(&mut self.buffer) {
+            Ok(0) => None,
+            Ok(_) => {
+                self.number += 1;
+                let line = self.buffer.strip_suffix('\n').unwrap_or(&self.buffer);
+                let line = line.strip_suffix('\r').unwrap_or(line);
+                Some(Ok((self.number, line)))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+
+    /// Parses the next line with `parse`, moving any error onto its line.
+    pub fn parse_next<T, K, F>(&mut self, parse: F) -> Option<Result<T, StreamError<K>>>
+    where
+        F: FnOnce(&str) -> Result<T, ParseError<K>>,
+    {
+        Some(match self.next_line()? {
+            Ok((number, line)) => parse(line).map_err(|e| StreamError::Parse(e.on_line(number))),
+            Err(e) => Err(StreamError::Io(e)),
+        })
+    }
+
+    /// Parses every line with `parse`.
+    pub fn parsed<T, K, F>(self, parse: F) -> Parsed<R, F>
+    where
+        F: FnMut(&str) -> Result<T, ParseError<K>>,
+    {
+        Parsed { lines: self, parse }
+    }
+}
+
+/// The values of each line, from [`Lines::parsed`].
+pub struct Parsed<R, F> {
+    lines: Lines<R>,
+    parse: F,
+}
+
+impl<R, T, K, F> Iterator for Parsed<R, F>
+where
+    R: BufRead,
+    F: FnMut(&str) -> Result<T, ParseError<K>>,
+{
+    type Item = Result<T, StreamError<K>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.lines.parse_next(&mut self.parse)
+    }
+}
+
+/// Hands `f` the values up to the first error, returning that error instead
+/// of what `f` made of them if there was one.
+pub fn until_error<T, E, U, I, F>(items: I, f: F) -> Result<U, E>
+where
+    I: IntoIterator<Item = Result<T, E>>,
+    F: FnOnce(&mut dyn Iterator<Item = T>) -> U,
+{
+    let mut error = None;
+    let mut values = items
+        .into_iter()
+        .map_while(|item| item.map_err(|e| error = Some(e)).ok());
+    let made = f(&mut values);
+    drop(values);
+    match error {
+        Some(e) => Err(e),
+        None => Ok(made),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_lines() {
+        let mut lines = Lines::new("one\r\ntwo\n\nthree".as_bytes());
+        let mut seen = Vec::new();
+        while let Some(line) = lines.next_line() {
+            let (number, text) = line.unwrap();
+            seen.push((number, text.to_string()));
+        }
+        let expected = [(1, "one"), (2, "two"), (3, ""), (4, "three")];
+        assert_eq!(
+            seen,
+            expected.map(|(n, text)| (n, text.to_string())).to_vec()
+        );
+    }
+
+    #[test]
+    fn test_parse_next() {
+        let mut lines = Lines::new("1\nx\n".as_bytes());
+        let parse = |line: &str| {
+            line.parse::<u8>()
+                .map_err(|_| ParseError::locate("not a number", line, line))
+        };
+        assert_eq!(lines.parse_next(parse).unwrap().unwrap(), 1);
+        match lines.parse_next(parse).unwrap() {
+            Err(StreamError::Parse(e)) => assert_eq!((e.line, e.column), (2, 1)),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+        assert!(lines.parse_next(parse).is_none());
+        let mut invalid = Lines::new(&b"\xff\n"[..]);
+        assert!(matches!(
+            invalid.parse_next(parse),
+            Some(Err(StreamError::Io(_)))
+        ));
+    }
+
+    #[test]
+    fn test_until_error() {
+        let sum = |values: &mut dyn Iterator<Item = u8>| values.sum::<u8>();
+        assert_eq!(until_error(vec![Ok(1), Ok(2)], sum), Ok::<_, &str>(3));
+        assert_eq!(until_error(vec![Ok(1), Err("bad"), Ok(2)], sum), Err("bad"));
+    }
+}
@@ -0,0 +1,360 @@
+//! Depth analysis with a configurable window: each window of readings is
+//! reduced to one value, and each value is compared with the one `lag`
+//! windows before it.
+
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::Add;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum AnalysisError {
+    #[error("the window must hold at least one reading")]
+    EmptyWindow,
+    #[error("the lag must be at least one window")]
+    NoLag,
+}
+
+/// How a window of readings becomes one value.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Aggregate {
+    #[default]
+    Sum,
+    Mean,
+    Min,
+    Max,
+    /// The middle reading, or the mean of the two middle readings.
+    Median,
+}
+
+impl FromStr for Aggregate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sum" => Ok(Aggregate::Sum),
+            "mean" => Ok(Aggregate::Mean),
+            "min" => Ok(Aggregate::Min),
+            "max" => Ok(Aggregate::Max),
+            "median" => Ok(Aggregate::Median),
+            _ => Err(format!(
+                "unknown aggregation {:?}, expected sum, mean, min, max or median",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Aggregate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Aggregate::Sum => "sum",
+            Aggregate::Mean => "mean",
+            Aggregate::Min => "min",
+            Aggregate::Max => "max",
+            Aggregate::Median => "median",
+        };
+        f.pad(name)
+    }
+}
+
+impl Aggregate {
+    /// The value of a window, which must not be empty. Readings are added
+    /// oldest first, so equal windows always give equal values.
+    pub fn apply(self, window: &[f64]) -> f64 {
+        match self {
+            Aggregate::Sum => window.iter().sum(),
+            Aggregate::Mean => window.iter().sum::<f64>() / window.len() as f64,
+            Aggregate::Min => window.iter().copied().fold(f64::INFINITY, f64::min),
+            Aggregate::Max => window.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            Aggregate::Median => {
+                let mut sorted = window.to_vec();
+                sorted.sort_by(f64::total_cmp);
+                let mid = sorted.len() / 2;
+                if sorted.len() % 2 == 1 {
+                    sorted[mid]
+                } else {
+                    (sorted[mid - 1] + sorted[mid]) / 2.0
+                }
+            }
+        }
+    }
+}
+
+/// How a window's value compares with the one it is measured against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Increase,
+    Decrease,
+    Unchanged,
+}
+
+impl Change {
+    pub(crate) fn between(before: f64, after: f64) -> Change {
+        match after.partial_cmp(&before) {
+            Some(Ordering::Greater) => Change::Increase,
+            Some(Ordering::Less) => Change::Decrease,
+            _ => Change::Unchanged,
+        }
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Change::Increase => "increase",
+            Change::Decrease => "decrease",
+            Change::Unchanged => "unchanged",
+        };
+        f.pad(name)
+    }
+}
+
+/// A full window's value, and the value `lag` windows back once there is one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Window {
+    pub value: f64,
+    pub before: Option<f64>,
+}
+
+impl Window {
+    pub fn change(&self) -> Option<Change> {
+        self.before
+            .map(|before| Change::between(before, self.value))
+    }
+}
+
+/// How many windows went each way.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Changes {
+    pub increases: usize,
+    pub decreases: usize,
+    pub unchanged: usize,
+}
+
+impl Changes {
+    pub fn record(&mut self, change: Change) {
+        match change {
+            Change::Increase => self.increases += 1,
+            Change::Decrease => self.decreases += 1,
+            Change::Unchanged => self.unchanged += 1,
+        }
+    }
+
+    /// Every comparison made.
+    pub fn total(&self) -> usize {
+        self.increases + self.decreases + self.unchanged
+    }
+}
+
+impl Add for Changes {
+    type Output = Changes;
+
+    fn add(self, other: Changes) -> Changes {
+        Changes {
+            increases: self.increases + other.increases,
+            decreases: self.decreases + other.decreases,
+            unchanged: self.unchanged + other.unchanged,
+        }
+    }
+}
+
+impl Sum for Changes {
+    fn sum<I: Iterator<Item = Changes>>(iter: I) -> Changes {
+        iter.fold(Changes::default(), Add::add)
+    }
+}
+
+/// The window size, aggregation and lag to analyze depths with. The
+/// default, single readings compared with the one before, is part 1;
+/// windows of three summed is part 2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Analysis {
+    window: usize,
+    aggregate: Aggregate,
+    lag: usize,
+}
+
+impl Default for Analysis {
+    fn default() -> Self {
+        Analysis {
+            window: 1,
+            aggregate: Aggregate::Sum,
+            lag: 1,
+        }
+    }
+}
+
+impl Analysis {
+    pub fn new(window: usize, aggregate: Aggregate, lag: usize) -> Result<Self, AnalysisError> {
+        if window == 0 {
+            return Err(AnalysisError::EmptyWindow);
+        }
+        if lag == 0 {
+            return Err(AnalysisError::NoLag);
+        }
+        Ok(Analysis {
+            window,
+            aggregate,
+            lag,
+        })
+    }
+
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    pub fn aggregate(&self) -> Aggregate {
+        self.aggregate
+    }
+
+    pub fn lag(&self) -> usize {
+        self.lag
+    }
+
+    /// The value of every full window, in order.
+    pub fn values(&self, depths: &[f64]) -> Vec<f64> {
+        depths
+            .windows(self.window)
+            .map(|window| self.aggregate.apply(window))
+            .collect()
+    }
+
+    /// Counts how the windows over `depths` change.
+    pub fn run<I>(&self, depths: I) -> Changes
+    where
+        I: IntoIterator<Item = f64>,
+    {
+        let mut rolling = Rolling::new(*self);
+        let mut changes = Changes::default();
+        for depth in depths {
+            if let Some(change) = rolling.push(depth).and_then(|w| w.change()) {
+                changes.record(change);
+            }
+        }
+        changes
+    }
+}
+
+/// An analysis fed one reading at a time, holding only the current window
+/// and the values it is compared against.
+#[derive(Debug, Clone)]
+pub struct Rolling {
+    analysis: Analysis,
+    readings: VecDeque<f64>,
+    values: VecDeque<f64>,
+}
+
+impl Rolling {
+    pub fn new(analysis: Analysis) -> Self {
+        Rolling {
+            analysis,
+            readings: VecDeque::with_capacity(analysis.window),
+            values: VecDeque::with_capacity(analysis.lag),
+        }
+    }
+
+    pub fn analysis(&self) -> Analysis {
+        self.analysis
+    }
+
+    /// Adds a reading, returning the window it completes, if any.
+    pub fn push(&mut self, depth: f64) -> Option<Window> {
+        if self.readings.len() == self.analysis.window {
+            self.readings.pop_front();
+        }
+        self.readings.push_back(depth);
+        if self.readings.len() < self.analysis.window {
+            return None;
+        }
+        let value = self
+            .analysis
+            .aggregate
+            .apply(self.readings.make_contiguous());
+        let before = if self.values.len() == self.analysis.lag {
+            self.values.pop_front()
+        } else {
+            None
+        };
+        self.values.push_back(value);
+        Some(Window { value, before })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{parse, part_1, part_2};
+    use proptest::prelude::*;
+
+    fn depths() -> Vec<f64> {
+        let depths = parse(include_str!("depths_test.csv")).unwrap();
+        depths.into_iter().map(f64::from).collect()
+    }
+
+    #[test]
+    fn test_parts() {
+        let changes = Analysis::default().run(depths());
+        assert_eq!(changes.increases, 7);
+        assert_eq!(changes.total(), 9);
+        let changes = Analysis::new(3, Aggregate::Sum, 1).unwrap().run(depths());
+        assert_eq!(changes.increases, 5);
+        assert_eq!(changes.unchanged, 1);
+        // the mean goes the same way as the sum
+        let mean = Analysis::new(3, Aggregate::Mean, 1).unwrap().run(depths());
+        assert_eq!(mean, changes);
+    }
+
+    #[test]
+    fn test_aggregates() {
+        let window = [3.0, 1.0, 4.0, 1.0];
+        assert_eq!(Aggregate::Sum.apply(&window), 9.0);
+        assert_eq!(Aggregate::Mean.apply(&window), 2.25);
+        assert_eq!(Aggregate::Min.apply(&window), 1.0);
+        assert_eq!(Aggregate::Max.apply(&window), 4.0);
+        assert_eq!(Aggregate::Median.apply(&window), 2.0);
+        assert_eq!(Aggregate::Median.apply(&window[..3]), 3.0);
+        assert_eq!("median".parse(), Ok(Aggregate::Median));
+        assert!("mode".parse::<Aggregate>().is_err());
+    }
+
+    #[test]
+    fn test_lag() {
+        // 199 200 208 210 200 207 240 269 260 263 against two readings back
+        let changes = Analysis::new(1, Aggregate::Sum, 2).unwrap().run(depths());
+        assert_eq!(
+            changes,
+            Changes {
+                increases: 5,
+                decreases: 3,
+                unchanged: 0
+            }
+        );
+        let values = Analysis::new(4, Aggregate::Max, 1)
+            .unwrap()
+            .values(&depths());
+        assert_eq!(values, [210.0, 210.0, 210.0, 240.0, 269.0, 269.0, 269.0]);
+        assert_eq!(
+            Analysis::new(0, Aggregate::Sum, 1),
+            Err(AnalysisError::EmptyWindow)
+        );
+        assert_eq!(
+            Analysis::new(2, Aggregate::Sum, 0),
+            Err(AnalysisError::NoLag)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn matches_parts(depths in prop::collection::vec(0..10_000u32, 0..60)) {
+            let readings = depths.iter().map(|&d| f64::from(d));
+            let changes = Analysis::default().run(readings.clone());
+            prop_assert_eq!(changes.increases as u32, part_1(&depths));
+            let changes = Analysis::new(3, Aggregate::Sum, 1).unwrap().run(readings);
+            prop_assert_eq!(changes.increases as u32, part_2(&depths));
+        }
+    }
+}
@@ -0,0 +1,238 @@
+//! Flagging readings that look more like a sensor fault than the seabed:
+//! sudden drops, single reading spikes, and readings far from the mean of
+//! those just before them.
+
+use std::collections::HashSet;
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum DetectorError {
+    #[error("the {0} threshold must be a positive number")]
+    Threshold(Kind),
+    #[error("the rolling window must hold at least two readings")]
+    Window,
+}
+
+/// A way of spotting anomalies. The constructors check the settings make
+/// sense; a detector built directly with a threshold that isn't positive
+/// flags too much or nothing at all.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Detector {
+    /// A reading at least this much shallower than the one before.
+    Drop(f64),
+    /// A reading at least this far above or below both its neighbours.
+    Spike(f64),
+    /// A reading more than `k` standard deviations from the mean of the
+    /// `window` readings before it. Windows of fewer than two readings have
+    /// no spread to measure, so flag nothing.
+    Deviation { window: usize, k: f64 },
+}
+
+fn threshold(kind: Kind, threshold: f64) -> Result<f64, DetectorError> {
+    match threshold.is_finite() && threshold > 0.0 {
+        true => Ok(threshold),
+        false => Err(DetectorError::Threshold(kind)),
+    }
+}
+
+impl Detector {
+    pub fn drop(by: f64) -> Result<Self, DetectorError> {
+        threshold(Kind::Drop, by).map(Detector::Drop)
+    }
+
+    pub fn spike(by: f64) -> Result<Self, DetectorError> {
+        threshold(Kind::Spike, by).map(Detector::Spike)
+    }
+
+    pub fn deviation(window: usize, k: f64) -> Result<Self, DetectorError> {
+        if window < 2 {
+            return Err(DetectorError::Window);
+        }
+        let k = threshold(Kind::Deviation, k)?;
+        Ok(Detector::Deviation { window, k })
+    }
+
+    pub fn kind(&self) -> Kind {
+        match self {
+            Detector::Drop(_) => Kind::Drop,
+            Detector::Spike(_) => Kind::Spike,
+            Detector::Deviation { .. } => Kind::Deviation,
+        }
+    }
+}
+
+/// Which detector flagged a reading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Kind {
+    Drop,
+    Spike,
+    Deviation,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Kind::Drop => "drop",
+            Kind::Spike => "spike",
+            Kind::Deviation => "deviation",
+        };
+        f.pad(name)
+    }
+}
+
+/// A flagged reading. The score is how far it went: the size of a drop or
+/// spike, or for a deviation how many standard deviations it is from the
+/// mean, which is infinite when the readings before it were all the same.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Anomaly {
+    pub index: usize,
+    pub kind: Kind,
+    pub score: f64,
+}
+
+/// Every anomaly one detector finds, in order.
+pub fn detect(depths: &[f64], detector: Detector) -> Vec<Anomaly> {
+    let kind = detector.kind();
+    let scores: Vec<(usize, f64)> = match detector {
+        Detector::Drop(threshold) => (1..depths.len())
+            .map(|i| (i, depths[i - 1] - depths[i]))
+            .filter(|&(_, fall)| fall >= threshold)
+            .collect(),
+        Detector::Spike(threshold) => (1..depths.len().saturating_sub(1))
+            .filter_map(|i| {
+                let (up, down) = (depths[i] - depths[i - 1], depths[i] - depths[i + 1]);
+                let score = up.abs().min(down.abs());
+                // level with either neighbour is no spike, whatever the
+                // threshold
+                let spike = up > 0.0 && down > 0.0 || up < 0.0 && down < 0.0;
+                (spike && score >= threshold).then_some((i, score))
+            })
+            .collect(),
+        Detector::Deviation { window, .. } if window < 2 => Vec::new(),
+        Detector::Deviation { window, k } => (window..depths.len())
+            .filter_map(|i| {
+                let before = &depths[i - window..i];
+                let mean = before.iter().sum::<f64>() / window as f64;
+                let variance =
+                    before.iter().map(|d| (d - mean).powi(2)).sum::<f64>() / window as f64;
+                let off = (depths[i] - mean).abs();
+                let score = match variance.sqrt() {
+                    spread if spread > 0.0 => off / spread,
+                    _ if off > 0.0 => f64::INFINITY,
+                    _ => 0.0,
+                };
+                (score > k).then_some((i, score))
+            })
+            .collect(),
+    };
+    scores
+        .into_iter()
+        .map(|(index, score)| Anomaly { index, kind, score })
+        .collect()
+}
+
+/// Every anomaly any of `detectors` finds, ordered by reading.
+pub fn detect_all(depths: &[f64], detectors: &[Detector]) -> Vec<Anomaly> {
+    let mut anomalies: Vec<_> = detectors
+        .iter()
+        .flat_map(|&detector| detect(depths, detector))
+        .collect();
+    anomalies.sort_by_key(|a| (a.index, a.kind));
+    anomalies
+}
+
+/// `depths` with the flagged readings taken out, ready to be counted.
+pub fn without(depths: &[f64], anomalies: &[Anomaly]) -> Vec<f64> {
+    let flagged: HashSet<usize> = anomalies.iter().map(|a| a.index).collect();
+    depths
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !flagged.contains(i))
+        .map(|(_, &depth)| depth)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn depths() -> Vec<f64> {
+        let depths = crate::parse(include_str!("depths_test.csv")).unwrap();
+        depths.into_iter().map(f64::from).collect()
+    }
+
+    fn flagged(anomalies: &[Anomaly]) -> Vec<(usize, f64)> {
+        anomalies.iter().map(|a| (a.index, a.score)).collect()
+    }
+
+    #[test]
+    fn test_drops_and_spikes() {
+        // 199 200 208 210 200 207 240 269 260 263
+        let depths = depths();
+        assert_eq!(
+            flagged(&detect(&depths, Detector::Drop(8.0))),
+            [(4, 10.0), (8, 9.0)]
+        );
+        assert_eq!(
+            flagged(&detect(&depths, Detector::Spike(5.0))),
+            [(4, 7.0), (7, 9.0)]
+        );
+        assert!(detect(&[1.0, 9.0], Detector::Spike(1.0)).is_empty());
+        assert!(detect(&[5.0, 5.0, 5.0, 9.0, 9.0], Detector::Spike(0.0)).is_empty());
+        assert_eq!(
+            flagged(&detect(&[5.0, 3.0, 5.0], Detector::Spike(0.0))),
+            [(1, 2.0)]
+        );
+    }
+
+    #[test]
+    fn test_deviation() {
+        let depths = depths();
+        let found = detect(&depths, Detector::Deviation { window: 3, k: 2.0 });
+        let indices: Vec<_> = found.iter().map(|a| a.index).collect();
+        assert_eq!(indices, [6, 7]);
+        assert!(found[0].score > 8.0 && found[1].score > 3.0);
+        // nothing varied before the jump, so any change is infinitely far
+        let flat = [5.0, 5.0, 5.0, 6.0];
+        let found = detect(&flat, Detector::Deviation { window: 3, k: 10.0 });
+        assert_eq!(flagged(&found), [(3, f64::INFINITY)]);
+        assert!(detect(&flat, Detector::Deviation { window: 1, k: 1.0 }).is_empty());
+    }
+
+    #[test]
+    fn test_constructors() {
+        assert_eq!(Detector::drop(8.0), Ok(Detector::Drop(8.0)));
+        assert_eq!(
+            Detector::deviation(3, 2.0),
+            Ok(Detector::Deviation { window: 3, k: 2.0 })
+        );
+        for bad in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(
+                Detector::drop(bad),
+                Err(DetectorError::Threshold(Kind::Drop))
+            );
+            assert_eq!(
+                Detector::spike(bad),
+                Err(DetectorError::Threshold(Kind::Spike))
+            );
+            assert_eq!(
+                Detector::deviation(3, bad),
+                Err(DetectorError::Threshold(Kind::Deviation))
+            );
+        }
+        assert_eq!(Detector::deviation(1, 2.0), Err(DetectorError::Window));
+        assert_eq!(Detector::deviation(0, 2.0), Err(DetectorError::Window));
+    }
+
+    #[test]
+    fn test_without() {
+        let depths = [100.0, 101.0, 5.0, 102.0, 103.0];
+        let detectors = [Detector::Drop(50.0), Detector::Spike(50.0)];
+        let anomalies = detect_all(&depths, &detectors);
+        let kinds: Vec<_> = anomalies.iter().map(|a| (a.index, a.kind)).collect();
+        assert_eq!(kinds, [(2, Kind::Drop), (2, Kind::Spike)]);
+        let cleaned = without(&depths, &anomalies);
+        assert_eq!(cleaned, [100.0, 101.0, 102.0, 103.0]);
+    }
+}
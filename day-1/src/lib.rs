@@ -0,0 +1,196 @@
+//! Day 1: Sonar Sweep. Counts how often a series of depth readings gets
+//! deeper, either reading by reading or over a sliding window.
+
+pub mod analysis;
+pub mod anomaly;
+pub mod log;
+pub mod monitor;
+pub mod trend;
+
+pub use analysis::{Aggregate, Analysis, Changes};
+pub use monitor::{Monitor, Thresholds};
+
+use common::check;
+use common::stream::{self, Lines};
+use common::{ParseError as SpanError, Puzzle};
+use std::io::BufRead;
+use std::num::{ParseFloatError, ParseIntError};
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ErrorKind {
+    #[error("invalid depth: {0}")]
+    InvalidDepth(ParseIntError),
+    #[error("invalid reading: {0}")]
+    InvalidReading(ParseFloatError),
+    #[error("reading is not a finite number")]
+    NotFinite,
+    #[error("no column {0} on this line")]
+    MissingColumn(usize),
+    #[error("no column named {0:?} in the header")]
+    UnknownColumn(String),
+}
+
+pub type ParseError = SpanError<ErrorKind>;
+pub type StreamError = stream::StreamError<ErrorKind>;
+
+fn parse_depth(line: &str) -> Result<u32, ParseError> {
+    line.parse::<u32>()
+        .map_err(|e| ParseError::locate(ErrorKind::InvalidDepth(e), line, line))
+}
+
+/// Reads one depth per line.
+pub fn parse(data: &str) -> Result<Vec<u32>, ParseError> {
+    data.lines()
+        .map(|o| parse_depth(o).map_err(|e| e.within(data, o)))
+        .collect()
+}
+
+/// Every line that isn't a depth.
+pub fn check(data: &str) -> Vec<ParseError> {
+    check::each_line(data, parse_depth).1
+}
+
+/// Reads one depth per line as they are needed, for inputs too big to hold.
+pub fn read_depths<R: BufRead>(reader: R) -> impl Iterator<Item = Result<u32, StreamError>> {
+    Lines::new(reader).parsed(parse_depth)
+}
+
+/// How many readings are deeper than the one before.
+pub fn part_1(depths: &[u32]) -> u32 {
+    num_increasing(depths.iter().map(|&o| u64::from(o)))
+}
+
+/// How many three reading windows are deeper than the one before.
+pub fn part_2(depths: &[u32]) -> u32 {
+    // summed as u64 so three deep readings can't overflow
+    num_increasing(
+        depths
+            .windows(3)
+            .map(|o| o.iter().map(|&o| u64::from(o)).sum()),
+    )
+}
+
+/// [`part_1`] over depths read one at a time.
+pub fn part_1_streamed<R: BufRead>(reader: R) -> Result<u32, StreamError> {
+    stream::until_error(read_depths(reader), |depths| {
+        num_increasing(depths.map(u64::from))
+    })
+}
+
+/// [`part_2`] over depths read one at a time, keeping only the last window.
+pub fn part_2_streamed<R: BufRead>(reader: R) -> Result<u32, StreamError> {
+    stream::until_error(read_depths(reader), |depths| {
+        let sums = depths
+            .map(u64::from)
+            .scan((0, [0; 3]), |(seen, window), depth| {
+                window[*seen % 3] = depth;
+                *seen += 1;
+                Some((*seen >= 3).then(|| window.iter().sum()))
+            })
+            .flatten();
+        num_increasing(sums)
+    })
+}
+
+/// Day 1 as a [`Puzzle`], for tools that drive every day the same way.
+pub struct SonarSweep;
+
+impl Puzzle for SonarSweep {
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Sonar Sweep";
+
+    type Input = Vec<u32>;
+    type ErrorKind = ErrorKind;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(data: &str) -> Result<Vec<u32>, ParseError> {
+        parse(data)
+    }
+
+    fn check(data: &str) -> Vec<ParseError> {
+        check(data)
+    }
+
+    fn part1(input: &Vec<u32>) -> u32 {
+        part_1(input)
+    }
+
+    fn part2(input: &Vec<u32>) -> u32 {
+        part_2(input)
+    }
+}
+
+/// Counts the values that are greater than the value before them.
+pub fn num_increasing<I>(depths: I) -> u32
+where
+    I: Iterator<Item = u64>,
+{
+    depths
+        .fold((0, u64::MAX), |(sum, prev), cur| {
+            if cur.cmp(&prev).is_gt() {
+                (sum + 1, cur)
+            } else {
+                (sum, cur)
+            }
+        })
+        .0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test() {
+        let depths = parse(include_str!("depths_test.csv")).unwrap();
+        assert_eq!(part_1(&depths), 7);
+        assert_eq!(part_2(&depths), 5);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse("199\n200\n2o8\n").unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::InvalidDepth(_)));
+        assert_eq!((err.line, err.column, err.text.as_str()), (3, 1, "2o8"));
+    }
+
+    #[test]
+    fn test_check() {
+        let problems = check("199\n-200\n208\n2o8\n");
+        let lines: Vec<_> = problems.iter().map(|e| e.line).collect();
+        assert_eq!(lines, vec![2, 4]);
+        assert!(check(include_str!("depths_test.csv")).is_empty());
+    }
+
+    #[test]
+    fn test_streamed() {
+        let data = include_str!("depths_test.csv");
+        assert_eq!(part_1_streamed(data.as_bytes()).unwrap(), 7);
+        assert_eq!(part_2_streamed(data.as_bytes()).unwrap(), 5);
+        match part_1_streamed("199\n200\n2o8\n".as_bytes()) {
+            Err(StreamError::Parse(err)) => {
+                assert_eq!((err.line, err.column, err.text.as_str()), (3, 1, "2o8"))
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn windows_compare_ends(depths in prop::collection::vec(0..10_000u32, 0..60)) {
+            // neighbouring windows share two depths, so only the ends matter
+            let expected = (3..depths.len()).filter(|&i| depths[i] > depths[i - 3]).count();
+            prop_assert_eq!(part_2(&depths), expected as u32);
+        }
+
+        #[test]
+        fn streamed_matches(depths in prop::collection::vec(0..10_000u32, 0..60)) {
+            let data: String = depths.iter().map(|d| format!("{}\n", d)).collect();
+            prop_assert_eq!(part_1_streamed(data.as_bytes()).unwrap(), part_1(&depths));
+            prop_assert_eq!(part_2_streamed(data.as_bytes()).unwrap(), part_2(&depths));
+        }
+    }
+}
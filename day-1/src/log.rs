@@ -0,0 +1,515 @@
+//! Depth logs as sonar exports write them: delimited columns with a
+//! timestamp and several channels, `#` comments, an optional header, and
+//! gaps where a reading is blank or `NaN`. Depths may be fractional, and
+//! negative above the datum.
+
+use crate::{ErrorKind, ParseError, StreamError};
+use common::stream::Lines;
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+
+/// A column, by its 1-based position or by its name in the header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Column {
+    Index(usize),
+    Name(String),
+}
+
+impl Default for Column {
+    fn default() -> Self {
+        Column::Index(1)
+    }
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse::<usize>() {
+            Ok(0) => Err("columns are numbered from 1".to_string()),
+            Ok(index) => Ok(Column::Index(index)),
+            Err(_) if s.is_empty() => Err("expected a column number or name".to_string()),
+            Err(_) => Ok(Column::Name(s.to_string())),
+        }
+    }
+}
+
+/// How to read a log. The default reads the first comma separated column,
+/// which is also how a plain list of depths reads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Format {
+    /// What separates columns. A space splits on runs of whitespace instead.
+    pub delimiter: char,
+    /// Where the depths are.
+    pub depth: Column,
+    /// Where the timestamps are, kept as they are written.
+    pub time: Option<Column>,
+    /// Whether the first line names the columns. Picking a column by name
+    /// always reads a header.
+    pub header: bool,
+}
+
+impl Default for Format {
+    fn default() -> Self {
+        Format {
+            delimiter: ',',
+            depth: Column::default(),
+            time: None,
+            header: false,
+        }
+    }
+}
+
+impl Format {
+    fn fields<'a>(&self, line: &'a str) -> Vec<&'a str> {
+        if self.delimiter == ' ' {
+            line.split_whitespace().collect()
+        } else {
+            line.split(self.delimiter).map(str::trim).collect()
+        }
+    }
+
+    fn has_header(&self) -> bool {
+        let named = |column: &Column| matches!(column, Column::Name(_));
+        self.header || named(&self.depth) || self.time.as_ref().is_some_and(named)
+    }
+}
+
+/// One line of a log. A missing depth is a gap.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reading {
+    /// 1-based line number in the log.
+    pub line: usize,
+    pub time: Option<String>,
+    pub depth: Option<f64>,
+}
+
+/// What to do about gaps in the depths.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Gaps {
+    /// Leave them out, joining the readings either side.
+    #[default]
+    Skip,
+    /// Fill them in on a straight line between the readings either side.
+    /// Gaps at either end have nothing to fill from, so are left out.
+    Interpolate,
+    /// Start afresh after each one, so no window spans a gap.
+    Break,
+}
+
+impl FromStr for Gaps {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "skip" => Ok(Gaps::Skip),
+            "interpolate" => Ok(Gaps::Interpolate),
+            "break" => Ok(Gaps::Break),
+            _ => Err(format!(
+                "unknown gap policy {:?}, expected skip, interpolate or break",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Gaps {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Gaps::Skip => "skip",
+            Gaps::Interpolate => "interpolate",
+            Gaps::Break => "break",
+        };
+        f.pad(name)
+    }
+}
+
+impl Gaps {
+    /// The depths to analyze, as runs of readings with no gap inside them.
+    /// Only [`Gaps::Break`] makes more than one.
+    pub fn apply(self, readings: &[Reading]) -> Vec<Vec<f64>> {
+        self.apply_lines(readings)
+            .into_iter()
+            .map(|segment| segment.into_iter().map(|(_, depth)| depth).collect())
+            .collect()
+    }
+
+    /// [`Gaps::apply`] with the log line of each depth alongside it. A
+    /// filled in depth has the line of the gap it fills.
+    pub fn apply_lines(self, readings: &[Reading]) -> Vec<Vec<(usize, f64)>> {
+        let depths = readings.iter().map(|r| r.depth.map(|d| (r.line, d)));
+        let segments = match self {
+            Gaps::Skip => vec![depths.flatten().collect()],
+            Gaps::Interpolate => vec![interpolate(readings)],
+            Gaps::Break => {
+                let mut segments = vec![Vec::new()];
+                for depth in depths {
+                    match depth {
+                        Some(depth) => segments.last_mut().unwrap().push(depth),
+                        None => segments.push(Vec::new()),
+                    }
+                }
+                segments
+            }
+        };
+        segments.into_iter().filter(|s| !s.is_empty()).collect()
+    }
+}
+
+fn interpolate(readings: &[Reading]) -> Vec<(usize, f64)> {
+    let known: Vec<(usize, f64)> = readings
+        .iter()
+        .enumerate()
+        .filter_map(|(i, r)| r.depth.map(|d| (i, d)))
+        .collect();
+    let mut filled = Vec::new();
+    for pair in known.windows(2) {
+        let ((i, a), (j, b)) = (pair[0], pair[1]);
+        filled.extend((i..j).map(|k| {
+            (
+                readings[k].line,
+                a + (b - a) * (k - i) as f64 / (j - i) as f64,
+            )
+        }));
+    }
+    filled.extend(known.last().map(|&(i, depth)| (readings[i].line, depth)));
+    filled
+}
+
+fn depth(data: &str, field: &str) -> Result<Option<f64>, ParseError> {
+    let missing = ["", "nan", "na", "null"];
+    if missing.iter().any(|m| field.eq_ignore_ascii_case(m)) {
+        return Ok(None);
+    }
+    let depth = field
+        .parse::<f64>()
+        .map_err(|e| ParseError::locate(ErrorKind::InvalidReading(e), data, field))?;
+    if !depth.is_finite() {
+        return Err(ParseError::locate(ErrorKind::NotFinite, data, field));
+    }
+    Ok(Some(depth))
+}
+
+/// Where the columns a [`Format`] asks for are, once any header is known.
+#[derive(Debug, Clone)]
+struct Columns {
+    format: Format,
+    depth: usize,
+    time: Option<usize>,
+}
+
+impl Columns {
+    /// Finds the columns, by name in `header` where asked. Errors are
+    /// located within `header`.
+    fn new(format: &Format, header: Option<&str>) -> Result<Self, ParseError> {
+        let position = |column: &Column| match column {
+            Column::Index(0) => Err(ParseError::whole_input(ErrorKind::MissingColumn(0))),
+            Column::Index(index) => Ok(index - 1),
+            Column::Name(name) => header
+                .and_then(|line| format.fields(line).iter().position(|f| f == name))
+                .ok_or_else(|| match header {
+                    Some(line) => {
+                        ParseError::locate(ErrorKind::UnknownColumn(name.clone()), line, line)
+                    }
+                    None => ParseError::whole_input(ErrorKind::UnknownColumn(name.clone())),
+                }),
+        };
+        Ok(Columns {
+            format: format.clone(),
+            depth: position(&format.depth)?,
+            time: format.time.as_ref().map(position).transpose()?,
+        })
+    }
+
+    /// Reads line `number` of the log. Errors are located within `line`.
+    fn read(&self, number: usize, line: &str) -> Result<Reading, ParseError> {
+        if line.trim().is_empty() {
+            return Ok(Reading {
+                line: number,
+                time: None,
+                depth: None,
+            });
+        }
+        let fields = self.format.fields(line);
+        let field = |at: usize| {
+            fields
+                .get(at)
+                .copied()
+                .ok_or_else(|| ParseError::locate(ErrorKind::MissingColumn(at + 1), line, line))
+        };
+        Ok(Reading {
+            line: number,
+            time: self.time.map(field).transpose()?.map(str::to_string),
+            depth: depth(line, field(self.depth)?)?,
+        })
+    }
+}
+
+fn is_comment(line: &str) -> bool {
+    line.trim_start().starts_with('#')
+}
+
+/// Reads every reading in a log.
+pub fn parse_log(data: &str, format: &Format) -> Result<Vec<Reading>, ParseError> {
+    let mut lines = data
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !is_comment(line));
+    let header = match format.has_header() {
+        true => lines.next().map(|(_, line)| line),
+        false => None,
+    };
+    let columns = Columns::new(format, header).map_err(|e| match header {
+        Some(line) => e.within(data, line),
+        None => e,
+    })?;
+    lines
+        .map(|(i, line)| columns.read(i + 1, line).map_err(|e| e.within(data, line)))
+        .collect()
+}
+
+/// The readings of a log as they arrive, from [`read_log`].
+pub struct LogReader<R> {
+    lines: Lines<R>,
+    columns: Columns,
+}
+
+/// Reads a log a line at a time, for feeds too big or too slow to wait for.
+/// Any header is read straight away, so a column that isn't there is an
+/// error here rather than on every line.
+pub fn read_log<R: BufRead>(reader: R, format: &Format) -> Result<LogReader<R>, StreamError> {
+    let mut lines = Lines::new(reader);
+    let columns = match format.has_header() {
+        true => loop {
+            match lines.next_line().transpose()? {
+                Some((_, line)) if is_comment(line) => continue,
+                Some((number, line)) => {
+                    break Columns::new(format, Some(line)).map_err(|e| e.on_line(number))?
+                }
+                None => break Columns::new(format, None)?,
+            }
+        },
+        false => Columns::new(format, None)?,
+    };
+    Ok(LogReader { lines, columns })
+}
+
+impl<R: BufRead> Iterator for LogReader<R> {
+    type Item = Result<Reading, StreamError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (number, line) = match self.lines.next_line()? {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e.into())),
+            };
+            if is_comment(line) {
+                continue;
+            }
+            let reading = self.columns.read(number, line);
+            return Some(reading.map_err(|e| e.on_line(number).into()));
+        }
+    }
+}
+
+/// What comes of a reading once a gap policy has seen it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Fill {
+    Depth(f64),
+    /// Start afresh from here.
+    Break,
+}
+
+/// [`Gaps::apply`] for readings that arrive one at a time. Interpolating
+/// holds back nothing but a count of the gaps since the last depth.
+#[derive(Debug, Clone)]
+pub struct GapFiller {
+    gaps: Gaps,
+    last: Option<f64>,
+    missing: usize,
+}
+
+impl GapFiller {
+    pub fn new(gaps: Gaps) -> Self {
+        GapFiller {
+            gaps,
+            last: None,
+            missing: 0,
+        }
+    }
+
+    /// What to do with the next depth, or gap when it is `None`.
+    pub fn push(&mut self, depth: Option<f64>) -> Vec<Fill> {
+        let Some(depth) = depth else {
+            return match self.gaps {
+                Gaps::Skip => Vec::new(),
+                Gaps::Interpolate => {
+                    // a gap before the first depth has nothing to fill from
+                    self.missing += usize::from(self.last.is_some());
+                    Vec::new()
+                }
+                Gaps::Break => vec![Fill::Break],
+            };
+        };
+        let mut fills = Vec::new();
+        if let Some(last) = self.last {
+            let steps = (self.missing + 1) as f64;
+            fills.extend(
+                (1..=self.missing).map(|k| Fill::Depth(last + (depth - last) * k as f64 / steps)),
+            );
+        }
+        fills.push(Fill::Depth(depth));
+        self.last = Some(depth);
+        self.missing = 0;
+        fills
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::analysis::{Aggregate, Analysis};
+
+    const LOG: &str = "\
+# survey 7, channels in metres
+time\tport\tstarboard
+08:00:00\t12.5\t12.0
+08:00:01\tNaN\t12.5
+08:00:02\t-1.5\t13.0
+08:00:03\t\t13.5
+
+08:00:05\t14.5\t14.0
+";
+
+    fn format(depth: &str) -> Format {
+        Format {
+            delimiter: '\t',
+            depth: depth.parse().unwrap(),
+            time: Some(Column::Index(1)),
+            header: true,
+        }
+    }
+
+    #[test]
+    fn test_parse_log() {
+        let readings = parse_log(LOG, &format("port")).unwrap();
+        let depths: Vec<_> = readings.iter().map(|r| r.depth).collect();
+        assert_eq!(
+            depths,
+            [Some(12.5), None, Some(-1.5), None, None, Some(14.5)]
+        );
+        assert_eq!(readings[2].time.as_deref(), Some("08:00:02"));
+        assert_eq!((readings[4].line, readings[4].time.as_ref()), (7, None));
+        let starboard = parse_log(LOG, &format("3")).unwrap();
+        assert_eq!(starboard[1].depth, Some(12.5));
+        // a plain list of depths reads the same as with `parse`
+        let plain = parse_log(include_str!("depths_test.csv"), &Format::default()).unwrap();
+        assert_eq!(plain[9].depth, Some(263.0));
+    }
+
+    #[test]
+    fn test_parse_log_errors() {
+        let err = parse_log(LOG, &format("sonar")).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::UnknownColumn("sonar".to_string()));
+        assert_eq!(err.line, 2);
+        let err = parse_log(LOG, &format("4")).unwrap_err();
+        assert_eq!((err.kind, err.line), (ErrorKind::MissingColumn(4), 3));
+        let err = parse_log("1.5\n2,5\nabc\n", &Format::default()).unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::InvalidReading(_)));
+        assert_eq!((err.line, err.column, err.text.as_str()), (3, 1, "abc"));
+        let err = parse_log("1\ninf\n", &Format::default()).unwrap_err();
+        assert_eq!((err.kind, err.line), (ErrorKind::NotFinite, 2));
+        assert_eq!(
+            "0".parse::<Column>(),
+            Err("columns are numbered from 1".into())
+        );
+    }
+
+    #[test]
+    fn test_gaps() {
+        let readings = parse_log(LOG, &format("port")).unwrap();
+        assert_eq!(Gaps::Skip.apply(&readings), [vec![12.5, -1.5, 14.5]]);
+        assert_eq!(
+            Gaps::Interpolate.apply(&readings),
+            [vec![
+                12.5,
+                5.5,
+                -1.5,
+                3.833333333333333,
+                9.166666666666666,
+                14.5
+            ]]
+        );
+        assert_eq!(
+            Gaps::Break.apply(&readings),
+            [vec![12.5], vec![-1.5], vec![14.5]]
+        );
+        let lines: Vec<_> = Gaps::Interpolate.apply_lines(&readings)[0]
+            .iter()
+            .map(|&(line, _)| line)
+            .collect();
+        assert_eq!(lines, [3, 4, 5, 6, 7, 8]);
+        assert_eq!(
+            Gaps::Break.apply_lines(&readings),
+            [vec![(3, 12.5)], vec![(5, -1.5)], vec![(8, 14.5)]]
+        );
+        assert_eq!("break".parse(), Ok(Gaps::Break));
+        assert!("fill".parse::<Gaps>().is_err());
+    }
+
+    #[test]
+    fn test_read_log() {
+        let streamed: Vec<_> = read_log(LOG.as_bytes(), &format("port"))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(streamed, parse_log(LOG, &format("port")).unwrap());
+        let err = read_log(LOG.as_bytes(), &format("sonar")).err().unwrap();
+        match err {
+            StreamError::Parse(e) => assert_eq!(
+                (e.kind, e.line),
+                (ErrorKind::UnknownColumn("sonar".to_string()), 2)
+            ),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+        let mut readings = read_log("1.5\nabc\n2".as_bytes(), &Format::default()).unwrap();
+        assert!(readings.next().unwrap().is_ok());
+        match readings.next().unwrap() {
+            Err(StreamError::Parse(e)) => assert_eq!((e.line, e.column), (2, 1)),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+        assert_eq!(readings.next().unwrap().unwrap().depth, Some(2.0));
+    }
+
+    #[test]
+    fn test_gap_filler() {
+        let readings = parse_log(LOG, &format("port")).unwrap();
+        for gaps in [Gaps::Skip, Gaps::Interpolate, Gaps::Break] {
+            let mut filler = GapFiller::new(gaps);
+            let mut segments = vec![Vec::new()];
+            for fill in readings.iter().flat_map(|r| filler.push(r.depth)) {
+                match fill {
+                    Fill::Depth(depth) => segments.last_mut().unwrap().push(depth),
+                    Fill::Break => segments.push(Vec::new()),
+                }
+            }
+            segments.retain(|s| !s.is_empty());
+            assert_eq!(segments, gaps.apply(&readings), "{}", gaps);
+        }
+    }
+
+    #[test]
+    fn test_analysis() {
+        let readings = parse_log(LOG, &format("starboard")).unwrap();
+        let analysis = Analysis::new(2, Aggregate::Mean, 1).unwrap();
+        let changes: Vec<_> = Gaps::Break
+            .apply(&readings)
+            .into_iter()
+            .map(|segment| analysis.run(segment))
+            .collect();
+        // 12.0 12.5 13.0 13.5, then 14.0 alone after the blank line
+        assert_eq!(changes[0].increases, 2);
+        assert_eq!(changes[1].total(), 0);
+    }
+}
@@ -0,0 +1,168 @@
+//! Watching depths as they arrive: a running count of the changes, and
+//! alerts as soon as the readings cross a threshold. Only the current window
+//! and the values it is compared against are kept, however long it runs.
+
+use crate::analysis::{Analysis, Change, Changes, Rolling, Window};
+use std::fmt;
+
+/// When to raise an alert. Both are off by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Thresholds {
+    /// A window at least this much deeper than the one it is compared with.
+    pub rise: Option<f64>,
+    /// This many increases in a row.
+    pub streak: Option<usize>,
+}
+
+/// Something worth shouting about.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Alert {
+    Rise(f64),
+    Streak(usize),
+}
+
+impl fmt::Display for Alert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Alert::Rise(by) => write!(f, "deeper by {}", by),
+            Alert::Streak(n) => write!(f, "{} increases in a row", n),
+        }
+    }
+}
+
+/// What one reading did.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Update {
+    /// Readings seen so far, this one included.
+    pub reading: usize,
+    pub depth: f64,
+    /// The window this reading completed, once there are enough readings.
+    pub window: Option<Window>,
+    /// Every change counted so far, this one included.
+    pub changes: Changes,
+    pub alerts: Vec<Alert>,
+}
+
+impl fmt::Display for Update {
+    /// Tab separated: reading, depth, window value, change and increases so
+    /// far, with `-` for anything not known yet.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = self.window.map(|w| w.value.to_string());
+        let change = self.window.and_then(|w| w.change()).map(|c| c.to_string());
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}",
+            self.reading,
+            self.depth,
+            value.as_deref().unwrap_or("-"),
+            change.as_deref().unwrap_or("-"),
+            self.changes.increases
+        )
+    }
+}
+
+/// Keeps count of the changes in depth, one reading at a time.
+#[derive(Debug, Clone)]
+pub struct Monitor {
+    rolling: Rolling,
+    thresholds: Thresholds,
+    changes: Changes,
+    readings: usize,
+    streak: usize,
+}
+
+impl Monitor {
+    pub fn new(analysis: Analysis, thresholds: Thresholds) -> Self {
+        Monitor {
+            rolling: Rolling::new(analysis),
+            thresholds,
+            changes: Changes::default(),
+            readings: 0,
+            streak: 0,
+        }
+    }
+
+    /// Every change counted so far.
+    pub fn changes(&self) -> Changes {
+        self.changes
+    }
+
+    /// Starts the windows afresh, as after a gap, keeping the counts so far.
+    pub fn restart(&mut self) {
+        self.rolling = Rolling::new(self.rolling.analysis());
+        self.streak = 0;
+    }
+
+    pub fn push(&mut self, depth: f64) -> Update {
+        self.readings += 1;
+        let window = self.rolling.push(depth);
+        let mut alerts = Vec::new();
+        if let Some(Window {
+            value,
+            before: Some(before),
+        }) = window
+        {
+            let change = Change::between(before, value);
+            self.changes.record(change);
+            self.streak = match change {
+                Change::Increase => self.streak + 1,
+                _ => 0,
+            };
+            if matches!(self.thresholds.rise, Some(rise) if value - before >= rise) {
+                alerts.push(Alert::Rise(value - before));
+            }
+            // once per streak, as it reaches the threshold
+            if self.thresholds.streak == Some(self.streak) {
+                alerts.push(Alert::Streak(self.streak));
+            }
+        }
+        Update {
+            reading: self.readings,
+            depth,
+            window,
+            changes: self.changes,
+            alerts,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::analysis::Aggregate;
+
+    #[test]
+    fn test_monitor() {
+        let thresholds = Thresholds {
+            rise: Some(25.0),
+            streak: Some(3),
+        };
+        let analysis = Analysis::new(3, Aggregate::Sum, 1).unwrap();
+        let mut monitor = Monitor::new(analysis, thresholds);
+        let updates: Vec<_> = include_str!("depths_test.csv")
+            .lines()
+            .map(|line| monitor.push(line.parse().unwrap()))
+            .collect();
+        assert_eq!(updates[1].to_string(), "2\t200\t-\t-\t0");
+        assert_eq!(updates[3].to_string(), "4\t210\t618\tincrease\t1");
+        assert_eq!(updates[9].changes.increases, 5);
+        assert_eq!(monitor.changes(), updates[9].changes);
+        // sums 607 618 618 617 647 716 769 792
+        let alerts: Vec<_> = updates
+            .iter()
+            .flat_map(|u| u.alerts.iter().map(move |a| (u.reading, *a)))
+            .collect();
+        assert_eq!(
+            alerts,
+            [
+                (7, Alert::Rise(30.0)),
+                (8, Alert::Rise(69.0)),
+                (9, Alert::Rise(53.0)),
+                (9, Alert::Streak(3)),
+            ]
+        );
+        monitor.restart();
+        assert_eq!(monitor.push(300.0).window, None);
+        assert_eq!(monitor.changes(), updates[9].changes);
+    }
+}
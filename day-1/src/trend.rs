@@ -0,0 +1,249 @@
+//! Where the depths go, rather than how often: the series split into
+//! maximal runs that keep rising, keep falling or stay flat.
+
+use crate::analysis::Change;
+use std::fmt;
+
+/// Readings `start..=end` all moving the same way. Neighbouring runs share
+/// the reading where one turns into the other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Run {
+    pub change: Change,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Run {
+    /// Steps from one reading to the next, which is one less than readings.
+    pub fn steps(&self) -> usize {
+        self.end - self.start
+    }
+}
+
+impl fmt::Display for Run {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..={}", self.start, self.end)
+    }
+}
+
+/// One reading to the next.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Step {
+    /// The later reading.
+    pub index: usize,
+    pub from: f64,
+    pub to: f64,
+}
+
+impl Step {
+    pub fn by(&self) -> f64 {
+        self.to - self.from
+    }
+}
+
+/// Splits `depths` into runs, in order. Fewer than two readings have none.
+pub fn runs(depths: &[f64]) -> Vec<Run> {
+    let mut runs: Vec<Run> = Vec::new();
+    for (i, pair) in depths.windows(2).enumerate() {
+        let change = Change::between(pair[0], pair[1]);
+        match runs.last_mut() {
+            Some(run) if run.change == change => run.end = i + 1,
+            _ => runs.push(Run {
+                change,
+                start: i,
+                end: i + 1,
+            }),
+        }
+    }
+    runs
+}
+
+/// The first of the longest runs going `change`'s way.
+pub fn longest(runs: &[Run], change: Change) -> Option<Run> {
+    runs.iter().filter(|run| run.change == change).fold(
+        None,
+        |best: Option<Run>, &run| match best {
+            Some(best) if best.steps() >= run.steps() => Some(best),
+            _ => Some(run),
+        },
+    )
+}
+
+/// The longest stretch where the seabed drops away, which is where the
+/// depths keep increasing.
+pub fn longest_descent(runs: &[Run]) -> Option<Run> {
+    longest(runs, Change::Increase)
+}
+
+/// The first of the biggest changes between neighbouring readings, either
+/// way.
+pub fn steepest(depths: &[f64]) -> Option<Step> {
+    depths
+        .windows(2)
+        .enumerate()
+        .map(|(i, pair)| Step {
+            index: i + 1,
+            from: pair[0],
+            to: pair[1],
+        })
+        .fold(None, |best: Option<Step>, step| match best {
+            Some(best) if best.by().abs() >= step.by().abs() => Some(best),
+            _ => Some(step),
+        })
+}
+
+/// How many runs went each way, how far they went in all, and the longest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Row {
+    pub change: Change,
+    pub runs: usize,
+    pub steps: usize,
+    pub longest: Option<Run>,
+}
+
+/// A row per direction, and the steepest step.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub rows: [Row; 3],
+    pub steepest: Option<Step>,
+    /// Where each reading is shown to be, such as its line in a log. Runs
+    /// and steps still hold indices into the readings.
+    pub positions: Vec<usize>,
+}
+
+impl Summary {
+    /// A summary with each reading shown at its index.
+    pub fn new(depths: &[f64]) -> Self {
+        Self::numbered(depths, &(0..depths.len()).collect::<Vec<_>>())
+    }
+
+    /// A summary with reading `i` shown at `positions[i]`, so there must be
+    /// a position for every depth.
+    pub fn numbered(depths: &[f64], positions: &[usize]) -> Self {
+        let runs = runs(depths);
+        let row = |change| {
+            let going = runs.iter().filter(|run| run.change == change);
+            Row {
+                change,
+                runs: going.clone().count(),
+                steps: going.map(Run::steps).sum(),
+                longest: longest(&runs, change),
+            }
+        };
+        Summary {
+            rows: [
+                row(Change::Increase),
+                row(Change::Decrease),
+                row(Change::Unchanged),
+            ],
+            steepest: steepest(depths),
+            positions: positions.to_vec(),
+        }
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:<10} {:>6} {:>6}  longest", "trend", "runs", "steps")?;
+        for row in self.rows.iter() {
+            write!(f, "\n{:<10} {:>6} {:>6}", row.change, row.runs, row.steps)?;
+            if let Some(run) = row.longest {
+                let (start, end) = (self.positions[run.start], self.positions[run.end]);
+                match run.steps() {
+                    1 => write!(f, "  {}..={} (1 step)", start, end)?,
+                    steps => write!(f, "  {}..={} ({} steps)", start, end, steps)?,
+                }
+            }
+        }
+        if let Some(step) = self.steepest {
+            write!(
+                f,
+                "\nsteepest step {} -> {} ({:+}) at {}",
+                step.from,
+                step.to,
+                step.by(),
+                self.positions[step.index]
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn depths() -> Vec<f64> {
+        let depths = crate::parse(include_str!("depths_test.csv")).unwrap();
+        depths.into_iter().map(f64::from).collect()
+    }
+
+    #[test]
+    fn test_runs() {
+        // 199 200 208 210 200 207 240 269 260 263
+        let found = runs(&depths());
+        let spans: Vec<_> = found.iter().map(|r| (r.change, r.start, r.end)).collect();
+        assert_eq!(
+            spans,
+            [
+                (Change::Increase, 0, 3),
+                (Change::Decrease, 3, 4),
+                (Change::Increase, 4, 7),
+                (Change::Decrease, 7, 8),
+                (Change::Increase, 8, 9),
+            ]
+        );
+        assert_eq!(
+            longest_descent(&found).map(|r| (r.start, r.end)),
+            Some((0, 3))
+        );
+        assert_eq!(longest(&found, Change::Unchanged), None);
+        assert_eq!(runs(&[1.0, 1.0, 1.0])[0].steps(), 2);
+        assert!(runs(&[5.0]).is_empty());
+    }
+
+    #[test]
+    fn test_steepest() {
+        let step = steepest(&depths()).unwrap();
+        assert_eq!((step.index, step.by()), (6, 33.0));
+        let step = steepest(&[10.0, 4.0, 7.0]).unwrap();
+        assert_eq!((step.index, step.by()), (1, -6.0));
+        assert_eq!(steepest(&[]), None);
+    }
+
+    #[test]
+    fn test_summary() {
+        let table = Summary::new(&depths()).to_string();
+        let lines: Vec<_> = table.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "trend        runs  steps  longest",
+                "increase        3      7  0..=3 (3 steps)",
+                "decrease        2      2  3..=4 (1 step)",
+                "unchanged       0      0",
+                "steepest step 207 -> 240 (+33) at 6",
+            ]
+        );
+        let positions: Vec<_> = (0..10).map(|i| 100 + 2 * i).collect();
+        let table = Summary::numbered(&depths(), &positions).to_string();
+        let lines: Vec<_> = table.lines().collect();
+        assert_eq!(lines[1], "increase        3      7  100..=106 (3 steps)");
+        assert_eq!(lines[4], "steepest step 207 -> 240 (+33) at 112");
+    }
+
+    proptest! {
+        #[test]
+        fn runs_cover_every_step(depths in prop::collection::vec(0..20u32, 0..60)) {
+            let depths: Vec<f64> = depths.into_iter().map(f64::from).collect();
+            let found = runs(&depths);
+            let steps: usize = found.iter().map(Run::steps).sum();
+            prop_assert_eq!(steps, depths.len().saturating_sub(1));
+            for pair in found.windows(2) {
+                prop_assert_eq!(pair[0].end, pair[1].start);
+                prop_assert_ne!(pair[0].change, pair[1].change);
+            }
+        }
+    }
+}
@@ -0,0 +1,289 @@
+//! Day 10: Syntax Scoring. Scores lines of brackets that close with the wrong
+//! bracket, and the brackets missing from lines that stop early.
+
+use common::check;
+use common::stream::{self, Lines};
+use common::{ParseError as SpanError, Puzzle};
+use std::io::BufRead;
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ErrorKind {
+    #[error("lines may only contain the brackets ()[]{{}}<>")]
+    InvalidChar,
+}
+
+pub type ParseError = SpanError<ErrorKind>;
+pub type StreamError = stream::StreamError<ErrorKind>;
+
+/// Which of the two scoring tables to use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParserType {
+    Corruption,
+    Completion,
+}
+trait ParseScorer {
+    fn is_open(&self) -> bool;
+    fn is_close(&self) -> bool;
+    fn get_partner(&self) -> Self;
+    fn get_score(&self, parse_type: ParserType) -> u64;
+}
+impl ParseScorer for char {
+    fn is_open(&self) -> bool {
+        matches!(*self, '{' | '<' | '[' | '(')
+    }
+
+    fn is_close(&self) -> bool {
+        matches!(*self, '}' | '>' | ']' | ')')
+    }
+
+    fn get_partner(&self) -> char {
+        match *self {
+            '{' => '}',
+            '<' => '>',
+            '[' => ']',
+            '(' => ')',
+            '}' => '{',
+            '>' => '<',
+            ']' => '[',
+            ')' => '(',
+            _ => unreachable!(),
+        }
+    }
+
+    fn get_score(&self, parse_type: ParserType) -> u64 {
+        match parse_type {
+            ParserType::Corruption => match *self {
+                ')' => 3,
+                ']' => 57,
+                '}' => 1197,
+                '>' => 25137,
+                _ => unreachable!(),
+            },
+            ParserType::Completion => match *self {
+                ')' => 1,
+                ']' => 2,
+                '}' => 3,
+                '>' => 4,
+                _ => unreachable!(),
+            },
+        }
+    }
+}
+
+/// The score of the first bracket that closes the wrong bracket, if any.
+pub fn is_corrupted(line: &str) -> Option<u64> {
+    let mut stack = Vec::with_capacity(line.len());
+    for c in line.chars() {
+        if c.is_open() {
+            stack.push(c);
+        } else if c.is_close() {
+            match stack.last() {
+                Some(o) if c.get_partner() == *o => {
+                    stack.pop();
+                }
+                _ => return Some(c.get_score(ParserType::Corruption)),
+            }
+        }
+    }
+    None
+}
+
+/// The score of the brackets needed to complete a line that is not
+/// corrupted, if it is missing any.
+pub fn is_unfinished(line: &str) -> Option<u64> {
+    let mut stack = Vec::with_capacity(line.len());
+    for c in line.chars() {
+        if c.is_open() {
+            stack.push(c);
+        } else if c.is_close() {
+            match stack.last() {
+                Some(o) if c.get_partner() == *o => {
+                    stack.pop();
+                }
+                // is corrupted not unfinished
+                _ => return None,
+            }
+        }
+    }
+    if stack.is_empty() {
+        None
+    } else {
+        Some(
+            stack
+                .iter()
+                .rev()
+                .map(|c| c.get_partner())
+                // saturates rather than overflowing on absurdly long lines
+                .fold(0u64, |acc, c| {
+                    acc.saturating_mul(5)
+                        .saturating_add(c.get_score(ParserType::Completion))
+                }),
+        )
+    }
+}
+
+/// Checks that a line is only brackets.
+fn check_line(line: &str) -> Result<&str, ParseError> {
+    match line
+        .char_indices()
+        .find(|(_, c)| !c.is_open() && !c.is_close())
+    {
+        Some((i, c)) => Err(ParseError::locate(
+            ErrorKind::InvalidChar,
+            line,
+            &line[i..i + c.len_utf8()],
+        )),
+        None => Ok(line),
+    }
+}
+
+/// Reads one line of brackets per line, rejecting anything else.
+pub fn parse(data: &str) -> Result<Vec<String>, ParseError> {
+    data.lines()
+        .map(|line| {
+            check_line(line)
+                .map(String::from)
+                .map_err(|e| e.within(data, line))
+        })
+        .collect()
+}
+
+/// Every line with something other than brackets.
+pub fn check(data: &str) -> Vec<ParseError> {
+    check::each_line(data, check_line).1
+}
+
+/// Reads and checks one line of brackets at a time, for inputs too big to
+/// hold.
+pub fn read_lines<R: BufRead>(reader: R) -> impl Iterator<Item = Result<String, StreamError>> {
+    Lines::new(reader).parsed(|line| check_line(line).map(String::from))
+}
+
+/// [`part_1`] over lines read one at a time.
+pub fn part_1_streamed<R: BufRead>(reader: R) -> Result<u64, StreamError> {
+    let scores = Lines::new(reader).parsed(|line| check_line(line).map(is_corrupted));
+    stream::until_error(scores, |scores| scores.flatten().sum())
+}
+
+/// [`part_2`] over lines read one at a time. Only the completion scores are
+/// kept, to find the middle one.
+pub fn part_2_streamed<R: BufRead>(reader: R) -> Result<Option<u64>, StreamError> {
+    let scores = Lines::new(reader).parsed(|line| check_line(line).map(is_unfinished));
+    let mut scores: Vec<u64> = stream::until_error(scores, |scores| scores.flatten().collect())?;
+    scores.sort_unstable();
+    Ok(scores.get(scores.len() / 2).copied())
+}
+
+/// The total score of the corrupted lines.
+pub fn part_1(lines: &[String]) -> u64 {
+    lines
+        .iter()
+        .map(String::as_str)
+        .filter_map(is_corrupted)
+        .sum()
+}
+
+/// The middle completion score, if any line is incomplete.
+pub fn part_2(lines: &[String]) -> Option<u64> {
+    let mut scores: Vec<_> = lines
+        .iter()
+        .map(String::as_str)
+        .filter_map(is_unfinished)
+        .collect();
+    scores.sort_unstable();
+    scores.get(scores.len() / 2).copied()
+}
+
+/// Day 10 as a [`Puzzle`], for tools that drive every day the same way.
+pub struct SyntaxScoring;
+
+impl Puzzle for SyntaxScoring {
+    const DAY: u8 = 10;
+    const TITLE: &'static str = "Syntax Scoring";
+
+    type Input = Vec<String>;
+    type ErrorKind = ErrorKind;
+    type Answer1 = u64;
+    type Answer2 = Option<u64>;
+
+    fn parse(data: &str) -> Result<Vec<String>, ParseError> {
+        parse(data)
+    }
+
+    fn check(data: &str) -> Vec<ParseError> {
+        check(data)
+    }
+
+    fn part1(input: &Vec<String>) -> u64 {
+        part_1(input)
+    }
+
+    fn part2(input: &Vec<String>) -> Option<u64> {
+        part_2(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_corruption_score() {
+        assert_eq!(
+            include_str!("../data/test.txt")
+                .lines()
+                .filter_map(is_corrupted)
+                .sum::<u64>(),
+            26397
+        )
+    }
+
+    #[test]
+    fn test_completion_score() {
+        let mut scores: Vec<_> = include_str!("../data/test.txt")
+            .lines()
+            .filter_map(is_unfinished)
+            .collect();
+        scores.sort();
+        println!("{:?}", scores);
+        assert_eq!(288957, scores[scores.len() / 2]);
+    }
+
+    #[test]
+    fn test_nothing_to_complete() {
+        assert_eq!(part_2(&parse("{([(<{}[<>[]}>{[]{[(<()>").unwrap()), None);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse("[({(<(())[]>[[{[]{<()<>>\n[(()[<>])]({[<{<<[]>>x").unwrap_err();
+        assert_eq!(err.kind, ErrorKind::InvalidChar);
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 22, "x"));
+    }
+
+    #[test]
+    fn test_check() {
+        let problems = check("[(x)]\n<>\n{ }");
+        let found: Vec<_> = problems.iter().map(|e| (e.line, e.column)).collect();
+        assert_eq!(found, vec![(1, 3), (3, 2)]);
+    }
+
+    #[test]
+    fn test_streamed() {
+        let data = include_str!("../data/test.txt");
+        assert_eq!(part_1_streamed(data.as_bytes()).unwrap(), 26397);
+        assert_eq!(part_2_streamed(data.as_bytes()).unwrap(), Some(288957));
+        let lines: Vec<String> = read_lines(data.as_bytes())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(lines, parse(data).unwrap());
+        let broken = "[({(<(())[]>[[{[]{<()<>>\n[(()[<>])]({[<{<<[]>>x";
+        match part_2_streamed(broken.as_bytes()) {
+            Err(StreamError::Parse(err)) => {
+                assert_eq!((err.line, err.column, err.text.as_str()), (2, 22, "x"))
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+}
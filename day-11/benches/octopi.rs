@@ -0,0 +1,27 @@
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use generate::day_11::Params;
+
+const SEED: u64 = 11;
+
+fn step(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day-11/Octopi::step");
+    for side in [10, 20, 40, 80, 160] {
+        let octopi = day_11::parse(&generate::day_11::generate(
+            &mut generate::rng(SEED),
+            &Params::sized(side),
+        ))
+        .unwrap();
+        group.throughput(Throughput::Elements((side * side) as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(side), &octopi, |b, octopi| {
+            b.iter_batched(
+                || octopi.clone(),
+                |mut octopi| octopi.step(),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, step);
+criterion_main!(benches);
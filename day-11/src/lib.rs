@@ -0,0 +1,204 @@
+//! Day 11: Dumbo Octopus. Steps a grid of octopi whose flashes spread energy
+//! to their neighbours, counting flashes until they all flash together.
+
+use common::{ParseError as SpanError, Puzzle};
+use grid::{Grid, GridErrorKind};
+use std::str::FromStr;
+use thiserror::Error;
+use tracing::field;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ErrorKind {
+    #[error("{0}")]
+    Grid(GridErrorKind),
+}
+
+pub type ParseError = SpanError<ErrorKind>;
+
+/// One octopus's energy level and whether it has flashed this step.
+#[derive(Clone, Debug)]
+pub struct Octopus {
+    val: u32,
+    has_flashed: bool,
+}
+/// A grid of octopi.
+#[derive(Clone, Debug)]
+pub struct Octopi {
+    octopi: Grid<Octopus>,
+}
+impl FromStr for Octopi {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let octopi = Grid::parse_with(s, |c| {
+            c.to_digit(10).map(|val| Octopus {
+                val,
+                has_flashed: false,
+            })
+        })
+        .map_err(|e| e.map_kind(ErrorKind::Grid))?;
+        Ok(Octopi { octopi })
+    }
+}
+
+impl Octopi {
+    /// Raises every energy level by one, lets the flashes spread and returns
+    /// how many octopi flashed.
+    pub fn step(&mut self) -> u64 {
+        self.step_with(|_| ())
+    }
+
+    /// Like [`Octopi::step`], calling `on_flash` after each flash while the
+    /// step is under way. Octopi that have flashed so far show an energy of 0.
+    pub fn step_with<F>(&mut self, mut on_flash: F) -> u64
+    where
+        F: FnMut(&Octopi),
+    {
+        let span = tracing::trace_span!("step", flashes = field::Empty);
+        let _span = span.enter();
+        let mut to_change: Vec<usize> = (0..self.octopi.len()).collect();
+        while let Some(i) = to_change.pop() {
+            let o = &mut self.octopi[i];
+            if !o.has_flashed {
+                o.val += 1;
+                if o.val > 9 {
+                    o.val = 0;
+                    o.has_flashed = true;
+                    for neighbor in self.octopi.neighbors8(i) {
+                        to_change.push(neighbor);
+                    }
+                    on_flash(self);
+                }
+            }
+        }
+
+        let flashes = self.octopi.iter().filter(|o| o.has_flashed).count() as u64;
+        span.record("flashes", flashes);
+        for o in self.octopi.iter_mut() {
+            o.has_flashed = false;
+        }
+        flashes
+    }
+
+    /// The energy levels, row by row.
+    pub fn energy(&self) -> Vec<u32> {
+        self.octopi.iter().map(|o| o.val).collect()
+    }
+
+    /// The energy levels laid out as the octopi are.
+    pub fn energy_grid(&self) -> Grid<u32> {
+        self.octopi.map(|o| o.val)
+    }
+
+    /// The number of octopi.
+    pub fn len(&self) -> usize {
+        self.octopi.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.octopi.is_empty()
+    }
+}
+
+/// Reads a grid of single digit energy levels.
+pub fn parse(data: &str) -> Result<Octopi, ParseError> {
+    Octopi::from_str(data)
+}
+
+/// Every row of the octopi with a non-digit energy level or the wrong length.
+pub fn check(data: &str) -> Vec<ParseError> {
+    Grid::check_digits(data)
+        .into_iter()
+        .map(|e| e.map_kind(ErrorKind::Grid))
+        .collect()
+}
+
+/// The number of flashes in the first 100 steps.
+pub fn part_1(octopi: &Octopi) -> u64 {
+    let mut octopi = octopi.clone();
+    (0..100).map(|_| octopi.step()).sum::<u64>()
+}
+
+/// The first step on which every octopus flashes, if there ever is one.
+pub fn part_2(octopi: &Octopi) -> Option<u64> {
+    let mut octopi = octopi.clone();
+    // there are only so many states, so octopi that get back to one they've
+    // been in without all flashing never will; checkpoints at powers of two
+    // spot the repeat without remembering every state (Brent's algorithm)
+    let mut checkpoint = octopi.energy();
+    let mut step = 0;
+    loop {
+        step += 1;
+        if octopi.step() == octopi.len() as u64 {
+            return Some(step);
+        }
+        let energy = octopi.energy();
+        if energy == checkpoint {
+            return None;
+        }
+        if step.is_power_of_two() {
+            checkpoint = energy;
+        }
+    }
+}
+
+/// Day 11 as a [`Puzzle`], for tools that drive every day the same way.
+pub struct DumboOctopus;
+
+impl Puzzle for DumboOctopus {
+    const DAY: u8 = 11;
+    const TITLE: &'static str = "Dumbo Octopus";
+
+    type Input = Octopi;
+    type ErrorKind = ErrorKind;
+    type Answer1 = u64;
+    type Answer2 = Option<u64>;
+
+    fn parse(data: &str) -> Result<Octopi, ParseError> {
+        parse(data)
+    }
+
+    fn check(data: &str) -> Vec<ParseError> {
+        check(data)
+    }
+
+    fn part1(input: &Octopi) -> u64 {
+        part_1(input)
+    }
+
+    fn part2(input: &Octopi) -> Option<u64> {
+        part_2(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let mut octopi = Octopi::from_str(include_str!("../data/test.txt")).unwrap();
+        let flashes = (0..100).map(|_| octopi.step()).sum::<u64>();
+        println!("{:?}", octopi.octopi);
+        assert_eq!(flashes, 1656);
+        let octopi = Octopi::from_str(include_str!("../data/test.txt")).unwrap();
+        assert_eq!(part_2(&octopi), Some(195));
+    }
+
+    #[test]
+    fn test_step_with() {
+        let mut octopi = Octopi::from_str("11111\n19991\n19191\n19991\n11111").unwrap();
+        let mut seen = Vec::new();
+        let flashes =
+            octopi.step_with(|o| seen.push(o.energy().iter().filter(|&&e| e == 0).count()));
+        assert_eq!(flashes, 9);
+        assert_eq!(seen, (1..=9).collect::<Vec<_>>());
+        assert_eq!(octopi.energy_grid().row(0), Some(&[3, 4, 5, 4, 3][..]));
+    }
+
+    #[test]
+    fn test_never_in_sync() {
+        let octopi = Octopi::from_str("002").unwrap();
+        assert_eq!(part_2(&octopi), None);
+    }
+}
@@ -0,0 +1,28 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use generate::day_12::Params;
+
+const SEED: u64 = 12;
+
+fn count_routes(c: &mut Criterion) {
+    for (name, is_part_a, sizes) in [
+        ("day-12/count_routes/part_1", true, 2..=7),
+        ("day-12/count_routes/part_2", false, 2..=5),
+    ] {
+        let mut group = c.benchmark_group(name);
+        group.sample_size(20);
+        for caves in sizes {
+            let system = day_12::parse(&generate::day_12::generate(
+                &mut generate::rng(SEED),
+                &Params::sized(caves),
+            ))
+            .unwrap();
+            group.bench_with_input(BenchmarkId::from_parameter(caves), &system, |b, system| {
+                b.iter(|| system.count_routes(is_part_a))
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, count_routes);
+criterion_main!(benches);
@@ -0,0 +1,308 @@
+//! Day 12: Passage Pathing. Counts the routes from `start` to `end` through a
+//! system of caves, where small caves may only be visited so often.
+
+use common::check;
+use common::{ParseError as SpanError, Puzzle};
+use rayon::prelude::*;
+// use std::cell::RefCell;
+use std::collections::HashMap;
+//use std::rc::Rc;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ErrorKind {
+    #[error("expected an edge like `start-A`")]
+    InvalidEdge,
+    #[error("cave names must not be empty")]
+    EmptyName,
+    #[error("two big caves are joined, so there are endless routes between them")]
+    BigCavesJoined,
+}
+
+pub type ParseError = SpanError<ErrorKind>;
+
+/// A cave and the caves it connects to. Caves named in capitals are big and
+/// may be visited any number of times.
+#[derive(Debug, Clone)]
+pub struct Cave {
+    name: String,
+    is_big: bool,
+    neighbors: Vec<String>,
+}
+
+impl Cave {
+    pub fn new(name: &str) -> Self {
+        Cave {
+            name: name.to_string(),
+            is_big: name == name.to_uppercase(),
+            neighbors: Vec::new(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn is_big(&self) -> bool {
+        self.is_big
+    }
+
+    /// The names of the connected caves.
+    pub fn neighbors(&self) -> &[String] {
+        &self.neighbors
+    }
+}
+/// Every cave, by name.
+#[derive(Debug, Clone)]
+pub struct CaveSystem {
+    caves: HashMap<String, Cave>,
+}
+
+impl Default for CaveSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CaveSystem {
+    pub fn new() -> Self {
+        CaveSystem {
+            caves: HashMap::new(),
+        }
+    }
+    pub fn cave(&self, name: &str) -> Option<&Cave> {
+        self.caves.get(name)
+    }
+
+    pub fn caves(&self) -> impl Iterator<Item = &Cave> {
+        self.caves.values()
+    }
+
+    /// Counts the routes from `start` to `end`. Small caves are visited at
+    /// most once, except that when `is_part_a` is false a single one of them
+    /// may be visited twice.
+    pub fn count_routes(&self, is_part_a: bool) -> u32 {
+        let _span = tracing::debug_span!("count_routes", is_part_a).entered();
+        let Some(cur) = self.caves.get("start") else {
+            return 0;
+        };
+        self.count_routes_from(cur, HashMap::new(), is_part_a)
+    }
+
+    /// [`CaveSystem::count_routes`] with each first step out of `start`
+    /// counted on its own thread.
+    pub fn count_routes_par(&self, is_part_a: bool) -> u32 {
+        let _span = tracing::debug_span!("count_routes_par", is_part_a).entered();
+        let Some(start) = self.caves.get("start") else {
+            return 0;
+        };
+        // start is small, and nothing has been visited yet to rule a first
+        // step out
+        let visited = HashMap::from([(start.name.clone(), 1)]);
+        start
+            .neighbors
+            .par_iter()
+            .map(|n| &self.caves[n])
+            .filter(|next| next.name != "start")
+            .map(|next| self.count_routes_from(next, visited.clone(), is_part_a))
+            .sum()
+    }
+
+    /// Counts the routes on to `end` from `cur`, given how many times each
+    /// small cave has been visited so far. Routes never go back to `start`.
+    pub fn count_routes_from(
+        &self,
+        cur: &Cave,
+        visited: HashMap<String, u8>,
+        is_part_a: bool,
+    ) -> u32 {
+        if cur.name == "end" {
+            return 1;
+        }
+        let mut visited = visited;
+        let times = visited.entry(cur.name.clone()).or_insert(0);
+        if !cur.is_big {
+            *times += 1;
+        }
+        let mut count = 0;
+
+        // every neighbour was added to the system along with its edge
+        for next in cur.neighbors.iter().map(|n| &self.caves[n]) {
+            if next.name == "start" {
+                continue;
+            }
+            // in part b a single small cave may be visited twice
+            let can_revisit =
+                !is_part_a && visited[&cur.name] < 2 && !visited.values().any(|&x| x >= 2);
+            if next.is_big || !visited.contains_key(&next.name) || can_revisit {
+                count += self.count_routes_from(next, visited.clone(), is_part_a);
+            }
+        }
+        count
+    }
+}
+
+impl FromStr for CaveSystem {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cave_system = CaveSystem::new();
+        for line in s.lines() {
+            let caves = edge(line).map_err(|e| e.within(s, line))?;
+            // update left_cave
+            let left_cave = cave_system
+                .caves
+                .entry(caves[0].to_owned())
+                .or_insert(Cave::new(caves[0]));
+            left_cave.neighbors.push(caves[1].to_string());
+            let right_cave = cave_system
+                .caves
+                .entry(caves[1].to_owned())
+                .or_insert(Cave::new(caves[1]));
+            right_cave.neighbors.push(caves[0].to_string())
+        }
+        Ok(cave_system)
+    }
+}
+
+/// The two caves an edge joins.
+fn edge(line: &str) -> Result<[&str; 2], ParseError> {
+    let caves: [&str; 2] = match line.split('-').collect::<Vec<_>>()[..] {
+        [left, right] => [left, right],
+        _ => return Err(ParseError::locate(ErrorKind::InvalidEdge, line, line)),
+    };
+    if let Some(name) = caves.iter().find(|name| name.is_empty()) {
+        return Err(ParseError::locate(ErrorKind::EmptyName, line, name));
+    }
+    if Cave::new(caves[0]).is_big && Cave::new(caves[1]).is_big {
+        return Err(ParseError::locate(ErrorKind::BigCavesJoined, line, line));
+    }
+    Ok(caves)
+}
+
+/// Every line that isn't an edge between two caves.
+pub fn check(data: &str) -> Vec<ParseError> {
+    check::each_line(data, edge).1
+}
+
+/// Reads one connection per line, such as `start-A`.
+pub fn parse(data: &str) -> Result<CaveSystem, ParseError> {
+    CaveSystem::from_str(data)
+}
+
+/// Routes that visit each small cave at most once.
+pub fn part_1(cave_system: &CaveSystem) -> u32 {
+    cave_system.count_routes_par(true)
+}
+
+/// Routes that may visit a single small cave twice.
+pub fn part_2(cave_system: &CaveSystem) -> u32 {
+    cave_system.count_routes_par(false)
+}
+
+/// Day 12 as a [`Puzzle`], for tools that drive every day the same way.
+pub struct PassagePathing;
+
+impl Puzzle for PassagePathing {
+    const DAY: u8 = 12;
+    const TITLE: &'static str = "Passage Pathing";
+
+    type Input = CaveSystem;
+    type ErrorKind = ErrorKind;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(data: &str) -> Result<CaveSystem, ParseError> {
+        parse(data)
+    }
+
+    fn check(data: &str) -> Vec<ParseError> {
+        check(data)
+    }
+
+    fn part1(input: &CaveSystem) -> u32 {
+        part_1(input)
+    }
+
+    fn part2(input: &CaveSystem) -> u32 {
+        part_2(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let cave_system = CaveSystem::from_str(include_str!("../data/test_1.txt")).unwrap();
+        assert_eq!(cave_system.count_routes(true), 10);
+        assert_eq!(cave_system.count_routes(false), 36);
+        let cave_system = CaveSystem::from_str(include_str!("../data/test_2.txt")).unwrap();
+        assert_eq!(cave_system.count_routes(true), 19);
+        assert_eq!(cave_system.count_routes(false), 103);
+        let cave_system = CaveSystem::from_str(include_str!("../data/test_3.txt")).unwrap();
+        assert_eq!(cave_system.count_routes(true), 226);
+        assert_eq!(cave_system.count_routes(false), 3509);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = CaveSystem::from_str("start-A\nA-b-c").unwrap_err();
+        assert_eq!((err.kind, err.line), (ErrorKind::InvalidEdge, 2));
+        let err = CaveSystem::from_str("start-A\nA-").unwrap_err();
+        assert_eq!(
+            (err.kind, err.line, err.column),
+            (ErrorKind::EmptyName, 2, 3)
+        );
+        let err = CaveSystem::from_str("start-A\nA-B\nB-end").unwrap_err();
+        assert_eq!((err.kind, err.line), (ErrorKind::BigCavesJoined, 2));
+    }
+
+    #[test]
+    fn test_check() {
+        let problems = check("start-A\nA-b-c\nA-\nb-end\nA-B");
+        let found: Vec<_> = problems.iter().map(|e| (e.line, e.column)).collect();
+        assert_eq!(found, vec![(2, 1), (3, 3), (5, 1)]);
+    }
+
+    #[test]
+    fn test_no_start() {
+        let cave_system = CaveSystem::from_str("a-end").unwrap();
+        assert_eq!(cave_system.count_routes(true), 0);
+        assert_eq!(cave_system.count_routes_par(true), 0);
+    }
+
+    #[test]
+    fn test_count_routes_par() {
+        let examples = [
+            include_str!("../data/test_1.txt"),
+            include_str!("../data/test_2.txt"),
+            include_str!("../data/test_3.txt"),
+            // a passage straight through, one back to start and a repeated one
+            "start-end\nstart-a\na-end\na-start\nstart-a",
+        ];
+        for example in examples {
+            let cave_system = CaveSystem::from_str(example).unwrap();
+            for is_part_a in [true, false] {
+                assert_eq!(
+                    cave_system.count_routes_par(is_part_a),
+                    cave_system.count_routes(is_part_a)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_routes_from() {
+        let cave_system = CaveSystem::from_str(include_str!("../data/test_1.txt")).unwrap();
+        let end = cave_system.cave("end").unwrap();
+        assert_eq!(cave_system.count_routes_from(end, HashMap::new(), true), 1);
+        // having already been through b leaves only the routes that avoid it
+        let a = cave_system.cave("A").unwrap();
+        let visited = HashMap::from([("start".to_string(), 1), ("b".to_string(), 1)]);
+        assert_eq!(cave_system.count_routes_from(a, visited, true), 2);
+        assert_eq!(cave_system.caves().filter(|c| c.is_big()).count(), 1);
+    }
+}
@@ -0,0 +1,442 @@
+//! Day 13: Transparent Origami. Folds a sheet of transparent paper marked with
+//! dots until the dots spell out a code.
+
+use common::{ParseError as SpanError, Puzzle};
+use std::num::ParseIntError;
+use std::{collections::VecDeque, str::FromStr};
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ErrorKind {
+    #[error("the paper has no dots")]
+    NoDots,
+    #[error("expected a dot like `x,y`")]
+    InvalidDot,
+    #[error("invalid coordinate: {0}")]
+    InvalidCoordinate(ParseIntError),
+    #[error("expected a blank line between the dots and the folds")]
+    MissingSeparator,
+    #[error("expected `fold along x=<n>` or `fold along y=<n>`")]
+    InvalidFold,
+    #[error("fold line is outside of the paper")]
+    FoldOutOfRange,
+    #[error("coordinates may be at most {MAX_COORDINATE}")]
+    CoordinateTooLarge,
+}
+
+pub type ParseError = SpanError<ErrorKind>;
+
+/// Every spot on the paper is stored, so dots are kept within a sensible
+/// distance of the corner.
+pub const MAX_COORDINATE: usize = 4095;
+
+/// The paper, with `#` for a dot and `.` for a blank spot, and the folds
+/// still to make.
+#[derive(Debug, Clone)]
+pub struct Paper {
+    pub rows: Vec<Vec<char>>,
+    pub instructions: VecDeque<FoldInstruction>,
+}
+
+/// A fold along a line: `FoldY` folds the bottom up, `FoldX` folds the right
+/// side over to the left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FoldInstruction {
+    FoldY(usize),
+    FoldX(usize),
+}
+impl FromStr for FoldInstruction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (axis, at) = s
+            .strip_prefix("fold along ")
+            .and_then(|o| o.split_once('='))
+            .ok_or_else(|| ParseError::locate(ErrorKind::InvalidFold, s, s))?;
+        let at = at
+            .parse::<usize>()
+            .map_err(|e| ParseError::locate(ErrorKind::InvalidCoordinate(e), s, at))?;
+        match axis {
+            "y" => Ok(FoldInstruction::FoldY(at)),
+            "x" => Ok(FoldInstruction::FoldX(at)),
+            _ => Err(ParseError::locate(ErrorKind::InvalidFold, s, axis)),
+        }
+    }
+}
+
+impl Paper {
+    /// Folds the rows below `y_fold` up over the rows above it.
+    pub fn fold_y(&mut self, y_fold: usize) {
+        let mut folded_section = self.rows.split_off(y_fold);
+        folded_section.remove(0);
+
+        if folded_section.len() > self.rows.len() {
+            let tmp = self.rows.iter().rev().cloned().collect::<Vec<_>>();
+            self.rows = folded_section.iter().rev().cloned().collect::<Vec<_>>();
+            folded_section = tmp;
+        }
+        for (r1, r2) in self.rows.iter_mut().rev().zip(folded_section.iter()) {
+            for (x1, x2) in r1.iter_mut().zip(r2.iter()) {
+                match x2 {
+                    '.' => (),
+                    '#' => *x1 = '#',
+                    _ => unreachable!(),
+                }
+            }
+        }
+    }
+
+    /// Folds the part of a row right of `x_fold` over to the left.
+    pub fn fold_row(mut row: Vec<char>, x_fold: usize) -> Vec<char> {
+        let mut folded_section = row.split_off(x_fold);
+        folded_section.remove(0);
+        if folded_section.len() > row.len() {
+            let tmp = row.iter().rev().cloned().collect::<Vec<_>>();
+            row = folded_section.iter().rev().cloned().collect::<Vec<_>>();
+            folded_section = tmp;
+        }
+        for (x1, x2) in row.iter_mut().rev().zip(folded_section.iter()) {
+            match x2 {
+                '.' => (),
+                '#' => *x1 = '#',
+                _ => unreachable!(),
+            }
+        }
+        row
+    }
+
+    /// Folds every row along the column `x_fold`.
+    pub fn fold_x(&mut self, x_fold: usize) {
+        self.rows = self
+            .rows
+            .iter()
+            .cloned()
+            .map(|row| Paper::fold_row(row, x_fold))
+            .collect()
+    }
+
+    pub fn count_dots(&self) -> usize {
+        self.rows
+            .iter()
+            .map(|r| r.iter().filter(|&&x| x == '#').count())
+            .sum()
+    }
+
+    /// Makes the next fold, if there is one.
+    pub fn fold(&mut self) {
+        let _span = tracing::debug_span!("fold", rows = self.rows.len()).entered();
+        match self.instructions.pop_front() {
+            Some(FoldInstruction::FoldY(y)) => self.fold_y(y),
+            Some(FoldInstruction::FoldX(x)) => self.fold_x(x),
+            None => (),
+        }
+    }
+
+    /// Makes every remaining fold.
+    pub fn fold_all(&mut self) {
+        while !self.instructions.is_empty() {
+            self.fold()
+        }
+    }
+
+    /// The paper as text, one line per row.
+    pub fn render(&self) -> String {
+        self.rows
+            .iter()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl FromStr for Paper {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut problems = Vec::new();
+        Paper::read(s, &mut problems).ok_or_else(|| problems.swap_remove(0))
+    }
+}
+
+impl Paper {
+    /// The paper, if there were no problems to add to `problems`. Reading
+    /// carries on past problems so that they can all be reported.
+    fn read(s: &str, problems: &mut Vec<ParseError>) -> Option<Paper> {
+        let mut lines = s.lines();
+        let mut dots = Vec::new();
+        let mut first_fold = None;
+        // dots come first, then a blank line, then the folds
+        for line in lines.by_ref() {
+            if line.is_empty() {
+                break;
+            }
+            if line.starts_with("fold") {
+                problems.push(ParseError::locate(ErrorKind::MissingSeparator, s, line));
+                first_fold = Some(line);
+                break;
+            }
+            match dot(line) {
+                Ok(dot) => dots.push(dot),
+                Err(e) => problems.push(e.within(s, line)),
+            }
+        }
+        let max_x = dots.iter().map(|&(x, _y)| x).max();
+        let max_y = dots.iter().map(|&(_x, y)| y).max();
+        let max = max_x.zip(max_y);
+        if max.is_none() && problems.is_empty() {
+            problems.push(ParseError::whole_input(ErrorKind::NoDots));
+        }
+
+        // the folds can only be checked against a paper whose dots all read
+        let mut size = max
+            .filter(|_| problems.is_empty())
+            .map(|(max_x, max_y)| (max_x + 1, max_y + 1));
+        let mut instructions = VecDeque::new();
+        for line in first_fold.into_iter().chain(lines) {
+            if line.is_empty() {
+                continue;
+            }
+            let instruction = match FoldInstruction::from_str(line) {
+                Ok(instruction) => instruction,
+                Err(e) => {
+                    problems.push(e.within(s, line));
+                    continue;
+                }
+            };
+            // folding along a line outside the paper would lose track of the dots
+            if let Some((width, height)) = size.as_mut() {
+                let (at, side) = match instruction {
+                    FoldInstruction::FoldX(x) => (x, width),
+                    FoldInstruction::FoldY(y) => (y, height),
+                };
+                if at >= *side {
+                    problems.push(ParseError::locate(ErrorKind::FoldOutOfRange, s, line));
+                    continue;
+                }
+                *side = at.max(*side - at - 1);
+            }
+            instructions.push_back(instruction);
+        }
+        if !problems.is_empty() {
+            return None;
+        }
+
+        let (max_x, max_y) = max?;
+        let mut rows = (0..=max_y)
+            .map(|_| vec!['.'; max_x + 1])
+            .collect::<Vec<_>>();
+        for (x, y) in dots {
+            rows[y][x] = '#';
+        }
+        Some(Paper { rows, instructions })
+    }
+}
+
+/// Reads a dot like `x,y`.
+fn dot(s: &str) -> Result<(usize, usize), ParseError> {
+    let (x, y) = s
+        .split_once(',')
+        .ok_or_else(|| ParseError::locate(ErrorKind::InvalidDot, s, s))?;
+    let coordinate = |v: &str| match v.parse::<usize>() {
+        Ok(c) if c > MAX_COORDINATE => Err(ParseError::locate(ErrorKind::CoordinateTooLarge, s, v)),
+        Ok(c) => Ok(c),
+        Err(e) => Err(ParseError::locate(ErrorKind::InvalidCoordinate(e), s, v)),
+    };
+    Ok((coordinate(x)?, coordinate(y)?))
+}
+
+/// Every bad dot and fold, a missing blank line between them, and folds
+/// that miss the paper.
+pub fn check(data: &str) -> Vec<ParseError> {
+    let mut problems = Vec::new();
+    Paper::read(data, &mut problems);
+    problems
+}
+
+/// Reads the `x,y` dots, a blank line, then the `fold along` instructions.
+pub fn parse(data: &str) -> Result<Paper, ParseError> {
+    Paper::from_str(data)
+}
+
+/// The number of dots left after the first fold.
+pub fn part_1(paper: &Paper) -> usize {
+    let mut paper = paper.clone();
+    paper.fold();
+    paper.count_dots()
+}
+
+/// The paper after every fold.
+pub fn part_2(paper: &Paper) -> String {
+    let mut paper = paper.clone();
+    paper.fold_all();
+    paper.render()
+}
+
+/// Day 13 as a [`Puzzle`], for tools that drive every day the same way.
+pub struct TransparentOrigami;
+
+impl Puzzle for TransparentOrigami {
+    const DAY: u8 = 13;
+    const TITLE: &'static str = "Transparent Origami";
+
+    type Input = Paper;
+    type ErrorKind = ErrorKind;
+    type Answer1 = usize;
+    type Answer2 = String;
+
+    fn parse(data: &str) -> Result<Paper, ParseError> {
+        parse(data)
+    }
+
+    fn check(data: &str) -> Vec<ParseError> {
+        check(data)
+    }
+
+    fn part1(input: &Paper) -> usize {
+        part_1(input)
+    }
+
+    fn part2(input: &Paper) -> String {
+        part_2(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test() {
+        let mut paper = Paper::from_str(include_str!("../data/test.txt")).unwrap();
+
+        let expected = vec![
+            vec!['.', '.', '.', '#', '.', '.', '#', '.', '.', '#', '.'],
+            vec!['.', '.', '.', '.', '#', '.', '.', '.', '.', '.', '.'],
+            vec!['.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.'],
+            vec!['#', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.'],
+            vec!['.', '.', '.', '#', '.', '.', '.', '.', '#', '.', '#'],
+            vec!['.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.'],
+            vec!['.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.'],
+            vec!['.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.'],
+            vec!['.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.'],
+            vec!['.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.'],
+            vec!['.', '#', '.', '.', '.', '.', '#', '.', '#', '#', '.'],
+            vec!['.', '.', '.', '.', '#', '.', '.', '.', '.', '.', '.'],
+            vec!['.', '.', '.', '.', '.', '.', '#', '.', '.', '.', '#'],
+            vec!['#', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.'],
+            vec!['#', '.', '#', '.', '.', '.', '.', '.', '.', '.', '.'],
+        ];
+
+        for (row, expected_row) in paper.rows.iter().zip(expected.iter()) {
+            assert_eq!(row, expected_row);
+        }
+        paper.fold();
+        let expected = [
+            vec!['#', '.', '#', '#', '.', '.', '#', '.', '.', '#', '.'],
+            vec!['#', '.', '.', '.', '#', '.', '.', '.', '.', '.', '.'],
+            vec!['.', '.', '.', '.', '.', '.', '#', '.', '.', '.', '#'],
+            vec!['#', '.', '.', '.', '#', '.', '.', '.', '.', '.', '.'],
+            vec!['.', '#', '.', '#', '.', '.', '#', '.', '#', '#', '#'],
+            vec!['.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.'],
+            vec!['.', '.', '.', '.', '.', '.', '.', '.', '.', '.', '.'],
+        ];
+        for (i, (row, expected_row)) in paper.rows.iter().zip(expected.iter()).enumerate() {
+            println!("{}", i);
+            assert_eq!(row, expected_row);
+        }
+
+        assert_eq!(paper.count_dots(), 17);
+        paper.fold();
+        let expected = [
+            vec!['#', '#', '#', '#', '#'],
+            vec!['#', '.', '.', '.', '#'],
+            vec!['#', '.', '.', '.', '#'],
+            vec!['#', '.', '.', '.', '#'],
+            vec!['#', '#', '#', '#', '#'],
+            vec!['.', '.', '.', '.', '.'],
+            vec!['.', '.', '.', '.', '.'],
+        ];
+        for (i, (row, expected_row)) in paper.rows.iter().zip(expected.iter()).enumerate() {
+            println!("{}", i);
+            assert_eq!(row, expected_row);
+        }
+
+        assert_eq!(paper.count_dots(), 16);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Paper::from_str("").unwrap_err();
+        assert_eq!(err.kind, ErrorKind::NoDots);
+        let err = Paper::from_str("6,10\n0;14\n\nfold along y=7").unwrap_err();
+        assert_eq!((err.kind, err.line), (ErrorKind::InvalidDot, 2));
+        let err = Paper::from_str("6,10\n0,1x\n").unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::InvalidCoordinate(_)));
+        assert_eq!((err.line, err.column), (2, 3));
+        let err = Paper::from_str("6,10\nfold along y=7").unwrap_err();
+        assert_eq!((err.kind, err.line), (ErrorKind::MissingSeparator, 2));
+        let err = Paper::from_str("6,10\n\nfold along z=7").unwrap_err();
+        assert_eq!(
+            (err.kind, err.line, err.column),
+            (ErrorKind::InvalidFold, 3, 12)
+        );
+        let err = Paper::from_str("6,10\n\nfold along y=5\nfold along y=6").unwrap_err();
+        assert_eq!((err.kind, err.line), (ErrorKind::FoldOutOfRange, 4));
+        let err = Paper::from_str(
+            "6,10
+4096,0
+
+fold along y=5",
+        )
+        .unwrap_err();
+        assert_eq!((err.kind, err.line), (ErrorKind::CoordinateTooLarge, 2));
+    }
+
+    #[test]
+    fn test_check() {
+        assert!(check(include_str!("../data/test.txt")).is_empty());
+        let problems = check("6,10\n0;14\n9,x\nfold along y=7\nfold along z=7\nfold alng x=5");
+        let found: Vec<_> = problems.iter().map(|e| (e.kind.clone(), e.line)).collect();
+        assert_eq!(found[0], (ErrorKind::InvalidDot, 2));
+        assert!(matches!(found[1], (ErrorKind::InvalidCoordinate(_), 3)));
+        assert_eq!(found[2], (ErrorKind::MissingSeparator, 4));
+        assert_eq!(found[3], (ErrorKind::InvalidFold, 5));
+        assert_eq!(found[4], (ErrorKind::InvalidFold, 6));
+        assert_eq!(found.len(), 5);
+        // folds are measured against the paper once the dots are fine
+        let problems = check("6,10\n\nfold along y=11\nfold along y=5\nfold along x=7");
+        let found: Vec<_> = problems.iter().map(|e| (e.kind.clone(), e.line)).collect();
+        assert_eq!(
+            found,
+            vec![
+                (ErrorKind::FoldOutOfRange, 3),
+                (ErrorKind::FoldOutOfRange, 5)
+            ]
+        );
+    }
+
+    fn transpose(rows: &[Vec<char>]) -> Vec<Vec<char>> {
+        (0..rows[0].len())
+            .map(|x| rows.iter().map(|row| row[x]).collect())
+            .collect()
+    }
+
+    fn sheet() -> impl Strategy<Value = (Vec<Vec<char>>, usize)> {
+        (1..8usize, 2..12usize).prop_flat_map(|(height, width)| {
+            let row = prop::collection::vec(prop::sample::select(vec!['.', '#']), width);
+            (prop::collection::vec(row, height), 0..width)
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn fold_x_is_transposed_fold_y((rows, at) in sheet()) {
+            let mut paper = Paper { rows: rows.clone(), instructions: VecDeque::new() };
+            paper.fold_x(at);
+            let mut transposed = Paper { rows: transpose(&rows), instructions: VecDeque::new() };
+            transposed.fold_y(at);
+            prop_assert_eq!(paper.rows, transpose(&transposed.rows));
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use generate::day_14::Params;
+
+const SEED: u64 = 14;
+
+/// A template over `elements` letters with a rule for every pair of them.
+fn input(elements: usize) -> String {
+    let params = Params {
+        elements,
+        ..Params::default()
+    };
+    generate::day_14::generate(&mut generate::rng(SEED), &params)
+}
+
+fn step(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day-14/Polymer::step");
+    for elements in [2, 5, 10, 18, 26] {
+        let polymer = day_14::parse(&input(elements)).unwrap();
+        group.throughput(Throughput::Elements(elements as u64 * elements as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(elements),
+            &polymer,
+            |b, polymer| {
+                b.iter_batched(
+                    || polymer.clone(),
+                    |mut polymer| polymer.step(),
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, step);
+criterion_main!(benches);
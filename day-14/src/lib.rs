@@ -0,0 +1,338 @@
+//! Day 14: Extended Polymerization. Grows a polymer by inserting elements
+//! between pairs, counting pairs rather than building the polymer itself.
+
+use common::{ParseError as SpanError, Puzzle};
+use std::{collections::HashMap, str::FromStr};
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ErrorKind {
+    #[error("the first line must be the polymer template")]
+    MissingTemplate,
+    #[error("expected a rule like `AB -> C`")]
+    InvalidRule,
+    #[error("a rule for this pair was already given")]
+    DuplicateRule,
+}
+
+pub type ParseError = SpanError<ErrorKind>;
+
+/// How many times each pair of elements occurs, the insertion rule for each
+/// pair as the two pairs it becomes, and the elements at either end.
+#[derive(Debug, Clone)]
+pub struct Polymer {
+    pub counter: HashMap<String, u64>,
+    pub instructions: HashMap<String, (String, String)>,
+    pub start: char,
+    pub end: char,
+}
+
+impl Polymer {
+    /// Applies every insertion rule once.
+    pub fn step(&mut self) {
+        let _span = tracing::trace_span!("step", pairs = self.counter.len()).entered();
+        let old_count = self.counter.clone();
+        for (key, (left_child, right_child)) in self.instructions.iter() {
+            let to_add = match old_count.get(key) {
+                Some(&to_add) if to_add > 0 => to_add,
+                _ => continue,
+            };
+            let count = self.counter.get_mut(key).unwrap();
+            *count -= to_add;
+            // pairs without a rule of their own are never split again
+            let count = self.counter.entry(left_child.clone()).or_insert(0);
+            *count += to_add;
+            let count = self.counter.entry(right_child.clone()).or_insert(0);
+            *count += to_add;
+        }
+    }
+
+    /// [`Polymer::step`], unless scoring afterwards could outgrow a `u64`,
+    /// in which case the polymer is left as it was.
+    pub fn checked_step(&mut self) -> Option<()> {
+        // a step at most doubles the pairs, and scoring counts each element
+        // of each pair
+        let pairs = self
+            .counter
+            .values()
+            .try_fold(0u64, |total, &n| total.checked_add(n))?;
+        if pairs > u64::MAX / 4 {
+            return None;
+        }
+        self.step();
+        Some(())
+    }
+
+    /// The count of the most common element minus that of the least common.
+    pub fn score(&self) -> u64 {
+        let mut counter = HashMap::new();
+        for (key, val) in self.counter.iter() {
+            for c in key.chars() {
+                let count = counter.entry(c).or_insert(0);
+                *count += val;
+            }
+        }
+        let count = counter.entry(self.start).or_insert(0);
+        *count += 1;
+        let count = counter.entry(self.end).or_insert(0);
+        *count += 1;
+        let min = counter.values().min().unwrap();
+        let max = counter.values().max().unwrap();
+        (max - min) / 2
+    }
+}
+impl FromStr for Polymer {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut problems = Vec::new();
+        Polymer::read(s, &mut problems).ok_or_else(|| problems.swap_remove(0))
+    }
+}
+
+impl Polymer {
+    /// The polymer, if there were no problems to add to `problems`. Reading
+    /// carries on past problems so that they can all be reported.
+    fn read(s: &str, problems: &mut Vec<ParseError>) -> Option<Polymer> {
+        let mut lines = s.lines().filter(|x| !x.is_empty()).peekable();
+        let template = match lines.next_if(|line| !line.contains("->")) {
+            Some(line) => line.chars().collect::<Vec<_>>(),
+            None => {
+                problems.push(match lines.peek() {
+                    Some(line) => ParseError::locate(ErrorKind::MissingTemplate, s, line),
+                    None => ParseError::whole_input(ErrorKind::MissingTemplate),
+                });
+                Vec::new()
+            }
+        };
+        let mut instructions = HashMap::new();
+        for line in lines {
+            let (pair, children) = match rule(line) {
+                Ok(rule) => rule,
+                Err(e) => {
+                    problems.push(e.within(s, line));
+                    continue;
+                }
+            };
+            if instructions.insert(String::from(pair), children).is_some() {
+                problems.push(ParseError::locate(ErrorKind::DuplicateRule, s, pair));
+            }
+        }
+        if !problems.is_empty() {
+            return None;
+        }
+
+        let mut counter: HashMap<String, u64> =
+            instructions.keys().map(|k| (k.clone(), 0)).collect();
+
+        for pair in template.windows(2) {
+            let count = counter.entry(pair.iter().collect()).or_insert(0);
+            *count += 1;
+        }
+
+        Some(Polymer {
+            counter,
+            instructions,
+            start: template[0],
+            end: template[template.len() - 1],
+        })
+    }
+}
+
+/// Reads a rule like `CH -> B` as the pair and the two pairs it becomes.
+fn rule(line: &str) -> Result<(&str, (String, String)), ParseError> {
+    let invalid = || ParseError::locate(ErrorKind::InvalidRule, line, line);
+    let (pair, insert) = line.split_once(" -> ").ok_or_else(invalid)?;
+    let (mut pair_chars, mut insert_chars) = (pair.chars(), insert.chars());
+    let (left, right, middle) = match (
+        pair_chars.next(),
+        pair_chars.next(),
+        pair_chars.next(),
+        insert_chars.next(),
+        insert_chars.next(),
+    ) {
+        (Some(left), Some(right), None, Some(middle), None) => (left, right, middle),
+        _ => return Err(invalid()),
+    };
+    let children = (
+        [left, middle].iter().collect::<String>(),
+        [middle, right].iter().collect::<String>(),
+    );
+    Ok((pair, children))
+}
+
+/// Every line that isn't the template or a rule, and every rule for a pair
+/// that already has one.
+pub fn check(data: &str) -> Vec<ParseError> {
+    let mut problems = Vec::new();
+    Polymer::read(data, &mut problems);
+    problems
+}
+
+/// Reads the template, a blank line, then rules like `CH -> B`.
+pub fn parse(data: &str) -> Result<Polymer, ParseError> {
+    Polymer::from_str(data)
+}
+
+/// The score after 10 steps.
+pub fn part_1(polymer: &Polymer) -> u64 {
+    score_after(polymer, 10)
+}
+
+/// The score after 40 steps.
+pub fn part_2(polymer: &Polymer) -> u64 {
+    score_after(polymer, 40)
+}
+
+/// Day 14 as a [`Puzzle`], for tools that drive every day the same way.
+pub struct ExtendedPolymerization;
+
+impl Puzzle for ExtendedPolymerization {
+    const DAY: u8 = 14;
+    const TITLE: &'static str = "Extended Polymerization";
+
+    type Input = Polymer;
+    type ErrorKind = ErrorKind;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(data: &str) -> Result<Polymer, ParseError> {
+        parse(data)
+    }
+
+    fn check(data: &str) -> Vec<ParseError> {
+        check(data)
+    }
+
+    fn part1(input: &Polymer) -> u64 {
+        part_1(input)
+    }
+
+    fn part2(input: &Polymer) -> u64 {
+        part_2(input)
+    }
+}
+
+/// The score after `steps` steps.
+pub fn score_after(polymer: &Polymer, steps: usize) -> u64 {
+    let _span = tracing::debug_span!("score_after", steps).entered();
+    let mut polymer = polymer.clone();
+    for _ in 0..steps {
+        polymer.step();
+    }
+    polymer.score()
+}
+
+/// [`score_after`], or `None` if the polymer outgrows a `u64` on the way.
+pub fn checked_score_after(polymer: &Polymer, steps: usize) -> Option<u64> {
+    let _span = tracing::debug_span!("checked_score_after", steps).entered();
+    let mut polymer = polymer.clone();
+    for _ in 0..steps {
+        polymer.checked_step()?;
+    }
+    Some(polymer.score())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test() {
+        let mut polymer = Polymer::from_str(include_str!("../data/test.txt")).unwrap();
+
+        for _ in 0..10 {
+            polymer.step();
+        }
+        println!("{:#?}", polymer.counter);
+        assert_eq!(polymer.score(), 1588);
+    }
+
+    #[test]
+    fn test_checked() {
+        let polymer = Polymer::from_str(include_str!("../data/test.txt")).unwrap();
+        assert_eq!(checked_score_after(&polymer, 10), Some(1588));
+        assert_eq!(checked_score_after(&polymer, 100), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Polymer::from_str("").unwrap_err();
+        assert_eq!(err.kind, ErrorKind::MissingTemplate);
+        let err = Polymer::from_str("CH -> B\nNNCB").unwrap_err();
+        assert_eq!((err.kind, err.line), (ErrorKind::MissingTemplate, 1));
+        let err = Polymer::from_str("NNCB\n\nCH -> B\nHHH -> N").unwrap_err();
+        assert_eq!((err.kind, err.line), (ErrorKind::InvalidRule, 4));
+        let err = Polymer::from_str("NNCB\n\nCH -> B\nCH -> N").unwrap_err();
+        assert_eq!(
+            (err.kind, err.line, err.column),
+            (ErrorKind::DuplicateRule, 4, 1)
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        assert!(check(include_str!("../data/test.txt")).is_empty());
+        let problems = check("NNCB\n\nCH -> B\nHHH -> N\nCH -> N\nNC - C\nNN -> C");
+        let found: Vec<_> = problems.iter().map(|e| (e.kind.clone(), e.line)).collect();
+        assert_eq!(
+            found,
+            vec![
+                (ErrorKind::InvalidRule, 4),
+                (ErrorKind::DuplicateRule, 5),
+                (ErrorKind::InvalidRule, 6)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pairs_without_rules() {
+        let mut polymer = Polymer::from_str("NNCB\n\nNN -> C").unwrap();
+        polymer.step();
+        assert_eq!(polymer.counter["NC"], 2);
+        assert_eq!(polymer.counter["CN"], 1);
+    }
+
+    /// A template and a full rule set over the first few letters.
+    fn polymer() -> impl Strategy<Value = (String, u8, Vec<char>)> {
+        (2..5u8).prop_flat_map(|elements| {
+            let element = (0..elements).prop_map(|i| char::from(b'A' + i));
+            (
+                prop::collection::vec(element.clone(), 2..8).prop_map(String::from_iter),
+                Just(elements),
+                prop::collection::vec(element, usize::from(elements * elements)),
+            )
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn pair_counts_match_the_string((template, elements, inserts) in polymer(), steps in 0..6usize) {
+            let mut rules = HashMap::new();
+            let mut text = format!("{}\n\n", template);
+            for (i, &insert) in inserts.iter().enumerate() {
+                let pair = [b'A' + i as u8 / elements, b'A' + i as u8 % elements];
+                rules.insert((pair[0] as char, pair[1] as char), insert);
+                text += &format!("{} -> {}\n", std::str::from_utf8(&pair).unwrap(), insert);
+            }
+            let mut polymer = Polymer::from_str(&text).unwrap();
+            let mut chain = template.chars().collect::<Vec<_>>();
+            for _ in 0..steps {
+                polymer.step();
+                let mut grown = vec![chain[0]];
+                for pair in chain.windows(2) {
+                    grown.push(rules[&(pair[0], pair[1])]);
+                    grown.push(pair[1]);
+                }
+                chain = grown;
+            }
+            let mut expected = HashMap::new();
+            for pair in chain.windows(2) {
+                *expected.entry(pair.iter().collect::<String>()).or_insert(0) += 1;
+            }
+            polymer.counter.retain(|_, &mut count| count > 0);
+            prop_assert_eq!(polymer.counter, expected);
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use generate::day_15::Params;
+
+const SEED: u64 = 15;
+
+fn dijkstra(c: &mut Criterion) {
+    let cave = day_15::parse(&generate::day_15::generate(
+        &mut generate::rng(SEED),
+        &Params::sized(25),
+    ))
+    .unwrap();
+    let mut group = c.benchmark_group("day-15/dijkstra_alg");
+    group.sample_size(20);
+    for n in 1..=5 {
+        let tiled = cave.tile(n);
+        group.throughput(Throughput::Elements((25 * n * 25 * n) as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(25 * n), &tiled, |b, cave| {
+            b.iter(|| cave.dijkstra_alg(0))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, dijkstra);
+criterion_main!(benches);
@@ -0,0 +1,307 @@
+//! Day 15: Chiton. Finds the path of lowest total risk across a cave, and
+//! across the full cave made of tiled copies of it.
+
+use common::{ParseError as SpanError, Puzzle};
+use grid::{Grid, GridErrorKind};
+use std::cmp::{Ord, Ordering, PartialOrd};
+use std::collections::{BinaryHeap, HashSet};
+use std::str::FromStr;
+use thiserror::Error;
+use tracing::field;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ErrorKind {
+    #[error("{0}")]
+    Grid(GridErrorKind),
+}
+
+pub type ParseError = SpanError<ErrorKind>;
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+struct State {
+    pos: usize,
+    cost: u32,
+}
+// The priority queue depends on `Ord`.
+// Explicitly implement the trait so the queue becomes a min-heap
+// instead of a max-heap.
+impl Ord for State {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Notice that the we flip the ordering on costs.
+        // In case of a tie we compare positions - this step is necessary
+        // to make implementations of `PartialEq` and `Ord` consistent.
+        other
+            .cost
+            .cmp(&self.cost)
+            .then_with(|| self.pos.cmp(&other.pos))
+    }
+}
+
+// `PartialOrd` needs to be implemented as well.
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+/// The risk level of every position in the cave.
+#[derive(Debug, Clone)]
+pub struct Cave {
+    floor: Grid<u32>,
+}
+
+impl FromStr for Cave {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Cave {
+            floor: Grid::parse_digits(s).map_err(|e| e.map_kind(ErrorKind::Grid))?,
+        })
+    }
+}
+
+impl Cave {
+    pub fn floor(&self) -> &Grid<u32> {
+        &self.floor
+    }
+
+    /// The full cave, `n` copies of this one across and down. Panics if `n`
+    /// is 0.
+    pub fn tile(&self, n: usize) -> Self {
+        fn rolled_add(x: u32, i: usize) -> u32 {
+            let tmp = x + i as u32;
+            if tmp <= 9 {
+                tmp
+            } else {
+                tmp - 9
+            }
+        }
+        let (row_len, col_len) = (self.floor.row_len(), self.floor.col_len());
+        // each tile is one riskier than the tile above it or to its left
+        let floor = Grid::from_fn(row_len * n, col_len * n, |x, y| {
+            rolled_add(
+                self.floor[(x % row_len, y % col_len)],
+                x / row_len + y / col_len,
+            )
+        })
+        .expect("a cave is tiled at least once");
+        Cave { floor }
+    }
+    /// Lowest total risk from `start` to the bottom right corner.
+    pub fn dijkstra_alg(&self, start: usize) -> Option<u32> {
+        self.dijkstra_with(start, |_, _| ())
+    }
+
+    /// Like [`Cave::dijkstra_alg`], calling `on_visit` with each position and
+    /// its lowest total risk as the search settles it.
+    pub fn dijkstra_with<F>(&self, start: usize, mut on_visit: F) -> Option<u32>
+    where
+        F: FnMut(usize, u32),
+    {
+        let span = tracing::debug_span!(
+            "dijkstra",
+            cells = self.floor.len(),
+            pops = field::Empty,
+            pushes = field::Empty,
+            settled = field::Empty
+        );
+        let _span = span.enter();
+        on_visit(start, 0);
+        if start == self.floor.len() - 1 {
+            return Some(0);
+        }
+        let mut to_visit: BinaryHeap<_> = self
+            .floor
+            .neighbors4(start)
+            .map(|x| State {
+                pos: x,
+                cost: self.floor[x],
+            })
+            .collect();
+        let mut have_visited = HashSet::new();
+        // no backtracking
+        have_visited.insert(start);
+        let (mut pops, mut pushes) = (0u64, to_visit.len() as u64);
+        let counted = |pops: u64, pushes: u64, settled: usize| {
+            span.record("pops", pops);
+            span.record("pushes", pushes);
+            span.record("settled", settled);
+        };
+
+        while let Some(State { pos, cost }) = to_visit.pop() {
+            pops += 1;
+            if pos == self.floor.len() - 1 {
+                on_visit(pos, cost);
+                counted(pops, pushes, have_visited.len() + 1);
+                return Some(cost);
+            }
+            if !have_visited.contains(&pos) {
+                have_visited.insert(pos);
+                on_visit(pos, cost);
+                for neighbor in self.floor.neighbors4(pos) {
+                    to_visit.push(State {
+                        pos: neighbor,
+                        cost: cost + self.floor[neighbor],
+                    });
+                    pushes += 1;
+                }
+            }
+        }
+        counted(pops, pushes, have_visited.len());
+        None
+    }
+
+    /// The positions on a path of lowest total risk from the top left to
+    /// the bottom right, both included.
+    pub fn lowest_risk_path(&self) -> Option<Vec<usize>> {
+        // when each position was settled, and at what total risk
+        let mut settled = vec![None; self.floor.len()];
+        let mut order = 0;
+        self.dijkstra_with(0, |pos, cost| {
+            settled[pos] = Some((order, cost));
+            order += 1;
+        })?;
+        let mut path = vec![self.floor.len() - 1];
+        let mut cur = self.floor.len() - 1;
+        // step back to whichever neighbour was settled earlier and accounts
+        // for the risk so far; the one that reached `cur` always qualifies
+        while cur != 0 {
+            let (cur_order, cur_cost) = settled[cur]?;
+            cur = self.floor.neighbors4(cur).find(|&n| {
+                settled[n].is_some_and(|(order, cost)| {
+                    order < cur_order && cost + self.floor[cur] == cur_cost
+                })
+            })?;
+            path.push(cur);
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Reads a grid of single digit risk levels.
+pub fn parse(data: &str) -> Result<Cave, ParseError> {
+    Cave::from_str(data)
+}
+
+/// Every row with a risk level that isn't a digit, or that doesn't line up
+/// with the first row.
+pub fn check(data: &str) -> Vec<ParseError> {
+    Grid::check_digits(data)
+        .into_iter()
+        .map(|e| e.map_kind(ErrorKind::Grid))
+        .collect()
+}
+
+/// The lowest total risk from the top left to the bottom right.
+pub fn part_1(cave: &Cave) -> u32 {
+    cave.dijkstra_alg(0).expect("no path through the cave")
+}
+
+/// The lowest total risk across the cave tiled five times in each direction.
+pub fn part_2(cave: &Cave) -> u32 {
+    cave.tile(5)
+        .dijkstra_alg(0)
+        .expect("no path through the tiled cave")
+}
+
+/// Day 15 as a [`Puzzle`], for tools that drive every day the same way.
+pub struct Chiton;
+
+impl Puzzle for Chiton {
+    const DAY: u8 = 15;
+    const TITLE: &'static str = "Chiton";
+
+    type Input = Cave;
+    type ErrorKind = ErrorKind;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(data: &str) -> Result<Cave, ParseError> {
+        parse(data)
+    }
+
+    fn check(data: &str) -> Vec<ParseError> {
+        check(data)
+    }
+
+    fn part1(input: &Cave) -> u32 {
+        part_1(input)
+    }
+
+    fn part2(input: &Cave) -> u32 {
+        part_2(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test() {
+        let cave = Cave::from_str(include_str!("../data/test.txt")).unwrap();
+        assert_eq!(cave.dijkstra_alg(0), Some(40));
+        let tiled_cave = cave.tile(5);
+        assert_eq!(tiled_cave.dijkstra_alg(0), Some(315));
+        let cave = Cave::from_str("7").unwrap();
+        assert_eq!((part_1(&cave), part_2(&cave)), (0, 38));
+    }
+
+    #[test]
+    fn test_visit_order() {
+        let cave = Cave::from_str(include_str!("../data/test.txt")).unwrap();
+        let mut visits = Vec::new();
+        let risk = cave.dijkstra_with(0, |pos, cost| visits.push((pos, cost)));
+        assert_eq!(risk, Some(40));
+        assert_eq!(visits.first(), Some(&(0, 0)));
+        assert_eq!(visits.last(), Some(&(99, 40)));
+        assert!(visits.windows(2).all(|w| w[0].1 <= w[1].1));
+    }
+
+    #[test]
+    fn test_lowest_risk_path() {
+        let cave = Cave::from_str(include_str!("../data/test.txt")).unwrap();
+        let path = cave.lowest_risk_path().unwrap();
+        assert_eq!((path[0], path[path.len() - 1]), (0, 99));
+        let risk: u32 = path[1..].iter().map(|&i| cave.floor[i]).sum();
+        assert_eq!(risk, 40);
+        assert_eq!(
+            Cave::from_str("7").unwrap().lowest_risk_path(),
+            Some(vec![0])
+        );
+    }
+
+    /// Relaxes every edge until nothing changes, as a slow but obvious
+    /// reference for the shortest path.
+    fn bellman_ford(cave: &Cave) -> u32 {
+        let mut risk = vec![u32::MAX; cave.floor.len()];
+        risk[0] = 0;
+        for _ in 0..cave.floor.len() {
+            for from in 0..cave.floor.len() {
+                if risk[from] == u32::MAX {
+                    continue;
+                }
+                for to in cave.floor.neighbors4(from) {
+                    risk[to] = risk[to].min(risk[from] + cave.floor[to]);
+                }
+            }
+        }
+        risk[cave.floor.len() - 1]
+    }
+
+    fn cave() -> impl Strategy<Value = Cave> {
+        (1..7usize, 2..7usize).prop_flat_map(|(row_len, col_len)| {
+            prop::collection::vec(1..=9u32, row_len * col_len).prop_map(move |cells| Cave {
+                floor: Grid::from_vec(cells, row_len, col_len).unwrap(),
+            })
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn dijkstra_matches_bellman_ford(cave in cave()) {
+            prop_assert_eq!(cave.dijkstra_alg(0), Some(bellman_ford(&cave)));
+        }
+    }
+}
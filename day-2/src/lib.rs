@@ -0,0 +1,264 @@
+//! Day 2: Dive! Steers the submarine through a course of commands, first
+//! moving directly and then by adjusting its aim.
+
+use common::check;
+use common::stream::{self, Lines};
+use common::{ParseError as SpanError, Puzzle};
+use std::convert::TryFrom;
+use std::io::BufRead;
+use std::num::ParseIntError;
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ErrorKind {
+    #[error("expected a direction and an amount")]
+    MissingAmount,
+    #[error("invalid amount: {0}")]
+    InvalidAmount(ParseIntError),
+    #[error("unknown direction, expected forward, down or up")]
+    UnknownDirection,
+}
+
+pub type ParseError = SpanError<ErrorKind>;
+pub type StreamError = stream::StreamError<ErrorKind>;
+
+/// Where the submarine ends up, with depth growing downwards.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Position {
+    pub x: i64,
+    pub depth: i64,
+}
+
+impl Position {
+    /// The horizontal position multiplied by the depth.
+    pub fn product(&self) -> i64 {
+        self.x.saturating_mul(self.depth)
+    }
+
+    /// Where one command moves the submarine, with up and down changing the
+    /// depth.
+    pub fn moved(self, command: &Command) -> Position {
+        match command {
+            Command::Forward(x) => Position {
+                x: self.x.saturating_add(i64::from(*x)),
+                ..self
+            },
+            Command::Up(y) => Position {
+                depth: self.depth.saturating_sub(i64::from(*y)),
+                ..self
+            },
+            Command::Down(y) => Position {
+                depth: self.depth.saturating_add(i64::from(*y)),
+                ..self
+            },
+        }
+    }
+}
+
+/// One line of the course.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Forward(i32),
+    Down(i32),
+    Up(i32),
+}
+
+impl TryFrom<&str> for Command {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (direction, amount) = value
+            .split_once(' ')
+            .ok_or_else(|| ParseError::locate(ErrorKind::MissingAmount, value, value))?;
+        let amount = amount
+            .parse()
+            .map_err(|e| ParseError::locate(ErrorKind::InvalidAmount(e), value, amount))?;
+        match direction {
+            "forward" => Ok(Command::Forward(amount)),
+            "down" => Ok(Command::Down(amount)),
+            "up" => Ok(Command::Up(amount)),
+            _ => Err(ParseError::locate(
+                ErrorKind::UnknownDirection,
+                value,
+                direction,
+            )),
+        }
+    }
+}
+
+/// Where the submarine ends up when up and down only change its aim.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PositionWithAim {
+    pub x: i64,
+    pub depth: i64,
+    pub aim: i64,
+}
+
+impl PositionWithAim {
+    /// The horizontal position multiplied by the depth.
+    pub fn product(&self) -> i64 {
+        self.x.saturating_mul(self.depth)
+    }
+
+    /// Where one command moves the submarine, with up and down changing the
+    /// aim.
+    pub fn moved(self, command: &Command) -> PositionWithAim {
+        match command {
+            Command::Forward(x) => PositionWithAim {
+                x: self.x.saturating_add(i64::from(*x)),
+                depth: self
+                    .depth
+                    .saturating_add(self.aim.saturating_mul(i64::from(*x))),
+                ..self
+            },
+            Command::Up(y) => PositionWithAim {
+                aim: self.aim.saturating_sub(i64::from(*y)),
+                ..self
+            },
+            Command::Down(y) => PositionWithAim {
+                aim: self.aim.saturating_add(i64::from(*y)),
+                ..self
+            },
+        }
+    }
+}
+
+/// Reads one command per line, such as `forward 5`.
+pub fn parse(data: &str) -> Result<Vec<Command>, ParseError> {
+    data.lines()
+        .enumerate()
+        .map(|(i, line)| Command::try_from(line).map_err(|e| e.on_line(i + 1)))
+        .collect()
+}
+
+/// Every line that isn't a command.
+pub fn check(data: &str) -> Vec<ParseError> {
+    check::each_line(data, Command::try_from).1
+}
+
+/// Reads one command per line as they are needed, for courses too long to
+/// hold.
+pub fn read_commands<R: BufRead>(reader: R) -> impl Iterator<Item = Result<Command, StreamError>> {
+    Lines::new(reader).parsed(|line| Command::try_from(line))
+}
+
+/// Where the course ends up when up and down change the depth.
+pub fn part_1(commands: &[Command]) -> i64 {
+    follow_course(commands).product()
+}
+
+/// Where the course ends up when up and down change the aim.
+pub fn part_2(commands: &[Command]) -> i64 {
+    follow_course_with_aim(commands).product()
+}
+
+/// Day 2 as a [`Puzzle`], for tools that drive every day the same way.
+pub struct Dive;
+
+impl Puzzle for Dive {
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Dive!";
+
+    type Input = Vec<Command>;
+    type ErrorKind = ErrorKind;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(data: &str) -> Result<Vec<Command>, ParseError> {
+        parse(data)
+    }
+
+    fn check(data: &str) -> Vec<ParseError> {
+        check(data)
+    }
+
+    fn part1(input: &Vec<Command>) -> i64 {
+        part_1(input)
+    }
+
+    fn part2(input: &Vec<Command>) -> i64 {
+        part_2(input)
+    }
+}
+
+/// Moves directly, with up and down changing the depth.
+pub fn follow_course(commands: &[Command]) -> Position {
+    commands.iter().fold(Position::default(), Position::moved)
+}
+
+/// Moves along the aim, with up and down changing the aim.
+pub fn follow_course_with_aim(commands: &[Command]) -> PositionWithAim {
+    commands
+        .iter()
+        .fold(PositionWithAim::default(), PositionWithAim::moved)
+}
+
+/// [`follow_course`] over commands read one at a time.
+pub fn follow_course_streamed<R: BufRead>(reader: R) -> Result<Position, StreamError> {
+    stream::until_error(read_commands(reader), |commands| {
+        commands.fold(Position::default(), |at, command| at.moved(&command))
+    })
+}
+
+/// [`follow_course_with_aim`] over commands read one at a time.
+pub fn follow_course_with_aim_streamed<R: BufRead>(
+    reader: R,
+) -> Result<PositionWithAim, StreamError> {
+    stream::until_error(read_commands(reader), |commands| {
+        commands.fold(PositionWithAim::default(), |at, command| at.moved(&command))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let course = parse(include_str!("test_course.txt")).unwrap();
+        assert_eq!(part_1(&course), 150);
+        assert_eq!(part_2(&course), 900);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse("forward 5\ndown").unwrap_err();
+        assert_eq!((err.kind, err.line), (ErrorKind::MissingAmount, 2));
+        let err = parse("forward 5\nsideways 3").unwrap_err();
+        assert_eq!(err.kind, ErrorKind::UnknownDirection);
+        assert_eq!(
+            (err.line, err.column, err.text.as_str()),
+            (2, 1, "sideways")
+        );
+        let err = parse("up x").unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::InvalidAmount(_)));
+        assert_eq!((err.line, err.column), (1, 4));
+    }
+
+    #[test]
+    fn test_check() {
+        let problems = check("forward 5\ndown\nup 3\nsideways 3\nup x");
+        let found: Vec<_> = problems.iter().map(|e| (e.line, e.column)).collect();
+        assert_eq!(found, vec![(2, 1), (4, 1), (5, 4)]);
+        assert_eq!(problems[1].kind, ErrorKind::UnknownDirection);
+    }
+
+    #[test]
+    fn test_streamed() {
+        let data = include_str!("test_course.txt");
+        let course = parse(data).unwrap();
+        let position = follow_course_streamed(data.as_bytes()).unwrap();
+        assert_eq!(position, follow_course(&course));
+        assert_eq!(position.product(), 150);
+        let position = follow_course_with_aim_streamed(data.as_bytes()).unwrap();
+        assert_eq!(position, follow_course_with_aim(&course));
+        assert_eq!(position.product(), 900);
+        match follow_course_streamed("forward 5\nsideways 3".as_bytes()) {
+            Err(StreamError::Parse(err)) => {
+                assert_eq!(err.kind, ErrorKind::UnknownDirection);
+                assert_eq!((err.line, err.column), (2, 1));
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,352 @@
+//! Day 3: Binary Diagnostic. Derives the power consumption and life support
+//! ratings from a report of equally wide binary numbers.
+
+use common::check;
+use common::{ParseError as SpanError, Puzzle};
+use thiserror::Error;
+use tracing::field;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ErrorKind {
+    #[error("input must contain at least one line")]
+    Empty,
+    #[error("input must only contain 1 or 0")]
+    InvalidBit,
+    #[error("expected {expected} bits, found {found}")]
+    RaggedLine { expected: usize, found: usize },
+    #[error("duplicate numbers leave no unique life support rating")]
+    NoUniqueRating,
+    #[error("numbers may have at most {max} bits, found {found}")]
+    TooWide { max: usize, found: usize },
+}
+
+pub type ParseError = SpanError<ErrorKind>;
+
+/// The widest numbers accepted, so that two ratings multiply without
+/// overflowing.
+pub const MAX_BITS: usize = 32;
+
+/// The rates and ratings of one diagnostic report.
+#[derive(Debug, Clone)]
+pub struct Diagnostics {
+    gamma: Vec<u8>,
+    epsilon: Vec<u8>,
+    oxygen: Vec<u8>,
+    co2: Vec<u8>,
+}
+
+/// The width of the numbers, which the first line sets.
+fn width(data: &str) -> Result<usize, ParseError> {
+    let (first, line_len) = match data.lines().next() {
+        Some(x) if !x.is_empty() => (x, x.len()),
+        _ => return Err(ParseError::whole_input(ErrorKind::Empty)),
+    };
+    // two 32 bit numbers still multiply without overflowing a u64
+    if line_len > MAX_BITS {
+        let kind = ErrorKind::TooWide {
+            max: MAX_BITS,
+            found: line_len,
+        };
+        return Err(ParseError::locate(kind, data, first));
+    }
+    Ok(line_len)
+}
+
+/// Checks that a line is `line_len` bits.
+fn check_line(line: &str, line_len: usize) -> Result<(), ParseError> {
+    if let Some((i, c)) = line.char_indices().find(|&(_, c)| c != '0' && c != '1') {
+        let bit = &line[i..i + c.len_utf8()];
+        return Err(ParseError::locate(ErrorKind::InvalidBit, line, bit));
+    }
+    if line.len() != line_len {
+        let kind = ErrorKind::RaggedLine {
+            expected: line_len,
+            found: line.len(),
+        };
+        return Err(ParseError::locate(kind, line, line));
+    }
+    Ok(())
+}
+
+/// Every line that isn't a number as wide as the first, or else what keeps
+/// the ratings from being found.
+pub fn check(data: &str) -> Vec<ParseError> {
+    let line_len = match width(data) {
+        Ok(line_len) => line_len,
+        Err(e) => return vec![e],
+    };
+    let problems = check::each_line(data, |line| check_line(line, line_len)).1;
+    if problems.is_empty() {
+        return Diagnostics::new(data).err().into_iter().collect();
+    }
+    problems
+}
+
+/// Which of the two life support ratings to find.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LifeSupportRating {
+    Oxygen,
+    CO2,
+}
+
+impl Diagnostics {
+    /// Built from the most common bit in each position.
+    pub fn gamma_rate(&self) -> u64 {
+        Diagnostics::to_number(&self.gamma)
+    }
+
+    /// Built from the least common bit in each position.
+    pub fn epsilon_rate(&self) -> u64 {
+        Diagnostics::to_number(&self.epsilon)
+    }
+
+    /// The number left after filtering the report by bit criteria.
+    pub fn rating(&self, rating: LifeSupportRating) -> u64 {
+        match rating {
+            LifeSupportRating::Oxygen => Diagnostics::to_number(&self.oxygen),
+            LifeSupportRating::CO2 => Diagnostics::to_number(&self.co2),
+        }
+    }
+
+    /// The gamma rate times the epsilon rate.
+    pub fn power_consumption(&self) -> u64 {
+        self.gamma_rate() * self.epsilon_rate()
+    }
+
+    /// The oxygen generator rating times the CO2 scrubber rating.
+    pub fn life_support_rating(&self) -> u64 {
+        self.rating(LifeSupportRating::Oxygen) * self.rating(LifeSupportRating::CO2)
+    }
+
+    fn to_number(bit_vec: &[u8]) -> u64 {
+        //assuming bits are in little_endian
+        bit_vec
+            .iter()
+            .rev()
+            .enumerate()
+            .fold(0, |acc, (place, &bit)| {
+                if bit > 1 {
+                    panic!("invalid bit")
+                } else {
+                    acc + (bit as u64).rotate_left(place as u32)
+                }
+            })
+    }
+
+    /// Reads one binary number per line; every line must be as wide as the
+    /// first.
+    pub fn new(data: &str) -> Result<Diagnostics, ParseError> {
+        let line_len = width(data)?;
+        for line in data.lines() {
+            check_line(line, line_len).map_err(|e| e.within(data, line))?;
+        }
+        let gamma = (0..line_len)
+            .map(|i| {
+                Diagnostics::most_common_bit(data.lines(), i).and_then(Diagnostics::char_to_u8)
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(ParseError::whole_input)?;
+        let epsilon = (0..line_len)
+            .map(|i| {
+                Diagnostics::least_common_bit(data.lines(), i).and_then(Diagnostics::char_to_u8)
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(ParseError::whole_input)?;
+        let oxygen = Diagnostics::get_life_rating(data, LifeSupportRating::Oxygen)
+            .map_err(ParseError::whole_input)?;
+        let co2 = Diagnostics::get_life_rating(data, LifeSupportRating::CO2)
+            .map_err(ParseError::whole_input)?;
+        Ok(Diagnostics {
+            gamma,
+            epsilon,
+            oxygen,
+            co2,
+        })
+    }
+
+    fn char_to_u8(c: char) -> Result<u8, ErrorKind> {
+        match c {
+            '1' => Ok(1),
+            '0' => Ok(0),
+            _ => Err(ErrorKind::InvalidBit),
+        }
+    }
+
+    fn most_common_bit<'a, I>(data: I, place: usize) -> Result<char, ErrorKind>
+    where
+        I: Iterator<Item = &'a str>,
+    {
+        let mut len = 0;
+        let mut acc = 0;
+        for line in data {
+            len += 1;
+            acc += match line.chars().nth(place) {
+                Some('1') => 1,
+                Some('0') => 0,
+                Some(_) => return Err(ErrorKind::InvalidBit),
+                // only reachable once every bit has been compared
+                None => return Err(ErrorKind::NoUniqueRating),
+            }
+        }
+        Ok(if (2 * acc).cmp(&len).is_ge() {
+            '1'
+        } else {
+            '0'
+        })
+    }
+
+    fn least_common_bit<'a, I>(data: I, place: usize) -> Result<char, ErrorKind>
+    where
+        I: Iterator<Item = &'a str>,
+    {
+        Diagnostics::most_common_bit(data, place).map(|o| match o {
+            '1' => '0',
+            '0' => '1',
+            _ => unreachable!(),
+        })
+    }
+
+    fn get_life_rating(
+        data: &str,
+        life_support_rating: LifeSupportRating,
+    ) -> Result<Vec<u8>, ErrorKind> {
+        let span = tracing::debug_span!(
+            "life_rating",
+            rating = ?life_support_rating,
+            rounds = field::Empty
+        );
+        let _span = span.enter();
+        let mut numbers = data.lines().map(String::from).collect::<Vec<_>>();
+        let mut place: usize = 0;
+        while numbers.len() > 1 {
+            let _round = tracing::trace_span!("round", place, left = numbers.len()).entered();
+            let matcher = match life_support_rating {
+                LifeSupportRating::Oxygen => {
+                    Diagnostics::most_common_bit(numbers.iter().map(|o| o.as_str()), place)?
+                }
+                LifeSupportRating::CO2 => {
+                    Diagnostics::least_common_bit(numbers.iter().map(|o| o.as_str()), place)?
+                }
+            };
+            // when every number shares this bit there is nothing to choose
+            // between, rather than nothing left to keep
+            if numbers
+                .iter()
+                .any(|o| o.chars().nth(place) == Some(matcher))
+            {
+                numbers.retain(|o| o.chars().nth(place) == Some(matcher));
+            }
+
+            place += 1;
+        }
+        span.record("rounds", place);
+        match numbers.pop() {
+            Some(s) => s
+                .chars()
+                .map(Diagnostics::char_to_u8)
+                .collect::<Result<Vec<_>, _>>(),
+            None => Err(ErrorKind::Empty),
+        }
+    }
+}
+
+/// Reads one binary number per line.
+pub fn parse(data: &str) -> Result<Diagnostics, ParseError> {
+    Diagnostics::new(data)
+}
+
+/// The power consumption of the submarine.
+pub fn part_1(diagnostics: &Diagnostics) -> u64 {
+    diagnostics.power_consumption()
+}
+
+/// The life support rating of the submarine.
+pub fn part_2(diagnostics: &Diagnostics) -> u64 {
+    diagnostics.life_support_rating()
+}
+
+/// Day 3 as a [`Puzzle`], for tools that drive every day the same way.
+pub struct BinaryDiagnostic;
+
+impl Puzzle for BinaryDiagnostic {
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Binary Diagnostic";
+
+    type Input = Diagnostics;
+    type ErrorKind = ErrorKind;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(data: &str) -> Result<Diagnostics, ParseError> {
+        parse(data)
+    }
+
+    fn check(data: &str) -> Vec<ParseError> {
+        check(data)
+    }
+
+    fn part1(input: &Diagnostics) -> u64 {
+        part_1(input)
+    }
+
+    fn part2(input: &Diagnostics) -> u64 {
+        part_2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part() {
+        let expected_gam = [1, 0, 1, 1, 0u8];
+        let expected_eps = [0, 1, 0, 0, 1u8];
+        let diagnostics = Diagnostics::new(include_str!("test.txt")).unwrap();
+        for (res, expected) in diagnostics.gamma.iter().zip(expected_gam.iter()) {
+            assert_eq!(res, expected);
+        }
+        for (res, expected) in diagnostics.epsilon.iter().zip(expected_eps.iter()) {
+            assert_eq!(res, expected);
+        }
+
+        assert_eq!(diagnostics.gamma_rate(), 22);
+        assert_eq!(diagnostics.epsilon_rate(), 9);
+        assert_eq!(diagnostics.rating(LifeSupportRating::Oxygen), 23);
+        assert_eq!(diagnostics.rating(LifeSupportRating::CO2), 10);
+        assert_eq!(diagnostics.power_consumption(), 198);
+        assert_eq!(diagnostics.life_support_rating(), 230);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Diagnostics::new("").unwrap_err();
+        assert_eq!(err.kind, ErrorKind::Empty);
+        let err = Diagnostics::new("00100\n11120").unwrap_err();
+        assert_eq!(err.kind, ErrorKind::InvalidBit);
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 4, "2"));
+        let err = Diagnostics::new("00100\n1110").unwrap_err();
+        assert_eq!(
+            err.kind,
+            ErrorKind::RaggedLine {
+                expected: 5,
+                found: 4
+            }
+        );
+        let err = Diagnostics::new("01\n01").unwrap_err();
+        assert_eq!(err.kind, ErrorKind::NoUniqueRating);
+        let err = Diagnostics::new(&"1".repeat(33)).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::TooWide { max: 32, found: 33 });
+    }
+
+    #[test]
+    fn test_check() {
+        let problems = check("00100\n1110\n10110\n10x11\n");
+        let found: Vec<_> = problems.iter().map(|e| (e.line, e.column)).collect();
+        assert_eq!(found, vec![(2, 1), (4, 3)]);
+        let problems = check("01\n01");
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].kind, ErrorKind::NoUniqueRating);
+        assert!(check(include_str!("test.txt")).is_empty());
+    }
+}
@@ -0,0 +1,482 @@
+//! Day 4: Giant Squid. Plays bingo on every board at once to find the board
+//! that wins first and the one that wins last.
+
+use common::{ParseError as SpanError, Puzzle};
+use rayon::prelude::*;
+use std::iter::Peekable;
+use std::num::ParseIntError;
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ErrorKind {
+    #[error("missing the line of called numbers")]
+    MissingNumbers,
+    #[error("invalid number: {0}")]
+    InvalidNumber(ParseIntError),
+    #[error("expected a blank line")]
+    MissingSeparator,
+    #[error("expected {expected} numbers in the row, found {found}")]
+    RaggedRow { expected: usize, found: usize },
+    #[error("board ended before all of its rows")]
+    ShortBoard,
+}
+
+pub type ParseError = SpanError<ErrorKind>;
+
+/// Marks a called number.
+pub trait CallNumber {
+    fn call_number(&mut self, _number: i32) {}
+}
+
+impl CallNumber for Board {
+    fn call_number(&mut self, number: i32) {
+        for square in self.squares.iter_mut() {
+            square.call_number(number)
+        }
+        if !self.is_winner {
+            self.last_move += 1;
+        }
+        // see if board has won
+        for row in self.squares.chunks(self.row_len) {
+            if row.iter().all(|square| square.is_checked) {
+                self.is_winner = true;
+            }
+        }
+        // no columns
+        for col in 0..self.row_len {
+            if self.squares[col..]
+                .iter()
+                .step_by(self.row_len)
+                .all(|square| square.is_checked)
+            {
+                self.is_winner = true;
+            }
+        }
+
+        if self.is_winner && self.score.is_none() {
+            let unmarked = self
+                .squares
+                .iter()
+                .filter_map(|square| match square.is_checked {
+                    false => Some(i64::from(square.number)),
+                    true => None,
+                })
+                .sum::<i64>();
+            self.score = Some(unmarked.saturating_mul(i64::from(number)))
+        }
+    }
+}
+
+impl CallNumber for BingoSquare {
+    fn call_number(&mut self, number: i32) {
+        if number == self.number {
+            self.is_checked = true;
+        }
+    }
+}
+
+/// One number on a board and whether it has been called.
+#[derive(Debug, Clone)]
+pub struct BingoSquare {
+    pub number: i32,
+    pub is_checked: bool,
+}
+
+impl BingoSquare {
+    pub fn new(val: &str) -> Result<Self, ParseError> {
+        let number = val
+            .parse()
+            .map_err(|e| ParseError::locate(ErrorKind::InvalidNumber(e), val, val))?;
+        Ok(BingoSquare {
+            number,
+            is_checked: false,
+        })
+    }
+}
+
+/// A five by five board, which wins once a whole row or column is called.
+#[derive(Debug, Clone)]
+pub struct Board {
+    pub squares: Vec<BingoSquare>,
+    row_len: usize,
+    col_len: usize,
+    is_winner: bool,
+    score: Option<i64>,
+    last_move: usize,
+}
+impl Board {
+    /// The squares, one row at a time.
+    pub fn rows(&self) -> std::slice::Chunks<'_, BingoSquare> {
+        self.squares.chunks(self.row_len)
+    }
+
+    /// Whether a whole row or column has been called.
+    pub fn is_winner(&self) -> bool {
+        self.is_winner
+    }
+
+    /// The sum of the uncalled numbers times the number that won, once the
+    /// board has won.
+    pub fn score(&self) -> Option<i64> {
+        self.score
+    }
+
+    fn add_row(&mut self, source: &str, data: &str) -> Result<(), ParseError> {
+        let new_squares: Vec<BingoSquare> = data
+            .split_whitespace()
+            .map(|v| BingoSquare::new(v).map_err(|e| ParseError::locate(e.kind, source, v)))
+            .collect::<Result<_, _>>()?;
+        if new_squares.len() != self.row_len {
+            let kind = ErrorKind::RaggedRow {
+                expected: self.row_len,
+                found: new_squares.len(),
+            };
+            return Err(ParseError::locate(kind, source, data));
+        }
+        self.squares.extend_from_slice(&new_squares[..]);
+        Ok(())
+    }
+    /// Reads the next five rows, noting any problems with them. Only a board
+    /// without problems is returned.
+    fn new<'a, I>(
+        source: &str,
+        data: &mut Peekable<I>,
+        problems: &mut Vec<ParseError>,
+    ) -> Option<Board>
+    where
+        I: Iterator<Item = &'a str>,
+    {
+        let mut board = Board {
+            squares: Vec::new(),
+            col_len: 5,
+            row_len: 5,
+            is_winner: false,
+            score: None,
+            last_move: 0,
+        };
+        let mut complete = true;
+        let mut last_row = "";
+        for _ in 0..board.col_len {
+            match data.next_if(|v| !v.is_empty()) {
+                Some(v) => {
+                    if let Err(e) = board.add_row(source, v) {
+                        problems.push(e);
+                        complete = false;
+                    }
+                    last_row = v;
+                }
+                // stopped by a blank line or the end of the input
+                None => {
+                    let at = data.peek().copied().unwrap_or(last_row);
+                    problems.push(ParseError::locate(ErrorKind::ShortBoard, source, at));
+                    return None;
+                }
+            }
+        }
+        complete.then_some(board)
+    }
+}
+
+/// The numbers to call, in order, and the boards to play.
+#[derive(Debug, Clone)]
+pub struct Bingo {
+    pub numbers: Vec<i32>,
+    pub boards: Vec<Board>,
+}
+
+/// Reads the comma separated numbers to call, then the boards, each after a
+/// blank line.
+pub fn parse(data: &str) -> Result<Bingo, ParseError> {
+    let (numbers, boards) = parse_data(data)?;
+    Ok(Bingo { numbers, boards })
+}
+
+/// The score of the first board to win.
+pub fn part_1(bingo: &Bingo) -> Option<i64> {
+    find_winner_par(&mut bingo.boards.clone(), &bingo.numbers)
+}
+
+/// The score of the last board to win.
+pub fn part_2(bingo: &Bingo) -> Option<i64> {
+    find_loser_par(&mut bingo.boards.clone(), &bingo.numbers)
+}
+
+/// Day 4 as a [`Puzzle`], for tools that drive every day the same way.
+pub struct GiantSquid;
+
+impl Puzzle for GiantSquid {
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Giant Squid";
+
+    type Input = Bingo;
+    type ErrorKind = ErrorKind;
+    type Answer1 = Option<i64>;
+    type Answer2 = Option<i64>;
+
+    fn parse(data: &str) -> Result<Bingo, ParseError> {
+        parse(data)
+    }
+
+    fn check(data: &str) -> Vec<ParseError> {
+        check(data)
+    }
+
+    fn part1(input: &Bingo) -> Option<i64> {
+        part_1(input)
+    }
+
+    fn part2(input: &Bingo) -> Option<i64> {
+        part_2(input)
+    }
+}
+
+fn parse_data(data: &str) -> Result<(Vec<i32>, Vec<Board>), ParseError> {
+    let mut problems = Vec::new();
+    let read = read(data, &mut problems);
+    match problems.into_iter().next() {
+        Some(e) => Err(e),
+        None => Ok(read),
+    }
+}
+
+/// Reads the numbers and boards, noting every problem along the way rather
+/// than stopping at the first.
+fn read(data: &str, problems: &mut Vec<ParseError>) -> (Vec<i32>, Vec<Board>) {
+    let mut boards = Vec::new();
+    let mut lines = data.lines().peekable();
+    let numbers = match lines.next() {
+        Some(line) if !line.is_empty() => line
+            .split(',')
+            .filter_map(|s| {
+                s.parse::<i32>()
+                    .map_err(|e| {
+                        problems.push(ParseError::locate(ErrorKind::InvalidNumber(e), data, s))
+                    })
+                    .ok()
+            })
+            .collect(),
+        _ => {
+            problems.push(ParseError::whole_input(ErrorKind::MissingNumbers));
+            return (Vec::new(), boards);
+        }
+    };
+    loop {
+        // boards are separated by (at least) one blank line
+        match lines.next() {
+            None => break,
+            Some("") => {}
+            Some(line) => {
+                problems.push(ParseError::locate(ErrorKind::MissingSeparator, data, line));
+                // the rest of the overlong board
+                while lines.next_if(|line| !line.is_empty()).is_some() {}
+                continue;
+            }
+        }
+        while lines.next_if_eq(&"").is_some() {}
+        if lines.peek().is_none() {
+            break;
+        }
+        boards.extend(Board::new(data, &mut lines, problems));
+    }
+    (numbers, boards)
+}
+
+/// Every problem with the numbers and boards.
+pub fn check(data: &str) -> Vec<ParseError> {
+    let mut problems = Vec::new();
+    read(data, &mut problems);
+    problems
+}
+
+/// Calls numbers until a board wins and returns its score. When several
+/// boards win on the same number the best score counts.
+pub fn find_winner(boards: &mut [Board], numbers: &[i32]) -> Option<i64> {
+    let span = tracing::debug_span!("find_winner", boards = boards.len(), called = 0);
+    let _span = span.enter();
+    for (called, num) in numbers.iter().enumerate() {
+        span.record("called", called + 1);
+        for board in boards.iter_mut() {
+            board.call_number(*num)
+        }
+        if let Some(top_score) = boards.iter().filter_map(|b| b.score).max() {
+            return Some(top_score);
+        }
+    }
+    // no board won bingo
+    None
+}
+
+/// Calls every number and returns the score of the board that won last.
+/// Boards that never win are ignored.
+pub fn find_loser(boards: &mut [Board], numbers: &[i32]) -> Option<i64> {
+    let _span = tracing::debug_span!("find_loser", boards = boards.len()).entered();
+    for num in numbers {
+        for board in boards.iter_mut() {
+            board.call_number(*num)
+        }
+    }
+    // find the boards which won last; boards that never win don't count
+    let final_move = boards
+        .iter()
+        .filter(|b| b.is_winner)
+        .map(|b| b.last_move)
+        .max()?;
+    boards
+        .iter()
+        .filter(|b| b.is_winner && b.last_move == final_move)
+        .filter_map(|b| b.score)
+        .min()
+}
+
+/// [`find_winner`] with the boards marked in parallel. Copies of the boards
+/// find the first winning number, each on its own thread, and then the
+/// boards are marked with the numbers up to it, just as [`find_winner`]
+/// leaves them.
+pub fn find_winner_par(boards: &mut [Board], numbers: &[i32]) -> Option<i64> {
+    let _span = tracing::debug_span!("find_winner", boards = boards.len()).entered();
+    let first_win = boards
+        .par_iter()
+        .filter_map(|board| {
+            let mut board = board.clone();
+            numbers.iter().position(|&num| {
+                board.call_number(num);
+                board.is_winner
+            })
+        })
+        .min();
+    let called = first_win.map_or(numbers.len(), |turn| turn + 1);
+    boards.par_iter_mut().for_each(|board| {
+        for &num in &numbers[..called] {
+            board.call_number(num)
+        }
+    });
+    first_win?;
+    boards.iter().filter_map(|b| b.score).max()
+}
+
+/// [`find_loser`] with the boards marked in parallel. Each board plays the
+/// whole draw on its own thread, since no board needs to know about the
+/// others until the end.
+pub fn find_loser_par(boards: &mut [Board], numbers: &[i32]) -> Option<i64> {
+    let _span = tracing::debug_span!("find_loser", boards = boards.len()).entered();
+    boards.par_iter_mut().for_each(|board| {
+        for &num in numbers {
+            board.call_number(num)
+        }
+    });
+    let final_move = boards
+        .iter()
+        .filter(|b| b.is_winner)
+        .map(|b| b.last_move)
+        .max()?;
+    boards
+        .iter()
+        .filter(|b| b.is_winner && b.last_move == final_move)
+        .filter_map(|b| b.score)
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part() {
+        let test_data = include_str!("test.txt");
+        let (numbers, mut boards) = parse_data(test_data).unwrap();
+        assert_eq!(boards.len(), 3);
+        assert_eq!(Some(4512), find_winner(&mut boards, &numbers));
+        assert_eq!(Some(1924), find_loser(&mut boards, &numbers));
+        assert!(boards.iter().all(Board::is_winner));
+        assert_eq!(boards[1].score(), Some(1924));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse_data("").unwrap_err();
+        assert_eq!(err.kind, ErrorKind::MissingNumbers);
+        let err = parse_data("1,2,x").unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::InvalidNumber(_)));
+        assert_eq!((err.line, err.column), (1, 5));
+        let err = parse_data("1,2\n1 2 3 4 5").unwrap_err();
+        assert_eq!((err.kind, err.line), (ErrorKind::MissingSeparator, 2));
+        let err = parse_data("1,2\n\n1 2 3 4 5\n1 2 3 4").unwrap_err();
+        assert_eq!(
+            (err.kind, err.line),
+            (
+                ErrorKind::RaggedRow {
+                    expected: 5,
+                    found: 4
+                },
+                4
+            )
+        );
+        let err = parse_data("1,2\n\n1 2 3 4 5\n1 2 3 4 5").unwrap_err();
+        assert_eq!((err.kind, err.line), (ErrorKind::ShortBoard, 4));
+    }
+
+    #[test]
+    fn test_check() {
+        let data = include_str!("test.txt");
+        assert!(check(data).is_empty());
+        // a bad number, an overlong board that runs into the next, and a
+        // short last board
+        let data = "7,x,9\n\n\
+            1 2 3 4 5\n1 2 3 4 5\n1 2 3 4 5\n1 2 3 4 5\n1 2 3 4 5\n1 2 3 4 5\n\n\
+            1 2 3 4 5\n1 2 3 y 5\n1 2 3 4 5\n1 2 3 4 5\n1 2 3 4 5\n\n\
+            1 2 3 4 5\n1 2 3 4\n";
+        let found: Vec<_> = check(data).iter().map(|e| e.line).collect();
+        assert_eq!(found, vec![1, 8, 11, 17, 17]);
+    }
+
+    /// What a board has been through: its marks, whether it won, and how.
+    fn marks(boards: &[Board]) -> Vec<(Vec<bool>, bool, usize, Option<i64>)> {
+        boards
+            .iter()
+            .map(|b| {
+                let checked = b.squares.iter().map(|s| s.is_checked).collect();
+                (checked, b.is_winner, b.last_move, b.score)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_par() {
+        // plenty of boards, drawn from few enough numbers that some tie
+        let params = generate::day_04::Params {
+            boards: 100,
+            numbers: 40,
+        };
+        let data = generate::day_04::generate(&mut generate::rng(4), &params);
+        let bingo = parse(&data).unwrap();
+        let numbers = &bingo.numbers;
+        let (mut serial, mut par) = (bingo.boards.clone(), bingo.boards.clone());
+        assert_eq!(
+            find_winner_par(&mut par, numbers),
+            find_winner(&mut serial, numbers)
+        );
+        assert_eq!(marks(&par), marks(&serial));
+        // playing on from the first win ends up the same way too
+        assert_eq!(
+            find_loser_par(&mut par, numbers),
+            find_loser(&mut serial, numbers)
+        );
+        assert_eq!(marks(&par), marks(&serial));
+        assert!(find_loser(&mut bingo.boards.clone(), numbers).is_some());
+        let (numbers, mut boards) = parse_data(include_str!("test.txt")).unwrap();
+        assert_eq!(find_winner_par(&mut boards, &numbers), Some(4512));
+        let (_, mut never) = parse_data(include_str!("test.txt")).unwrap();
+        assert_eq!(find_winner_par(&mut never, &numbers[..5]), None);
+        let (_, mut serial) = parse_data(include_str!("test.txt")).unwrap();
+        find_winner(&mut serial, &numbers[..5]);
+        assert_eq!(marks(&never), marks(&serial));
+    }
+
+    #[test]
+    fn test_no_winner() {
+        let bingo = parse("10,11,12,13,20\n\n10 11 12 13 14\n15 16 17 18 19\n20 21 22 23 24\n25 26 27 28 29\n30 31 32 33 34").unwrap();
+        assert_eq!(part_1(&bingo), None);
+        assert_eq!(part_2(&bingo), None);
+        assert_eq!(part_1(&parse("1,2").unwrap()), None);
+    }
+}
@@ -0,0 +1,26 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use generate::day_05::Params;
+
+const SEED: u64 = 5;
+
+fn ocean_floor(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day-5/OceanFloor");
+    for count in [100, 500, 2_500, 10_000] {
+        let lines = day_5::parse(&generate::day_05::generate(
+            &mut generate::rng(SEED),
+            &Params::sized(count),
+        ))
+        .unwrap();
+        group.throughput(Throughput::Elements(count as u64));
+        group.bench_with_input(BenchmarkId::new("new", count), &lines, |b, lines| {
+            b.iter(|| day_5::OceanFloor::new(lines, true))
+        });
+        group.bench_with_input(BenchmarkId::new("new_par", count), &lines, |b, lines| {
+            b.iter(|| day_5::OceanFloor::new_par(lines, true))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, ocean_floor);
+criterion_main!(benches);
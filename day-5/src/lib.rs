@@ -0,0 +1,375 @@
+//! Day 5: Hydrothermal Venture. Draws lines of vents onto the ocean floor and
+//! counts the points where at least two of them overlap.
+
+use common::check;
+use common::{ParseError as SpanError, Puzzle};
+use rayon::prelude::*;
+use std::num::ParseIntError;
+use thiserror::Error;
+use tracing::field;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ErrorKind {
+    #[error("expected a line like `x0,y0 -> x1,y1`")]
+    MissingArrow,
+    #[error("expected a point like `x,y`")]
+    MissingComma,
+    #[error("invalid coordinate: {0}")]
+    InvalidCoordinate(ParseIntError),
+    #[error("lines must be horizontal, vertical or at 45 degrees")]
+    UnsupportedAngle,
+    #[error("coordinates may be at most {MAX_COORDINATE}")]
+    CoordinateTooLarge,
+}
+
+pub type ParseError = SpanError<ErrorKind>;
+
+/// The ocean floor is stored densely, so coordinates are capped to keep it
+/// to a sensible size.
+pub const MAX_COORDINATE: usize = 4095;
+
+/// How many vent lines cover each point of the floor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OceanFloor {
+    vals: Vec<Vec<u32>>,
+}
+impl OceanFloor {
+    /// Draws every horizontal and vertical line, and the diagonal ones only
+    /// when `include_diag` is set.
+    pub fn new(lines: &[Line], include_diag: bool) -> Self {
+        let span = tracing::debug_span!("draw", lines = lines.len(), points = field::Empty);
+        let _span = span.enter();
+        let mut points = 0;
+        let (row_len, col_len) = Self::bounds(lines);
+        let mut floor = Self::empty(row_len, col_len);
+        for line in lines
+            .iter()
+            .filter(|l| if include_diag { true } else { !l.is_diagonal() })
+        {
+            points += floor.draw(line);
+        }
+        span.record("points", points);
+        floor
+    }
+
+    /// [`OceanFloor::new`] with the lines drawn onto a shard of the floor per
+    /// thread, and the shards added up at the end. Each shard takes at least
+    /// an even share of the lines, so there are no more shards than threads.
+    pub fn new_par(lines: &[Line], include_diag: bool) -> Self {
+        let _span = tracing::debug_span!("draw_par", lines = lines.len()).entered();
+        let (row_len, col_len) = Self::bounds(lines);
+        let share = lines.len().div_ceil(rayon::current_num_threads()).max(1);
+        lines
+            .par_iter()
+            .with_min_len(share)
+            .filter(|l| include_diag || !l.is_diagonal())
+            .fold(
+                || Self::empty(row_len, col_len),
+                |mut shard, line| {
+                    shard.draw(line);
+                    shard
+                },
+            )
+            .reduce_with(|mut total, shard| {
+                total.add(&shard);
+                total
+            })
+            .unwrap_or_else(|| Self::empty(row_len, col_len))
+    }
+
+    fn empty(row_len: usize, col_len: usize) -> Self {
+        OceanFloor {
+            vals: vec![vec![0; row_len]; col_len],
+        }
+    }
+
+    /// Draws one line, returning how many points it covers.
+    fn draw(&mut self, line: &Line) -> usize {
+        let mut points = 0;
+        for point in line.to_points() {
+            self.vals[point.y][point.x] += 1;
+            points += 1;
+        }
+        points
+    }
+
+    /// Adds the counts on another floor of the same size to these.
+    fn add(&mut self, other: &OceanFloor) {
+        for (row, other) in self.vals.iter_mut().zip(other.vals.iter()) {
+            for (val, other) in row.iter_mut().zip(other) {
+                *val += other;
+            }
+        }
+    }
+
+    /// Columns and rows needed to reach the furthest line end.
+    fn bounds(lines: &[Line]) -> (usize, usize) {
+        let (xmax, ymax) = lines.iter().fold((0, 0), |acc, line| {
+            (
+                acc.0.max(line.start.x).max(line.end.x),
+                acc.1.max(line.start.y).max(line.end.y),
+            )
+        });
+        (xmax + 1, ymax + 1)
+    }
+
+    /// The number of lines covering a point; zero beyond the floor.
+    pub fn vents_at(&self, x: usize, y: usize) -> u32 {
+        self.vals
+            .get(y)
+            .and_then(|row| row.get(x))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Columns and rows, just enough to reach the furthest line end.
+    pub fn size(&self) -> (usize, usize) {
+        (self.vals.first().map_or(0, Vec::len), self.vals.len())
+    }
+
+    /// Points covered by at least two lines.
+    pub fn find_intersections(&self) -> u32 {
+        self.vals
+            .iter()
+            .map(|row| row.iter().filter(|&&v| v > 1).count() as u32)
+            .sum()
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Point {
+    /// Reads a point like `x,y`.
+    pub fn new(data: &str) -> Result<Self, ParseError> {
+        let (x, y) = data
+            .split_once(',')
+            .ok_or_else(|| ParseError::locate(ErrorKind::MissingComma, data, data))?;
+        let coordinate = |v: &str| match v.parse::<usize>() {
+            Ok(c) if c > MAX_COORDINATE => {
+                Err(ParseError::locate(ErrorKind::CoordinateTooLarge, data, v))
+            }
+            Ok(c) => Ok(c),
+            Err(e) => Err(ParseError::locate(ErrorKind::InvalidCoordinate(e), data, v)),
+        };
+        Ok(Point {
+            x: coordinate(x)?,
+            y: coordinate(y)?,
+        })
+    }
+}
+
+/// A line of vents between two points, inclusive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Line {
+    pub start: Point,
+    pub end: Point,
+}
+
+impl Line {
+    /// Reads a line like `x0,y0 -> x1,y1`.
+    pub fn new(data: &str) -> Result<Line, ParseError> {
+        let (start, end) = data
+            .split_once("->")
+            .ok_or_else(|| ParseError::locate(ErrorKind::MissingArrow, data, data))?;
+        let point = |o: &str| {
+            let o = o.trim();
+            Point::new(o).map_err(|e| e.within(data, o))
+        };
+        let line = Line {
+            start: point(start)?,
+            end: point(end)?,
+        };
+        let (dx, dy) = (
+            line.start.x.abs_diff(line.end.x),
+            line.start.y.abs_diff(line.end.y),
+        );
+        if dx != 0 && dy != 0 && dx != dy {
+            return Err(ParseError::locate(ErrorKind::UnsupportedAngle, data, data));
+        }
+        Ok(line)
+    }
+    pub fn is_diagonal(&self) -> bool {
+        self.start.x != self.end.x && self.start.y != self.end.y
+    }
+
+    /// Every point on the line, from start to end.
+    pub fn to_points(&self) -> Vec<Point> {
+        let mut x_range = vec![self.start.x];
+        let mut y_range = vec![self.start.y];
+        let mut place = 0;
+        while x_range[place] != self.end.x {
+            if x_range[place] < self.end.x {
+                x_range.push(x_range[place] + 1);
+            } else {
+                x_range.push(x_range[place] - 1);
+            }
+            place += 1
+        }
+        let mut place = 0;
+        while y_range[place] != self.end.y {
+            if y_range[place] < self.end.y {
+                y_range.push(y_range[place] + 1);
+            } else {
+                y_range.push(y_range[place] - 1);
+            }
+            place += 1
+        }
+
+        if x_range.len() == 1 {
+            std::iter::repeat(x_range[0])
+                .zip(y_range)
+                .map(|(x, y)| Point { x, y })
+                .collect()
+        } else if y_range.len() == 1 {
+            x_range
+                .into_iter()
+                .zip(std::iter::repeat(y_range[0]))
+                .map(|(x, y)| Point { x, y })
+                .collect()
+        } else {
+            x_range
+                .into_iter()
+                .zip(y_range)
+                .map(|(x, y)| Point { x, y })
+                .collect()
+        }
+    }
+}
+
+/// Reads one line of vents per line, such as `0,9 -> 5,9`.
+pub fn parse(data: &str) -> Result<Vec<Line>, ParseError> {
+    data.lines()
+        .map(|line| Line::new(line).map_err(|e| e.within(data, line)))
+        .collect()
+}
+
+/// Every line that isn't a line of vents.
+pub fn check(data: &str) -> Vec<ParseError> {
+    check::each_line(data, Line::new).1
+}
+
+/// Overlapping points, counting horizontal and vertical lines only.
+pub fn part_1(lines: &[Line]) -> u32 {
+    OceanFloor::new_par(lines, false).find_intersections()
+}
+
+/// Overlapping points, counting every line.
+pub fn part_2(lines: &[Line]) -> u32 {
+    OceanFloor::new_par(lines, true).find_intersections()
+}
+
+/// Day 5 as a [`Puzzle`], for tools that drive every day the same way.
+pub struct HydrothermalVenture;
+
+impl Puzzle for HydrothermalVenture {
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "Hydrothermal Venture";
+
+    type Input = Vec<Line>;
+    type ErrorKind = ErrorKind;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(data: &str) -> Result<Vec<Line>, ParseError> {
+        parse(data)
+    }
+
+    fn check(data: &str) -> Vec<ParseError> {
+        check(data)
+    }
+
+    fn part1(input: &Vec<Line>) -> u32 {
+        part_1(input)
+    }
+
+    fn part2(input: &Vec<Line>) -> u32 {
+        part_2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let test_data = include_str!("test.txt");
+        let lines = parse(test_data).unwrap();
+        let ocean_floor = OceanFloor::new(&lines, false);
+        assert_eq!(5, ocean_floor.find_intersections());
+        assert_eq!(ocean_floor.size(), (10, 10));
+        assert_eq!(
+            (ocean_floor.vents_at(0, 9), ocean_floor.vents_at(99, 0)),
+            (2, 0)
+        );
+        println!("{:?}", ocean_floor.vals);
+        assert_eq!(lines.len(), 10);
+        assert_eq!(
+            lines[0],
+            Line {
+                start: Point { x: 0, y: 9 },
+                end: Point { x: 5, y: 9 }
+            }
+        );
+        let ocean_floor = OceanFloor::new(&lines, true);
+        assert_eq!(12, ocean_floor.find_intersections());
+    }
+
+    #[test]
+    fn test_new_par() {
+        let lines = parse(include_str!("test.txt")).unwrap();
+        // crossing lines in every direction, longer than the example's
+        let mut many = lines.clone();
+        for i in 0..200 {
+            let (a, b) = ((i * 37) % 300, (i * 91) % 300);
+            let line = match i % 3 {
+                0 => format!("{},{} -> {},{}", a, b, a, 299 - b),
+                1 => format!("{},{} -> {},{}", a, b, 299 - a, b),
+                _ => format!("{},{} -> {},{}", a, a, b, b),
+            };
+            many.push(Line::new(&line).unwrap());
+        }
+        for lines in [&lines[..], &many[..], &[]] {
+            for include_diag in [false, true] {
+                assert_eq!(
+                    OceanFloor::new_par(lines, include_diag),
+                    OceanFloor::new(lines, include_diag)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse("0,9 -> 5,9\n8,0 - 0,8").unwrap_err();
+        assert_eq!((err.kind, err.line), (ErrorKind::MissingArrow, 2));
+        let err = parse("0,9 -> 5,9\n8,0 -> 0;8").unwrap_err();
+        assert_eq!(
+            (err.kind, err.line, err.column),
+            (ErrorKind::MissingComma, 2, 8)
+        );
+        let err = parse("0,9 -> 5,x").unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::InvalidCoordinate(_)));
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 10, "x"));
+        let err = parse("0,0 -> 2,1").unwrap_err();
+        assert_eq!(err.kind, ErrorKind::UnsupportedAngle);
+        let err = parse("0,9 -> 4096,9").unwrap_err();
+        assert_eq!((err.kind, err.column), (ErrorKind::CoordinateTooLarge, 8));
+    }
+
+    #[test]
+    fn test_check() {
+        let problems = check("0,9 -> 5,9\n8,0 - 0,8\n9,4 -> 3,4\n0,0 -> 2,1\n0,9 -> 5,x");
+        let found: Vec<_> = problems.iter().map(|e| (e.line, e.column)).collect();
+        assert_eq!(found, vec![(2, 1), (4, 1), (5, 10)]);
+    }
+
+    #[test]
+    fn test_single_point_line() {
+        let lines = parse("3,4 -> 3,4").unwrap();
+        assert_eq!(lines[0].to_points(), [Point { x: 3, y: 4 }]);
+    }
+}
@@ -0,0 +1,226 @@
+//! Day 6: Lanternfish. Counts a school of lanternfish as it grows, tracking
+//! how many fish share each timer rather than every fish.
+
+use common::{ParseError as SpanError, Puzzle};
+use std::num::ParseIntError;
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ErrorKind {
+    #[error("invalid age: {0}")]
+    InvalidAge(ParseIntError),
+    #[error("ages must be between 0 and 8")]
+    AgeOutOfRange,
+}
+
+pub type ParseError = SpanError<ErrorKind>;
+
+/// The number of fish with each timer value, from 0 to 8.
+#[derive(Debug, Clone)]
+pub struct Ocean {
+    fish: [u64; 9],
+}
+
+impl Ocean {
+    /// Reads the comma separated timers of the starting fish.
+    pub fn build(data: &str) -> Result<Self, ParseError> {
+        let mut fish = [0; 9];
+        for elm in data.trim().split(',') {
+            fish[age(data, elm)?] += 1;
+        }
+        Ok(Ocean { fish })
+    }
+
+    /// Advances one day: every fish at 0 resets to 6 and spawns a fish at 8.
+    pub fn step_time(&mut self) {
+        self.fish.rotate_left(1);
+        self.fish[6] += self.fish[8];
+    }
+
+    /// [`Ocean::step_time`], unless the total would outgrow a `u64`, in
+    /// which case the ocean is left as it was.
+    pub fn checked_step_time(&mut self) -> Option<()> {
+        // each fish at 0 adds one more
+        self.checked_total()?.checked_add(self.fish[0])?;
+        self.step_time();
+        Some(())
+    }
+
+    /// The number of fish whose timer is `age`.
+    pub fn count(&self, age: usize) -> u64 {
+        self.fish.get(age).copied().unwrap_or(0)
+    }
+
+    /// The number of fish in the ocean.
+    pub fn total(&self) -> u64 {
+        self.fish.iter().sum()
+    }
+
+    /// [`Ocean::total`], or `None` if it doesn't fit in a `u64`.
+    pub fn checked_total(&self) -> Option<u64> {
+        self.fish
+            .iter()
+            .try_fold(0u64, |total, &n| total.checked_add(n))
+    }
+}
+/// Reads one timer, which must be from 0 to 8.
+fn age(data: &str, elm: &str) -> Result<usize, ParseError> {
+    match elm.parse::<usize>() {
+        Ok(age) if age <= 8 => Ok(age),
+        Ok(_) => Err(ParseError::locate(ErrorKind::AgeOutOfRange, data, elm)),
+        Err(e) => Err(ParseError::locate(ErrorKind::InvalidAge(e), data, elm)),
+    }
+}
+
+/// Every timer that isn't a number from 0 to 8.
+pub fn check(data: &str) -> Vec<ParseError> {
+    data.trim()
+        .split(',')
+        .filter_map(|elm| age(data, elm).err())
+        .collect()
+}
+
+/// Reads the comma separated timers of the starting fish.
+pub fn parse(data: &str) -> Result<Ocean, ParseError> {
+    Ocean::build(data)
+}
+
+/// The number of fish after 80 days.
+pub fn part_1(ocean: &Ocean) -> u64 {
+    fish_after(ocean, 80)
+}
+
+/// The number of fish after 256 days.
+pub fn part_2(ocean: &Ocean) -> u64 {
+    fish_after(ocean, 256)
+}
+
+/// Day 6 as a [`Puzzle`], for tools that drive every day the same way.
+pub struct Lanternfish;
+
+impl Puzzle for Lanternfish {
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Lanternfish";
+
+    type Input = Ocean;
+    type ErrorKind = ErrorKind;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(data: &str) -> Result<Ocean, ParseError> {
+        parse(data)
+    }
+
+    fn check(data: &str) -> Vec<ParseError> {
+        check(data)
+    }
+
+    fn part1(input: &Ocean) -> u64 {
+        part_1(input)
+    }
+
+    fn part2(input: &Ocean) -> u64 {
+        part_2(input)
+    }
+}
+
+/// The number of fish after `days` days.
+pub fn fish_after(ocean: &Ocean, days: usize) -> u64 {
+    let mut ocean = ocean.clone();
+    for _ in 0..days {
+        ocean.step_time()
+    }
+    ocean.total()
+}
+
+/// [`fish_after`], or `None` if the fish outgrow a `u64` on the way.
+pub fn checked_fish_after(ocean: &Ocean, days: usize) -> Option<u64> {
+    let mut ocean = ocean.clone();
+    for _ in 0..days {
+        ocean.checked_step_time()?;
+    }
+    ocean.checked_total()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_part() {
+        let test_data = include_str!("test.txt");
+        let mut ocean = Ocean::build(test_data).unwrap();
+
+        for _ in 0..18 {
+            ocean.step_time()
+        }
+        assert_eq!(ocean.total(), 26);
+
+        for _ in 18..80 {
+            ocean.step_time()
+        }
+        assert_eq!(ocean.total(), 5934);
+        for _ in 80..256 {
+            ocean.step_time()
+        }
+        assert_eq!(ocean.total(), 26984457539);
+    }
+
+    #[test]
+    fn test_checked() {
+        let ocean = Ocean::build(include_str!("test.txt")).unwrap();
+        assert_eq!(checked_fish_after(&ocean, 256), Some(26984457539));
+        assert_eq!(checked_fish_after(&ocean, 1000), None);
+        let mut full = Ocean {
+            fish: [u64::MAX / 2, 0, 0, 0, 0, 0, 0, u64::MAX / 2, 0],
+        };
+        let before = full.clone();
+        assert_eq!(full.checked_step_time(), None);
+        assert_eq!(full.fish, before.fish);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Ocean::build("3,4,3,1,2,x").unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::InvalidAge(_)));
+        assert_eq!((err.line, err.column), (1, 11));
+        let err = Ocean::build("3,4,9,1,2").unwrap_err();
+        assert_eq!((err.kind, err.column), (ErrorKind::AgeOutOfRange, 5));
+    }
+
+    #[test]
+    fn test_check() {
+        let problems = check("3,4,9,1,,2,x\n");
+        let found: Vec<_> = problems.iter().map(|e| e.column).collect();
+        assert_eq!(found, vec![5, 9, 12]);
+        assert_eq!(problems[0].kind, ErrorKind::AgeOutOfRange);
+    }
+
+    proptest! {
+        #[test]
+        fn buckets_match_each_fish(ages in prop::collection::vec(0..=8u8, 1..30), days in 0..40usize) {
+            let data = ages.iter().map(u8::to_string).collect::<Vec<_>>().join(",");
+            let mut ocean = Ocean::build(&data).unwrap();
+            let mut fish = ages;
+            for _ in 0..days {
+                ocean.step_time();
+                let mut born = 0;
+                for timer in fish.iter_mut() {
+                    if *timer == 0 {
+                        *timer = 6;
+                        born += 1;
+                    } else {
+                        *timer -= 1;
+                    }
+                }
+                fish.extend(std::iter::repeat_n(8, born));
+            }
+            for timer in 0..9 {
+                let expected = fish.iter().filter(|&&t| usize::from(t) == timer).count();
+                let count = ocean.count(timer);
+                prop_assert_eq!(count, expected as u64);
+            }
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use generate::day_07::Params;
+
+const SEED: u64 = 7;
+
+/// `count` crabs, bunched towards the low end like the real input.
+fn crabs(count: usize) -> Vec<i32> {
+    let text = generate::day_07::generate(&mut generate::rng(SEED), &Params::sized(count));
+    day_7::parse(&text).unwrap()
+}
+
+fn min_cost(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day-7/min_cost/l1_error");
+    for count in [250, 500, 1_000, 2_000] {
+        let crabs = crabs(count);
+        group.throughput(Throughput::Elements(count as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(count), &crabs, |b, crabs| {
+            b.iter(|| day_7::min_cost(crabs, day_7::l1_error))
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("day-7/min_cost/crab_walk");
+    group.sample_size(10);
+    for count in [100, 200, 400] {
+        let crabs = crabs(count);
+        group.throughput(Throughput::Elements(count as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(count), &crabs, |b, crabs| {
+            b.iter(|| day_7::min_cost(crabs, day_7::crab_walk))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, min_cost);
+criterion_main!(benches);
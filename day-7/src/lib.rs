@@ -0,0 +1,161 @@
+//! Day 7: The Treachery of Whales. Finds the position the crabs can line up
+//! on for the least fuel.
+
+// Part one is the same thing as minimizng L1 norm.
+// I could write a fancy optimizer but for now I will just
+// brute force it
+
+use common::{ParseError as SpanError, Puzzle};
+use rayon::prelude::*;
+use std::num::ParseIntError;
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ErrorKind {
+    #[error("invalid position: {0}")]
+    InvalidPosition(ParseIntError),
+}
+
+pub type ParseError = SpanError<ErrorKind>;
+
+fn distance(from: i32, to: i32) -> u64 {
+    (i64::from(from) - i64::from(to)).unsigned_abs()
+}
+
+/// Fuel needed for every crab to reach `guess` at one unit per step.
+pub fn l1_error(val: &[i32], guess: i32) -> u64 {
+    val.iter()
+        .map(|&o| distance(o, guess))
+        .fold(0, u64::saturating_add)
+}
+
+/// Fuel needed for every crab to reach `guess` when each step costs one more than the last.
+pub fn crab_walk(val: &[i32], guess: i32) -> u64 {
+    val.iter()
+        .map(|&o| {
+            // 1 + 2 + ... + d; a distance fits in 32 bits so this can't overflow
+            let d = distance(o, guess);
+            d * (d + 1) / 2
+        })
+        .fold(0, u64::saturating_add)
+}
+
+/// Cheapest alignment over every position between the outermost crabs.
+/// Costs saturate rather than overflow, and no crabs cost nothing.
+pub fn min_cost(val: &[i32], cost_fn: fn(&[i32], i32) -> u64) -> u64 {
+    let (Some(&min), Some(&max)) = (val.iter().min(), val.iter().max()) else {
+        return 0;
+    };
+    let _span = tracing::debug_span!("min_cost", crabs = val.len(), min, max).entered();
+    (min..=max)
+        .map(|guess| cost_fn(val, guess))
+        .min()
+        .unwrap_or(0)
+}
+
+/// [`min_cost`] with the positions shared out between threads.
+pub fn min_cost_par(val: &[i32], cost_fn: fn(&[i32], i32) -> u64) -> u64 {
+    let (Some(&min), Some(&max)) = (val.iter().min(), val.iter().max()) else {
+        return 0;
+    };
+    let _span = tracing::debug_span!("min_cost_par", crabs = val.len(), min, max).entered();
+    (min..=max)
+        .into_par_iter()
+        .map(|guess| cost_fn(val, guess))
+        .min()
+        .unwrap_or(0)
+}
+
+/// Reads the comma separated crab positions.
+pub fn parse(data: &str) -> Result<Vec<i32>, ParseError> {
+    data.split(',').map(|o| position(data, o)).collect()
+}
+
+fn position(data: &str, o: &str) -> Result<i32, ParseError> {
+    let o = o.trim();
+    o.parse::<i32>()
+        .map_err(|e| ParseError::locate(ErrorKind::InvalidPosition(e), data, o))
+}
+
+/// Every position that isn't a number.
+pub fn check(data: &str) -> Vec<ParseError> {
+    data.split(',')
+        .filter_map(|o| position(data, o).err())
+        .collect()
+}
+
+/// The least fuel when every step costs one.
+pub fn part_1(crabs: &[i32]) -> u64 {
+    min_cost_par(crabs, l1_error)
+}
+
+/// The least fuel when each step costs one more than the last.
+pub fn part_2(crabs: &[i32]) -> u64 {
+    min_cost_par(crabs, crab_walk)
+}
+
+/// Day 7 as a [`Puzzle`], for tools that drive every day the same way.
+pub struct TreacheryOfWhales;
+
+impl Puzzle for TreacheryOfWhales {
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "The Treachery of Whales";
+
+    type Input = Vec<i32>;
+    type ErrorKind = ErrorKind;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(data: &str) -> Result<Vec<i32>, ParseError> {
+        parse(data)
+    }
+
+    fn check(data: &str) -> Vec<ParseError> {
+        check(data)
+    }
+
+    fn part1(input: &Vec<i32>) -> u64 {
+        part_1(input)
+    }
+
+    fn part2(input: &Vec<i32>) -> u64 {
+        part_2(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_min_l1() {
+        let data = parse(include_str!("../data/test.txt")).unwrap();
+        assert_eq!(min_cost(&data, l1_error), 37);
+        assert_eq!(min_cost(&data, crab_walk), 168);
+    }
+
+    #[test]
+    fn test_min_cost_par() {
+        let data = parse(include_str!("../data/test.txt")).unwrap();
+        assert_eq!(min_cost_par(&data, crab_walk), 168);
+        assert_eq!(min_cost_par(&[], l1_error), 0);
+        // spread out and lopsided, so the candidates differ a lot
+        let crabs: Vec<i32> = (0..500).map(|i| (i * i * 7919) % 1999 - 300).collect();
+        for cost_fn in [l1_error, crab_walk] {
+            assert_eq!(min_cost_par(&crabs, cost_fn), min_cost(&crabs, cost_fn));
+        }
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse("16,1,2,\n").unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::InvalidPosition(_)));
+        assert_eq!((err.line, err.column), (1, 8));
+    }
+
+    #[test]
+    fn test_check() {
+        let found: Vec<_> = check("16,x,2,0,4.5\n").iter().map(|e| e.column).collect();
+        assert_eq!(found, vec![4, 10]);
+    }
+}
@@ -0,0 +1,391 @@
+//! Day 8: Seven Segment Search. Works out which scrambled wires drive which
+//! segments of each display, then reads the four digit outputs.
+
+use common::check;
+use common::stream::{self, Lines};
+use common::{ParseError as SpanError, Puzzle};
+use std::collections::{HashMap, HashSet};
+use std::io::BufRead;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ErrorKind {
+    #[error("expected signal patterns and output separated by ` | `")]
+    MissingDelimiter,
+    #[error("patterns may only use the segments a to g, each at most once")]
+    InvalidPattern,
+    #[error("expected {expected} patterns, found {found}")]
+    WrongPatternCount { expected: usize, found: usize },
+    #[error("no pattern with {0} segments")]
+    MissingPattern(usize),
+    #[error("signal patterns do not describe the ten digits")]
+    AmbiguousSignals,
+    #[error("output pattern is not one of the signal patterns")]
+    UndecodableOutput,
+}
+
+pub type ParseError = SpanError<ErrorKind>;
+pub type StreamError = stream::StreamError<ErrorKind>;
+
+/// One line of notes: the ten signal patterns and the four output patterns,
+/// along with the digit each pattern shows. Patterns are kept with their
+/// segments sorted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiaryEntry {
+    signal: Vec<String>,
+    output: Vec<String>,
+    decoder: HashMap<String, u8>,
+}
+
+impl DiaryEntry {
+    /// The ten unique signal patterns.
+    pub fn signal(&self) -> &[String] {
+        &self.signal
+    }
+
+    /// The four patterns of the output value.
+    pub fn output(&self) -> &[String] {
+        &self.output
+    }
+
+    /// Works out which digit each signal pattern shows.
+    pub fn build_decoder(&mut self) -> Result<(), ErrorKind> {
+        let find = |len: usize| {
+            self.signal
+                .iter()
+                .find(|o| o.len() == len)
+                .ok_or(ErrorKind::MissingPattern(len))
+        };
+        // first get 1 4 7 8
+        self.decoder.insert(find(2)?.to_owned(), 1);
+        let four = find(4)?;
+        self.decoder.insert(four.to_string(), 4);
+        let four = to_set(four);
+
+        let seven = find(3)?;
+        self.decoder.insert(seven.to_string(), 7);
+        let seven = to_set(seven);
+
+        let eight = find(7)?;
+        self.decoder.insert(eight.to_string(), 8);
+        let eight = to_set(eight);
+        // Now find 6 9 0; we need 6 & 9 to be sets for later
+        let mut six = String::new();
+        let mut nine = String::new();
+        let mut zero = String::new();
+        for s in self.signal.iter().filter(|o| o.len() == 6) {
+            if eight
+                .difference(&seven)
+                .cloned()
+                .collect::<HashSet<char>>()
+                .is_subset(&to_set(s))
+            {
+                six = s.to_string();
+            } else if four.is_subset(&to_set(s)) {
+                nine = s.to_string();
+            } else {
+                zero = s.to_string();
+            }
+        }
+        self.decoder.insert(six.clone(), 6);
+        self.decoder.insert(nine.clone(), 9);
+        self.decoder.insert(zero, 0);
+
+        let six = to_set(&six);
+
+        // Get the last couple: 2 3 5
+
+        let mut two = String::new();
+        let mut three = String::new();
+        let mut five = String::new();
+        for s in self.signal.iter().filter(|o| o.len() == 5) {
+            if six.is_superset(&to_set(s)) {
+                five = s.clone();
+            } else if eight
+                .difference(&four)
+                .cloned()
+                .collect::<HashSet<_>>()
+                .is_subset(&to_set(s))
+            {
+                two = s.to_string();
+            } else {
+                three = s.to_string();
+            }
+        }
+        self.decoder.insert(two, 2);
+        self.decoder.insert(three, 3);
+        self.decoder.insert(five, 5);
+        // every digit must have ended up with its own pattern
+        if self.decoder.len() != 10 || self.decoder.contains_key("") {
+            return Err(ErrorKind::AmbiguousSignals);
+        }
+        Ok(())
+    }
+    /// The digit shown by a pattern, with its segments in any order.
+    pub fn decode(&self, pattern: &str) -> Option<u8> {
+        let mut segments = pattern.chars().collect::<Vec<_>>();
+        segments.sort_unstable();
+        let pattern = segments.into_iter().collect::<String>();
+        self.decoder.get(&pattern).copied()
+    }
+
+    /// The four digit output value.
+    pub fn get_output(&self) -> u32 {
+        (0..4)
+            .rev()
+            .map(|i| 10u32.pow(i))
+            // every output pattern was checked against the decoder when parsing
+            .zip(self.output.iter().map(|o| self.decoder[o]))
+            .map(|(place, dig)| place * (dig as u32))
+            .sum()
+    }
+}
+
+fn to_set(in_string: &str) -> HashSet<char> {
+    in_string.chars().collect()
+}
+
+impl FromStr for DiaryEntry {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let line = s.trim();
+        let (signal_part, output_part) = line
+            .split_once(" | ")
+            .ok_or_else(|| ParseError::locate(ErrorKind::MissingDelimiter, s, line))?;
+
+        let patterns = |part: &str, expected: usize| -> Result<Vec<String>, ParseError> {
+            let raw: Vec<&str> = part.split(' ').map(|o| o.trim()).collect();
+            if raw.len() != expected {
+                let kind = ErrorKind::WrongPatternCount {
+                    expected,
+                    found: raw.len(),
+                };
+                return Err(ParseError::locate(kind, s, part));
+            }
+            raw.into_iter()
+                .map(|o| {
+                    let mut char_vec: Vec<char> = o.chars().collect();
+                    char_vec.sort();
+                    let len = char_vec.len();
+                    char_vec.dedup();
+                    if len == 0
+                        || len != char_vec.len()
+                        || char_vec.iter().any(|c| !('a'..='g').contains(c))
+                    {
+                        return Err(ParseError::locate(ErrorKind::InvalidPattern, s, o));
+                    }
+                    Ok(char_vec.into_iter().collect())
+                })
+                .collect()
+        };
+        let signal = patterns(signal_part, 10)?;
+        let output = patterns(output_part, 4)?;
+
+        let mut diary = DiaryEntry {
+            signal,
+            output,
+            decoder: HashMap::new(),
+        };
+        diary
+            .build_decoder()
+            .map_err(|kind| ParseError::locate(kind, s, signal_part))?;
+        for (decoded, raw) in diary.output.iter().zip(output_part.split(' ')) {
+            if !diary.decoder.contains_key(decoded) {
+                return Err(ParseError::locate(ErrorKind::UndecodableOutput, s, raw));
+            }
+        }
+        Ok(diary)
+    }
+}
+
+/// How many output digits are a 1, 4, 7 or 8, the digits with a unique
+/// number of segments.
+pub fn easy_chars(entries: &[DiaryEntry]) -> usize {
+    entries
+        .iter()
+        .map(|o| {
+            o.output
+                .iter()
+                .map(|x| match x.len() {
+                    2 | 3 | 4 | 7 => 1,
+                    _ => 0,
+                })
+                .sum::<usize>()
+        })
+        .sum()
+}
+
+/// Reads one entry per line, such as `<ten patterns> | <four patterns>`.
+pub fn parse(data: &str) -> Result<Vec<DiaryEntry>, ParseError> {
+    data.lines()
+        .map(|line| DiaryEntry::from_str(line).map_err(|e| e.within(data, line)))
+        .collect()
+}
+
+/// Every line that isn't an entry that decodes.
+pub fn check(data: &str) -> Vec<ParseError> {
+    check::each_line(data, DiaryEntry::from_str).1
+}
+
+/// Reads one entry per line as they are needed, for notes too long to hold.
+pub fn read_entries<R: BufRead>(
+    reader: R,
+) -> impl Iterator<Item = Result<DiaryEntry, StreamError>> {
+    Lines::new(reader).parsed(DiaryEntry::from_str)
+}
+
+/// [`part_1`] over entries read one at a time.
+pub fn part_1_streamed<R: BufRead>(reader: R) -> Result<usize, StreamError> {
+    stream::until_error(read_entries(reader), |entries| {
+        entries
+            .map(|entry| easy_chars(std::slice::from_ref(&entry)))
+            .sum()
+    })
+}
+
+/// [`part_2`] over entries read one at a time.
+pub fn part_2_streamed<R: BufRead>(reader: R) -> Result<u32, StreamError> {
+    stream::until_error(read_entries(reader), |entries| {
+        entries.map(|entry| entry.get_output()).sum()
+    })
+}
+
+/// How many output digits are a 1, 4, 7 or 8.
+pub fn part_1(entries: &[DiaryEntry]) -> usize {
+    easy_chars(entries)
+}
+
+/// The sum of every output value.
+pub fn part_2(entries: &[DiaryEntry]) -> u32 {
+    entries.iter().map(|entry| entry.get_output()).sum()
+}
+
+/// Day 8 as a [`Puzzle`], for tools that drive every day the same way.
+pub struct SevenSegmentSearch;
+
+impl Puzzle for SevenSegmentSearch {
+    const DAY: u8 = 8;
+    const TITLE: &'static str = "Seven Segment Search";
+
+    type Input = Vec<DiaryEntry>;
+    type ErrorKind = ErrorKind;
+    type Answer1 = usize;
+    type Answer2 = u32;
+
+    fn parse(data: &str) -> Result<Vec<DiaryEntry>, ParseError> {
+        parse(data)
+    }
+
+    fn check(data: &str) -> Vec<ParseError> {
+        check(data)
+    }
+
+    fn part1(input: &Vec<DiaryEntry>) -> usize {
+        part_1(input)
+    }
+
+    fn part2(input: &Vec<DiaryEntry>) -> u32 {
+        part_2(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_part_1() {
+        let data = parse(include_str!("../data/test.txt")).unwrap();
+        assert_eq!(easy_chars(&data), 26);
+    }
+
+    #[test]
+    fn test_build_decoder() {
+        let entry = DiaryEntry::from_str(
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf",
+        )
+        .unwrap();
+        assert_eq!(entry.decode("acedgfb"), Some(8));
+        assert_eq!(entry.decode("cdfbe"), Some(5));
+        assert_eq!(entry.decode("gcdfa"), Some(2));
+        assert_eq!(entry.decode("fbcad"), Some(3));
+        assert_eq!(entry.decode("dab"), Some(7));
+        assert_eq!(entry.decode("cefabd"), Some(9));
+        assert_eq!(entry.decode("cdfgeb"), Some(6));
+        assert_eq!(entry.decode("eafb"), Some(4));
+        assert_eq!(entry.decode("cagedb"), Some(0));
+        assert_eq!(entry.decode("ab"), Some(1));
+        assert_eq!(entry.decode("abc"), None);
+        assert_eq!(entry.get_output(), 5353)
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let entry = "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab";
+        let err = DiaryEntry::from_str(entry).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::MissingDelimiter);
+        let err = DiaryEntry::from_str(&format!("{} | cdfeb fcadb cdfeb", entry)).unwrap_err();
+        assert_eq!(
+            err.kind,
+            ErrorKind::WrongPatternCount {
+                expected: 4,
+                found: 3
+            }
+        );
+        let err =
+            DiaryEntry::from_str(&format!("{} | cdfeb fcadb cdfxb cdbaf", entry)).unwrap_err();
+        assert_eq!((err.kind, err.column), (ErrorKind::InvalidPattern, 74));
+        let err =
+            DiaryEntry::from_str(&format!("{} | cdfeb fcadb cdfeb cdbag", entry)).unwrap_err();
+        assert_eq!((err.kind, err.column), (ErrorKind::UndecodableOutput, 80));
+        let err = DiaryEntry::from_str(
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb abc | ab ab ab ab",
+        )
+        .unwrap_err();
+        assert_eq!(err.kind, ErrorKind::MissingPattern(2));
+    }
+
+    #[test]
+    fn test_check() {
+        let data = include_str!("../data/test.txt");
+        assert!(check(data).is_empty());
+        // drop a signal pattern from the second entry and the bar from the fourth
+        let mut lines: Vec<String> = data.lines().map(String::from).collect();
+        lines[1] = lines[1].replacen("edbfga ", "", 1);
+        lines[3] = lines[3].replacen(" | ", " ", 1);
+        let problems = check(&lines.join("\n"));
+        let found: Vec<_> = problems.iter().map(|e| (e.kind.clone(), e.line)).collect();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    ErrorKind::WrongPatternCount {
+                        expected: 10,
+                        found: 9
+                    },
+                    2
+                ),
+                (ErrorKind::MissingDelimiter, 4)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_streamed() {
+        let data = include_str!("../data/test.txt");
+        assert_eq!(part_1_streamed(data.as_bytes()).unwrap(), 26);
+        assert_eq!(
+            part_2_streamed(data.as_bytes()).unwrap(),
+            part_2(&parse(data).unwrap())
+        );
+        let broken = data.replacen(" | ", " ", 2);
+        match part_2_streamed(broken.as_bytes()) {
+            Err(StreamError::Parse(err)) => {
+                assert_eq!((err.kind, err.line), (ErrorKind::MissingDelimiter, 1))
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+}
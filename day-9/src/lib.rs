@@ -0,0 +1,181 @@
+//! Day 9: Smoke Basin. Finds the low points of a height map and the basins
+//! that drain into them.
+
+use common::{ParseError as SpanError, Puzzle};
+use grid::{Grid, GridErrorKind};
+use std::collections::HashSet;
+use std::str::FromStr;
+use thiserror::Error;
+use tracing::field;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ErrorKind {
+    #[error("{0}")]
+    Grid(GridErrorKind),
+}
+
+pub type ParseError = SpanError<ErrorKind>;
+
+/// A height map, with heights from 0 to 9.
+#[derive(Debug, Clone)]
+pub struct Cave {
+    floor: Grid<u32>,
+}
+impl FromStr for Cave {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Cave {
+            floor: Grid::parse_digits(s).map_err(|e| e.map_kind(ErrorKind::Grid))?,
+        })
+    }
+}
+
+impl Cave {
+    /// The underlying height map.
+    pub fn floor(&self) -> &Grid<u32> {
+        &self.floor
+    }
+
+    /// Grid indices of the locations lower than all of their neighbours.
+    pub fn get_low_points(&self) -> Vec<usize> {
+        self.floor
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &val)| {
+                // a lone location has no neighbours and is its own low point
+                let lowest_neighbor = self.floor.neighbors4(i).map(|o| self.floor[o]).min();
+                if lowest_neighbor.is_none_or(|lowest| val < lowest) {
+                    Some(i)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// The locations that flow into `start`, bounded by nines, in the order
+    /// the flood fill reaches them.
+    pub fn get_basin(&self, start: usize) -> Vec<usize> {
+        let mut to_visit = vec![start];
+        let mut have_visited = HashSet::new();
+        let mut basin = Vec::new();
+
+        while let Some(x) = to_visit.pop() {
+            if have_visited.insert(x) {
+                basin.push(x);
+            }
+            for neighbor in self.floor.neighbors4(x) {
+                if self.floor[neighbor] != 9 && !have_visited.contains(&neighbor) {
+                    to_visit.push(neighbor);
+                }
+            }
+        }
+        basin
+    }
+
+    /// The number of locations that flow into `start`.
+    pub fn get_basin_size(&self, start: usize) -> usize {
+        self.get_basin(start).len()
+    }
+
+    /// The size of every basin, largest first.
+    pub fn get_all_basins(&self) -> Vec<usize> {
+        let span =
+            tracing::debug_span!("basins", count = field::Empty, cells_visited = field::Empty);
+        let _span = span.enter();
+        let mut basins = self
+            .get_low_points()
+            .iter()
+            .map(|&pos| self.get_basin_size(pos))
+            .collect::<Vec<_>>();
+        span.record("count", basins.len());
+        span.record("cells_visited", basins.iter().sum::<usize>());
+        basins.sort();
+        basins.into_iter().rev().collect()
+    }
+}
+
+/// Reads a grid of single digit heights.
+pub fn parse(data: &str) -> Result<Cave, ParseError> {
+    Cave::from_str(data)
+}
+
+/// Every row with a height that isn't a single digit, or that is a different
+/// length to the first.
+pub fn check(data: &str) -> Vec<ParseError> {
+    Grid::check_digits(data)
+        .into_iter()
+        .map(|e| e.map_kind(ErrorKind::Grid))
+        .collect()
+}
+
+/// The sum of the risk levels, one more than the height, of the low points.
+pub fn part_1(cave: &Cave) -> u32 {
+    cave.get_low_points()
+        .iter()
+        .map(|&i| cave.floor[i] + 1)
+        .sum::<u32>()
+}
+
+/// The product of the sizes of the three largest basins.
+pub fn part_2(cave: &Cave) -> usize {
+    cave.get_all_basins().iter().take(3).product()
+}
+
+/// Day 9 as a [`Puzzle`], for tools that drive every day the same way.
+pub struct SmokeBasin;
+
+impl Puzzle for SmokeBasin {
+    const DAY: u8 = 9;
+    const TITLE: &'static str = "Smoke Basin";
+
+    type Input = Cave;
+    type ErrorKind = ErrorKind;
+    type Answer1 = u32;
+    type Answer2 = usize;
+
+    fn parse(data: &str) -> Result<Cave, ParseError> {
+        parse(data)
+    }
+
+    fn check(data: &str) -> Vec<ParseError> {
+        check(data)
+    }
+
+    fn part1(input: &Cave) -> u32 {
+        part_1(input)
+    }
+
+    fn part2(input: &Cave) -> usize {
+        part_2(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let cave = Cave::from_str(include_str!("../data/test.txt")).unwrap();
+        assert_eq!(part_1(&cave), 15);
+        assert_eq!(part_2(&cave), 1134);
+        let cave = Cave::from_str("5").unwrap();
+        assert_eq!((part_1(&cave), part_2(&cave)), (6, 1));
+    }
+
+    #[test]
+    fn test_check() {
+        let found: Vec<_> = check("219\n3a8\n98\n").iter().map(|e| e.line).collect();
+        assert_eq!(found, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_basin_order() {
+        let cave = Cave::from_str(include_str!("../data/test.txt")).unwrap();
+        let low = cave.get_low_points()[0];
+        let basin = cave.get_basin(low);
+        assert_eq!((basin[0], basin.len()), (low, 3));
+    }
+}
@@ -0,0 +1,189 @@
+/* C interface to the Advent of Code 2021 parsers and solvers. */
+
+#ifndef AOC_H
+#define AOC_H
+
+/* Generated by cbindgen from ffi/src/lib.rs, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// The most days or steps a simulation may be asked to run, the same as
+// everywhere else. Written out so the header gets a number.
+#define AOC_MAX_REPEAT 1000000
+
+// What came of a call.
+typedef enum AocStatus {
+  AOC_STATUS_OK = 0,
+  // A required pointer was null.
+  AOC_STATUS_NULL_POINTER,
+  // The input was not valid UTF-8.
+  AOC_STATUS_INVALID_UTF8,
+  // The input could not be parsed.
+  AOC_STATUS_PARSE_ERROR,
+  // The part has no answer for this input.
+  AOC_STATUS_NO_ANSWER,
+  // There is no solution for the day.
+  AOC_STATUS_UNKNOWN_DAY,
+  // The part was not 1 or 2.
+  AOC_STATUS_UNKNOWN_PART,
+  // The buffer given was too small for the result.
+  AOC_STATUS_BUFFER_TOO_SMALL,
+  // The solver panicked. This is a bug.
+  AOC_STATUS_PANIC,
+  // A count was more than `AOC_MAX_REPEAT`.
+  AOC_STATUS_OUT_OF_RANGE,
+  // The result would not fit in its type.
+  AOC_STATUS_OVERFLOW,
+} AocStatus;
+
+// Day 12: the caves and the passages between them.
+typedef struct AocCaveSystem AocCaveSystem;
+
+// Day 6: the count of fish at each age.
+typedef struct AocOcean AocOcean;
+
+// Day 11: the grid of octopi, which steps in place.
+typedef struct AocOctopi AocOctopi;
+
+// Day 14: the polymer template and its insertion rules.
+typedef struct AocPolymer AocPolymer;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Parses `input` and solves `part` of `day`. On success `*answer` is set to
+// a string the caller must release with `aoc_string_free`.
+//
+// # Safety
+// `input` must be a nul terminated string and `answer` must point to
+// writable memory for a pointer.
+enum AocStatus aoc_solve(uint8_t day, uint8_t part, const char *input, char **answer);
+
+// The message for the last failed call on this thread, or null if the last
+// call succeeded. The caller must release it with `aoc_string_free`.
+char *aoc_last_error(void);
+
+// Releases a string returned by this library. Null is ignored.
+//
+// # Safety
+// `s` must be null or a string from this library not already released.
+void aoc_string_free(char *s);
+
+// Parses `input` as a day 6 ocean. On success `*out` is set to a handle
+// the caller must release with `aoc_ocean_free`.
+//
+// # Safety
+// `input` must be a nul terminated string and `out` must point to
+// writable memory for a pointer.
+enum AocStatus aoc_ocean_parse(const char *input, struct AocOcean **out);
+
+// Releases an ocean. Null is ignored.
+//
+// # Safety
+// `ocean` must be null or a handle not already released.
+void aoc_ocean_free(struct AocOcean *ocean);
+
+// Parses `input` as a day 11 grid of octopi. On success `*out` is set to a
+// handle the caller must release with `aoc_octopi_free`.
+//
+// # Safety
+// `input` must be a nul terminated string and `out` must point to
+// writable memory for a pointer.
+enum AocStatus aoc_octopi_parse(const char *input, struct AocOctopi **out);
+
+// Releases a grid of octopi. Null is ignored.
+//
+// # Safety
+// `octopi` must be null or a handle not already released.
+void aoc_octopi_free(struct AocOctopi *octopi);
+
+// Parses `input` as a day 12 cave system. On success `*out` is set to a
+// handle the caller must release with `aoc_cave_system_free`.
+//
+// # Safety
+// `input` must be a nul terminated string and `out` must point to
+// writable memory for a pointer.
+enum AocStatus aoc_cave_system_parse(const char *input, struct AocCaveSystem **out);
+
+// Releases a cave system. Null is ignored.
+//
+// # Safety
+// `caves` must be null or a handle not already released.
+void aoc_cave_system_free(struct AocCaveSystem *caves);
+
+// Parses `input` as a day 14 polymer. On success `*out` is set to a handle
+// the caller must release with `aoc_polymer_free`.
+//
+// # Safety
+// `input` must be a nul terminated string and `out` must point to
+// writable memory for a pointer.
+enum AocStatus aoc_polymer_parse(const char *input, struct AocPolymer **out);
+
+// Releases a polymer. Null is ignored.
+//
+// # Safety
+// `polymer` must be null or a handle not already released.
+void aoc_polymer_free(struct AocPolymer *polymer);
+
+// Sets `*count` to the number of fish after `days` days. The ocean itself
+// is left as it is. Fails with `OutOfRange` for more than
+// `AOC_MAX_REPEAT` days, and with `Overflow` once the fish outgrow a
+// `u64`.
+//
+// # Safety
+// `ocean` must be a live handle and `count` must point to writable memory.
+enum AocStatus aoc_ocean_fish_after(const struct AocOcean *ocean, size_t days, uint64_t *count);
+
+// The number of octopi, or 0 for a null handle.
+//
+// # Safety
+// `octopi` must be null or a live handle.
+size_t aoc_octopi_len(const struct AocOctopi *octopi);
+
+// Moves the octopi on one step and sets `*flashes` to how many flashed.
+//
+// # Safety
+// `octopi` must be a live handle and `flashes` must point to writable
+// memory.
+enum AocStatus aoc_octopi_step(struct AocOctopi *octopi, uint64_t *flashes);
+
+// Copies the energy levels, row by row, into `buffer`, which must have
+// room for `aoc_octopi_len` values.
+//
+// # Safety
+// `octopi` must be a live handle and `buffer` must point to `len` writable
+// values.
+enum AocStatus aoc_octopi_energy(const struct AocOctopi *octopi, uint32_t *buffer, size_t len);
+
+// Sets `*routes` to the number of routes from start to end. Small caves
+// are visited at most once, except that one may be visited twice when
+// `revisit_one_small` is set.
+//
+// # Safety
+// `caves` must be a live handle and `routes` must point to writable memory.
+enum AocStatus aoc_cave_system_count_routes(const struct AocCaveSystem *caves,
+                                            bool revisit_one_small,
+                                            uint32_t *routes);
+
+// Sets `*score` to the most common element's count less the least common
+// one's after `steps` steps of insertion. Fails with `OutOfRange` for
+// more than `AOC_MAX_REPEAT` steps, and with `Overflow` once the polymer
+// outgrows a `u64`.
+//
+// # Safety
+// `polymer` must be a live handle and `score` must point to writable
+// memory.
+enum AocStatus aoc_polymer_score_after(const struct AocPolymer *polymer,
+                                       size_t steps,
+                                       uint64_t *score);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* AOC_H */
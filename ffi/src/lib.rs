@@ -0,0 +1,596 @@
+//! A C interface to the parsers and solvers.
+//!
+//! [`aoc_solve`] answers any part of any day. The states worth keeping
+//! between calls, like day 11's [`Octopi`], are behind opaque handles that
+//! are created by an `aoc_*_parse` function and released with the matching
+//! `aoc_*_free`.
+//!
+//! No function panics across the boundary: each returns an [`AocStatus`]
+//! and writes its result through an out pointer. After a failure
+//! [`aoc_last_error`] describes what went wrong. Every string handed to the
+//! caller is theirs to release with [`aoc_string_free`].
+//!
+//! The header, `include/aoc.h`, is checked in. The build script generates
+//! it afresh from this file, and a test fails if the two differ.
+use common::puzzle::{self, Puzzle};
+use day_11::{DumboOctopus, Octopi};
+use day_12::{CaveSystem, PassagePathing};
+use day_14::{ExtendedPolymerization, Polymer};
+use day_6::{Lanternfish, Ocean};
+use std::any::Any;
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+/// What came of a call.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AocStatus {
+    Ok = 0,
+    /// A required pointer was null.
+    NullPointer,
+    /// The input was not valid UTF-8.
+    InvalidUtf8,
+    /// The input could not be parsed.
+    ParseError,
+    /// The part has no answer for this input.
+    NoAnswer,
+    /// There is no solution for the day.
+    UnknownDay,
+    /// The part was not 1 or 2.
+    UnknownPart,
+    /// The buffer given was too small for the result.
+    BufferTooSmall,
+    /// The solver panicked. This is a bug.
+    Panic,
+    /// A count was more than `AOC_MAX_REPEAT`.
+    OutOfRange,
+    /// The result would not fit in its type.
+    Overflow,
+}
+
+/// The most days or steps a simulation may be asked to run, the same as
+/// everywhere else. Written out so the header gets a number.
+pub const AOC_MAX_REPEAT: u64 = 1_000_000;
+
+/// Day 6: the count of fish at each age.
+pub struct AocOcean(Ocean);
+
+/// Day 11: the grid of octopi, which steps in place.
+pub struct AocOctopi(Octopi);
+
+/// Day 12: the caves and the passages between them.
+pub struct AocCaveSystem(CaveSystem);
+
+/// Day 14: the polymer template and its insertion rules.
+pub struct AocPolymer(Polymer);
+
+struct Failure {
+    status: AocStatus,
+    message: String,
+}
+
+impl Failure {
+    fn new(status: AocStatus, message: impl Into<String>) -> Self {
+        Failure {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+type Outcome = Result<(), Failure>;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Runs `f`, turning a failure or a panic into a status and remembering
+/// its message for [`aoc_last_error`].
+fn call<F>(f: F) -> AocStatus
+where
+    F: FnOnce() -> Outcome,
+{
+    // a handle may be left mid-update by a panic; it is still safe to use
+    // or free, the caller just can't trust its contents
+    let failure = match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => {
+            LAST_ERROR.with(|last| last.borrow_mut().take());
+            return AocStatus::Ok;
+        }
+        Ok(Err(failure)) => failure,
+        Err(payload) => Failure::new(AocStatus::Panic, panic_message(payload)),
+    };
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(failure.message));
+    failure.status
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    let detail = match (
+        payload.downcast_ref::<&str>(),
+        payload.downcast_ref::<String>(),
+    ) {
+        (Some(s), _) => s.to_string(),
+        (_, Some(s)) => s.clone(),
+        _ => String::from("unknown cause"),
+    };
+    format!("solver panicked: {}", detail)
+}
+
+/// # Safety
+/// `ptr` must be null or point to a nul terminated string that outlives `'a`.
+unsafe fn read_str<'a>(ptr: *const c_char, name: &str) -> Result<&'a str, Failure> {
+    if ptr.is_null() {
+        return Err(Failure::new(
+            AocStatus::NullPointer,
+            format!("{} is null", name),
+        ));
+    }
+    CStr::from_ptr(ptr).to_str().map_err(|e| {
+        Failure::new(
+            AocStatus::InvalidUtf8,
+            format!("{} is not UTF-8: {}", name, e),
+        )
+    })
+}
+
+/// # Safety
+/// `ptr` must be null or point to a live `T`.
+unsafe fn borrow<'a, T>(ptr: *const T, name: &str) -> Result<&'a T, Failure> {
+    ptr.as_ref()
+        .ok_or_else(|| Failure::new(AocStatus::NullPointer, format!("{} is null", name)))
+}
+
+/// # Safety
+/// `ptr` must be null or point to a live `T` that nothing else is using.
+unsafe fn borrow_mut<'a, T>(ptr: *mut T, name: &str) -> Result<&'a mut T, Failure> {
+    ptr.as_mut()
+        .ok_or_else(|| Failure::new(AocStatus::NullPointer, format!("{} is null", name)))
+}
+
+/// Checks that `count` is no more than [`AOC_MAX_REPEAT`].
+fn check_repeat(count: usize, name: &str) -> Outcome {
+    match u64::try_from(count) {
+        Ok(count) if count <= AOC_MAX_REPEAT => Ok(()),
+        _ => Err(Failure::new(
+            AocStatus::OutOfRange,
+            format!("{} is more than {}", name, AOC_MAX_REPEAT),
+        )),
+    }
+}
+
+/// Checks `out` before any work is done, so nothing is lost on a null.
+fn check_out<T>(out: *mut T, name: &str) -> Outcome {
+    match out.is_null() {
+        true => Err(Failure::new(
+            AocStatus::NullPointer,
+            format!("{} is null", name),
+        )),
+        false => Ok(()),
+    }
+}
+
+fn into_c_string(s: String) -> *mut c_char {
+    // answers and messages never hold a nul, but be safe if one does
+    CString::new(s.replace('\0', " "))
+        .expect("nuls were replaced")
+        .into_raw()
+}
+
+/// Parses `input` for puzzle `P`, with the error rendered against it.
+fn parse<P: Puzzle>(input: &str) -> Result<P::Input, Failure> {
+    puzzle::parse::<P>(input).map_err(|e| Failure::new(AocStatus::ParseError, e.render(input)))
+}
+
+fn solve<P: Puzzle>(input: &str, part: u8) -> Result<String, Failure> {
+    if !(1..=2).contains(&part) {
+        return Err(Failure::new(
+            AocStatus::UnknownPart,
+            format!("there is no part {}", part),
+        ));
+    }
+    let input = parse::<P>(input)?;
+    puzzle::solve::<P>(&input, part).map_err(|e| Failure::new(AocStatus::NoAnswer, e.to_string()))
+}
+
+/// Parses `input` and solves `part` of `day`. On success `*answer` is set to
+/// a string the caller must release with `aoc_string_free`.
+///
+/// # Safety
+/// `input` must be a nul terminated string and `answer` must point to
+/// writable memory for a pointer.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u8,
+    part: u8,
+    input: *const c_char,
+    answer: *mut *mut c_char,
+) -> AocStatus {
+    call(|| {
+        check_out(answer, "answer")?;
+        let input = read_str(input, "input")?;
+        let solved = match day {
+            1 => solve::<day_1::SonarSweep>(input, part),
+            2 => solve::<day_2::Dive>(input, part),
+            3 => solve::<day_3::BinaryDiagnostic>(input, part),
+            4 => solve::<day_4::GiantSquid>(input, part),
+            5 => solve::<day_5::HydrothermalVenture>(input, part),
+            6 => solve::<day_6::Lanternfish>(input, part),
+            7 => solve::<day_7::TreacheryOfWhales>(input, part),
+            8 => solve::<day_8::SevenSegmentSearch>(input, part),
+            9 => solve::<day_9::SmokeBasin>(input, part),
+            10 => solve::<day_10::SyntaxScoring>(input, part),
+            11 => solve::<day_11::DumboOctopus>(input, part),
+            12 => solve::<day_12::PassagePathing>(input, part),
+            13 => solve::<day_13::TransparentOrigami>(input, part),
+            14 => solve::<day_14::ExtendedPolymerization>(input, part),
+            15 => solve::<day_15::Chiton>(input, part),
+            _ => Err(Failure::new(
+                AocStatus::UnknownDay,
+                format!("no solution for day {}", day),
+            )),
+        }?;
+        *answer = into_c_string(solved);
+        Ok(())
+    })
+}
+
+/// The message for the last failed call on this thread, or null if the last
+/// call succeeded. The caller must release it with `aoc_string_free`.
+#[no_mangle]
+pub extern "C" fn aoc_last_error() -> *mut c_char {
+    LAST_ERROR.with(|last| match last.borrow().as_ref() {
+        Some(message) => into_c_string(message.clone()),
+        None => ptr::null_mut(),
+    })
+}
+
+/// Releases a string returned by this library. Null is ignored.
+///
+/// # Safety
+/// `s` must be null or a string from this library not already released.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// Parses `input` for puzzle `P` and sets `*out` to a new handle around it.
+///
+/// # Safety
+/// As for the `aoc_*_parse` functions.
+unsafe fn parse_handle<P, H>(
+    input: *const c_char,
+    out: *mut *mut H,
+    wrap: fn(P::Input) -> H,
+) -> AocStatus
+where
+    P: Puzzle,
+{
+    call(|| {
+        check_out(out, "out")?;
+        let input = read_str(input, "input")?;
+        *out = Box::into_raw(Box::new(wrap(parse::<P>(input)?)));
+        Ok(())
+    })
+}
+
+/// # Safety
+/// As for the `aoc_*_free` functions.
+unsafe fn free_handle<H>(handle: *mut H) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+/// Parses `input` as a day 6 ocean. On success `*out` is set to a handle
+/// the caller must release with `aoc_ocean_free`.
+///
+/// # Safety
+/// `input` must be a nul terminated string and `out` must point to
+/// writable memory for a pointer.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_ocean_parse(
+    input: *const c_char,
+    out: *mut *mut AocOcean,
+) -> AocStatus {
+    parse_handle::<Lanternfish, _>(input, out, AocOcean)
+}
+
+/// Releases an ocean. Null is ignored.
+///
+/// # Safety
+/// `ocean` must be null or a handle not already released.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_ocean_free(ocean: *mut AocOcean) {
+    free_handle(ocean)
+}
+
+/// Parses `input` as a day 11 grid of octopi. On success `*out` is set to a
+/// handle the caller must release with `aoc_octopi_free`.
+///
+/// # Safety
+/// `input` must be a nul terminated string and `out` must point to
+/// writable memory for a pointer.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_octopi_parse(
+    input: *const c_char,
+    out: *mut *mut AocOctopi,
+) -> AocStatus {
+    parse_handle::<DumboOctopus, _>(input, out, AocOctopi)
+}
+
+/// Releases a grid of octopi. Null is ignored.
+///
+/// # Safety
+/// `octopi` must be null or a handle not already released.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_octopi_free(octopi: *mut AocOctopi) {
+    free_handle(octopi)
+}
+
+/// Parses `input` as a day 12 cave system. On success `*out` is set to a
+/// handle the caller must release with `aoc_cave_system_free`.
+///
+/// # Safety
+/// `input` must be a nul terminated string and `out` must point to
+/// writable memory for a pointer.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_cave_system_parse(
+    input: *const c_char,
+    out: *mut *mut AocCaveSystem,
+) -> AocStatus {
+    parse_handle::<PassagePathing, _>(input, out, AocCaveSystem)
+}
+
+/// Releases a cave system. Null is ignored.
+///
+/// # Safety
+/// `caves` must be null or a handle not already released.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_cave_system_free(caves: *mut AocCaveSystem) {
+    free_handle(caves)
+}
+
+/// Parses `input` as a day 14 polymer. On success `*out` is set to a handle
+/// the caller must release with `aoc_polymer_free`.
+///
+/// # Safety
+/// `input` must be a nul terminated string and `out` must point to
+/// writable memory for a pointer.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_polymer_parse(
+    input: *const c_char,
+    out: *mut *mut AocPolymer,
+) -> AocStatus {
+    parse_handle::<ExtendedPolymerization, _>(input, out, AocPolymer)
+}
+
+/// Releases a polymer. Null is ignored.
+///
+/// # Safety
+/// `polymer` must be null or a handle not already released.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_polymer_free(polymer: *mut AocPolymer) {
+    free_handle(polymer)
+}
+
+/// Sets `*count` to the number of fish after `days` days. The ocean itself
+/// is left as it is. Fails with `OutOfRange` for more than
+/// `AOC_MAX_REPEAT` days, and with `Overflow` once the fish outgrow a
+/// `u64`.
+///
+/// # Safety
+/// `ocean` must be a live handle and `count` must point to writable memory.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_ocean_fish_after(
+    ocean: *const AocOcean,
+    days: usize,
+    count: *mut u64,
+) -> AocStatus {
+    call(|| {
+        check_out(count, "count")?;
+        let ocean = borrow(ocean, "ocean")?;
+        check_repeat(days, "days")?;
+        *count = day_6::checked_fish_after(&ocean.0, days)
+            .ok_or_else(|| Failure::new(AocStatus::Overflow, "the fish outgrow a u64"))?;
+        Ok(())
+    })
+}
+
+/// The number of octopi, or 0 for a null handle.
+///
+/// # Safety
+/// `octopi` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_octopi_len(octopi: *const AocOctopi) -> usize {
+    octopi.as_ref().map_or(0, |octopi| octopi.0.len())
+}
+
+/// Moves the octopi on one step and sets `*flashes` to how many flashed.
+///
+/// # Safety
+/// `octopi` must be a live handle and `flashes` must point to writable
+/// memory.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_octopi_step(octopi: *mut AocOctopi, flashes: *mut u64) -> AocStatus {
+    call(|| {
+        check_out(flashes, "flashes")?;
+        let octopi = borrow_mut(octopi, "octopi")?;
+        *flashes = octopi.0.step();
+        Ok(())
+    })
+}
+
+/// Copies the energy levels, row by row, into `buffer`, which must have
+/// room for `aoc_octopi_len` values.
+///
+/// # Safety
+/// `octopi` must be a live handle and `buffer` must point to `len` writable
+/// values.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_octopi_energy(
+    octopi: *const AocOctopi,
+    buffer: *mut u32,
+    len: usize,
+) -> AocStatus {
+    call(|| {
+        check_out(buffer, "buffer")?;
+        let energy = borrow(octopi, "octopi")?.0.energy();
+        if len < energy.len() {
+            return Err(Failure::new(
+                AocStatus::BufferTooSmall,
+                format!("buffer holds {} values but needs {}", len, energy.len()),
+            ));
+        }
+        ptr::copy_nonoverlapping(energy.as_ptr(), buffer, energy.len());
+        Ok(())
+    })
+}
+
+/// Sets `*routes` to the number of routes from start to end. Small caves
+/// are visited at most once, except that one may be visited twice when
+/// `revisit_one_small` is set.
+///
+/// # Safety
+/// `caves` must be a live handle and `routes` must point to writable memory.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_cave_system_count_routes(
+    caves: *const AocCaveSystem,
+    revisit_one_small: bool,
+    routes: *mut u32,
+) -> AocStatus {
+    call(|| {
+        check_out(routes, "routes")?;
+        let caves = borrow(caves, "caves")?;
+        *routes = caves.0.count_routes(!revisit_one_small);
+        Ok(())
+    })
+}
+
+/// Sets `*score` to the most common element's count less the least common
+/// one's after `steps` steps of insertion. Fails with `OutOfRange` for
+/// more than `AOC_MAX_REPEAT` steps, and with `Overflow` once the polymer
+/// outgrows a `u64`.
+///
+/// # Safety
+/// `polymer` must be a live handle and `score` must point to writable
+/// memory.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_polymer_score_after(
+    polymer: *const AocPolymer,
+    steps: usize,
+    score: *mut u64,
+) -> AocStatus {
+    call(|| {
+        check_out(score, "score")?;
+        let polymer = borrow(polymer, "polymer")?;
+        check_repeat(steps, "steps")?;
+        *score = day_14::checked_score_after(&polymer.0, steps)
+            .ok_or_else(|| Failure::new(AocStatus::Overflow, "the polymer outgrows a u64"))?;
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn solve_str(day: u8, part: u8, input: &str) -> (AocStatus, Option<String>) {
+        let input = CString::new(input).unwrap();
+        let mut answer = ptr::null_mut();
+        let status = unsafe { aoc_solve(day, part, input.as_ptr(), &mut answer) };
+        let answer = (!answer.is_null()).then(|| unsafe {
+            let s = CStr::from_ptr(answer).to_str().unwrap().to_string();
+            aoc_string_free(answer);
+            s
+        });
+        (status, answer)
+    }
+
+    fn last_error() -> Option<String> {
+        let message = aoc_last_error();
+        (!message.is_null()).then(|| unsafe {
+            let s = CStr::from_ptr(message).to_str().unwrap().to_string();
+            aoc_string_free(message);
+            s
+        })
+    }
+
+    #[test]
+    fn test_solve() {
+        let example = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263";
+        assert_eq!(
+            solve_str(1, 2, example),
+            (AocStatus::Ok, Some(String::from("5")))
+        );
+        assert_eq!(last_error(), None);
+        assert_eq!(solve_str(1, 3, example), (AocStatus::UnknownPart, None));
+        assert_eq!(last_error().as_deref(), Some("there is no part 3"));
+        assert_eq!(solve_str(16, 1, example).0, AocStatus::UnknownDay);
+        let (status, _) = solve_str(1, 1, "199\nlots");
+        assert_eq!(status, AocStatus::ParseError);
+        assert!(last_error().unwrap().contains("line 2"));
+        assert_eq!(
+            solve_str(11, 2, "5"),
+            (AocStatus::Ok, Some(String::from("5")))
+        );
+    }
+
+    #[test]
+    fn test_null_pointers() {
+        let mut answer = ptr::null_mut();
+        let status = unsafe { aoc_solve(1, 1, ptr::null(), &mut answer) };
+        assert_eq!(status, AocStatus::NullPointer);
+        assert!(answer.is_null());
+        let input = CString::new("1").unwrap();
+        let status = unsafe { aoc_solve(1, 1, input.as_ptr(), ptr::null_mut()) };
+        assert_eq!(status, AocStatus::NullPointer);
+        assert_eq!(last_error().as_deref(), Some("answer is null"));
+        let mut count = 0;
+        let status = unsafe { aoc_ocean_fish_after(ptr::null(), 18, &mut count) };
+        assert_eq!(status, AocStatus::NullPointer);
+        assert_eq!(unsafe { aoc_octopi_len(ptr::null()) }, 0);
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        assert_eq!(AOC_MAX_REPEAT, puzzle::MAX_REPEAT);
+        let input = CString::new("3,4,3,1,2").unwrap();
+        let mut ocean = ptr::null_mut();
+        let mut count = 0;
+        unsafe {
+            assert_eq!(aoc_ocean_parse(input.as_ptr(), &mut ocean), AocStatus::Ok);
+            let status = aoc_ocean_fish_after(ocean, 1000, &mut count);
+            assert_eq!(status, AocStatus::Overflow);
+            assert_eq!(last_error().as_deref(), Some("the fish outgrow a u64"));
+            let status = aoc_ocean_fish_after(ocean, usize::MAX, &mut count);
+            assert_eq!(status, AocStatus::OutOfRange);
+            assert_eq!(count, 0);
+            aoc_ocean_free(ocean);
+        }
+    }
+
+    #[test]
+    fn test_handles() {
+        let input = CString::new("5483143223\n2745854711\n5264556173\n6141336146\n6357385478\n4167524645\n2176841721\n6882881134\n4846848554\n5283751526").unwrap();
+        let mut octopi = ptr::null_mut();
+        unsafe {
+            assert_eq!(aoc_octopi_parse(input.as_ptr(), &mut octopi), AocStatus::Ok);
+            assert_eq!(aoc_octopi_len(octopi), 100);
+            let mut flashes = 0;
+            assert_eq!(aoc_octopi_step(octopi, &mut flashes), AocStatus::Ok);
+            assert_eq!(aoc_octopi_step(octopi, &mut flashes), AocStatus::Ok);
+            assert_eq!(flashes, 35);
+            let mut energy = [0; 100];
+            let status = aoc_octopi_energy(octopi, energy.as_mut_ptr(), 99);
+            assert_eq!(status, AocStatus::BufferTooSmall);
+            let status = aoc_octopi_energy(octopi, energy.as_mut_ptr(), energy.len());
+            assert_eq!(status, AocStatus::Ok);
+            assert_eq!(energy[..10], [8, 8, 0, 7, 4, 7, 6, 5, 5, 5]);
+            aoc_octopi_free(octopi);
+        }
+    }
+}
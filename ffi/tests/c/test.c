@@ -0,0 +1,155 @@
+/* Exercises the C interface with the puzzle examples. Exits non-zero on
+ * the first check that fails. */
+#include <stdio.h>
+#include <string.h>
+
+#include "aoc.h"
+
+static int failures = 0;
+
+#define CHECK(cond)                                                       \
+    do {                                                                  \
+        if (!(cond)) {                                                    \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__,        \
+                    __LINE__, #cond);                                     \
+            failures++;                                                   \
+        }                                                                 \
+    } while (0)
+
+static const char *SONAR = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263";
+static const char *CAVES = "start-A\nstart-b\nA-c\nA-b\nb-d\nA-end\nb-end";
+static const char *POLYMER =
+    "NNCB\n\n"
+    "CH -> B\nHH -> N\nCB -> H\nNH -> C\nHB -> C\nHC -> B\nHN -> C\n"
+    "NN -> C\nBH -> H\nNC -> B\nNB -> B\nBN -> B\nBB -> N\nBC -> B\n"
+    "CC -> N\nCN -> C";
+static const char *OCTOPI =
+    "5483143223\n2745854711\n5264556173\n6141336146\n6357385478\n"
+    "4167524645\n2176841721\n6882881134\n4846848554\n5283751526";
+
+static void check_answer(uint8_t day, uint8_t part, const char *input,
+                         const char *expected) {
+    char *answer = NULL;
+    AocStatus status = aoc_solve(day, part, input, &answer);
+    CHECK(status == AOC_STATUS_OK);
+    if (answer != NULL) {
+        if (strcmp(answer, expected) != 0) {
+            fprintf(stderr, "day %d part %d: got %s, expected %s\n", day,
+                    part, answer, expected);
+            failures++;
+        }
+        aoc_string_free(answer);
+    }
+}
+
+static void test_solve(void) {
+    check_answer(1, 1, SONAR, "7");
+    check_answer(1, 2, SONAR, "5");
+    check_answer(12, 2, CAVES, "36");
+    check_answer(14, 1, POLYMER, "1588");
+}
+
+static void test_errors(void) {
+    char *answer = NULL;
+    CHECK(aoc_solve(1, 1, "199\nlots", &answer) == AOC_STATUS_PARSE_ERROR);
+    CHECK(answer == NULL);
+    char *message = aoc_last_error();
+    CHECK(message != NULL && strstr(message, "lots") != NULL);
+    aoc_string_free(message);
+
+    CHECK(aoc_solve(0, 1, SONAR, &answer) == AOC_STATUS_UNKNOWN_DAY);
+    CHECK(aoc_solve(1, 3, SONAR, &answer) == AOC_STATUS_UNKNOWN_PART);
+    CHECK(aoc_solve(1, 1, NULL, &answer) == AOC_STATUS_NULL_POINTER);
+    CHECK(aoc_solve(1, 1, SONAR, NULL) == AOC_STATUS_NULL_POINTER);
+    CHECK(aoc_solve(1, 1, "\xff", &answer) == AOC_STATUS_INVALID_UTF8);
+
+    /* a success clears the last error */
+    CHECK(aoc_solve(1, 1, SONAR, &answer) == AOC_STATUS_OK);
+    aoc_string_free(answer);
+    CHECK(aoc_last_error() == NULL);
+
+    /* freeing null is a no-op */
+    aoc_string_free(NULL);
+    aoc_octopi_free(NULL);
+}
+
+static void test_ocean(void) {
+    AocOcean *ocean = NULL;
+    uint64_t count = 0;
+    CHECK(aoc_ocean_parse("3,4,3,1,2", &ocean) == AOC_STATUS_OK);
+    CHECK(aoc_ocean_fish_after(ocean, 18, &count) == AOC_STATUS_OK);
+    CHECK(count == 26);
+    CHECK(aoc_ocean_fish_after(ocean, 256, &count) == AOC_STATUS_OK);
+    CHECK(count == 26984457539ULL);
+
+    /* too many fish for a u64, or too many days to ask for */
+    count = 0;
+    CHECK(aoc_ocean_fish_after(ocean, 1000, &count) == AOC_STATUS_OVERFLOW);
+    CHECK(aoc_ocean_fish_after(ocean, AOC_MAX_REPEAT + 1, &count) ==
+          AOC_STATUS_OUT_OF_RANGE);
+    CHECK(count == 0);
+    aoc_ocean_free(ocean);
+}
+
+static void test_octopi(void) {
+    AocOctopi *octopi = NULL;
+    uint64_t flashes = 0, total = 0;
+    CHECK(aoc_octopi_parse(OCTOPI, &octopi) == AOC_STATUS_OK);
+    size_t len = aoc_octopi_len(octopi);
+    CHECK(len == 100);
+    for (int step = 0; step < 10; step++) {
+        CHECK(aoc_octopi_step(octopi, &flashes) == AOC_STATUS_OK);
+        total += flashes;
+    }
+    CHECK(total == 204);
+
+    uint32_t energy[100];
+    CHECK(aoc_octopi_energy(octopi, energy, 10) == AOC_STATUS_BUFFER_TOO_SMALL);
+    CHECK(aoc_octopi_energy(octopi, energy, len) == AOC_STATUS_OK);
+    /* after step 10 the first row is 0481112976 */
+    CHECK(energy[0] == 0 && energy[1] == 4 && energy[9] == 6);
+    aoc_octopi_free(octopi);
+}
+
+static void test_caves(void) {
+    AocCaveSystem *caves = NULL;
+    uint32_t routes = 0;
+    CHECK(aoc_cave_system_parse(CAVES, &caves) == AOC_STATUS_OK);
+    CHECK(aoc_cave_system_count_routes(caves, false, &routes) == AOC_STATUS_OK);
+    CHECK(routes == 10);
+    CHECK(aoc_cave_system_count_routes(caves, true, &routes) == AOC_STATUS_OK);
+    CHECK(routes == 36);
+    aoc_cave_system_free(caves);
+
+    CHECK(aoc_cave_system_parse("start-", &caves) == AOC_STATUS_PARSE_ERROR);
+}
+
+static void test_polymer(void) {
+    AocPolymer *polymer = NULL;
+    uint64_t score = 0;
+    CHECK(aoc_polymer_parse(POLYMER, &polymer) == AOC_STATUS_OK);
+    CHECK(aoc_polymer_score_after(polymer, 10, &score) == AOC_STATUS_OK);
+    CHECK(score == 1588);
+    CHECK(aoc_polymer_score_after(polymer, 40, &score) == AOC_STATUS_OK);
+    CHECK(score == 2188189693529ULL);
+    CHECK(aoc_polymer_score_after(NULL, 10, &score) == AOC_STATUS_NULL_POINTER);
+    CHECK(aoc_polymer_score_after(polymer, 100, &score) == AOC_STATUS_OVERFLOW);
+    CHECK(aoc_polymer_score_after(polymer, AOC_MAX_REPEAT + 1, &score) ==
+          AOC_STATUS_OUT_OF_RANGE);
+    aoc_polymer_free(polymer);
+}
+
+int main(void) {
+    test_solve();
+    test_errors();
+    test_ocean();
+    test_octopi();
+    test_caves();
+    test_polymer();
+    if (failures > 0) {
+        fprintf(stderr, "%d checks failed\n", failures);
+        return 1;
+    }
+    printf("all checks passed\n");
+    return 0;
+}
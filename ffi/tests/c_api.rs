@@ -0,0 +1,63 @@
+//! Builds `tests/c/test.c` against the shared library and the checked in
+//! header, then runs it.
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+#[test]
+fn test_header_is_current() {
+    let manifest = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let generated = include_str!(concat!(env!("OUT_DIR"), "/aoc.h"));
+    let checked_in = fs::read_to_string(manifest.join("include/aoc.h")).unwrap();
+    assert!(
+        generated == checked_in,
+        "include/aoc.h is out of date, copy it from {}/aoc.h",
+        env!("OUT_DIR")
+    );
+}
+
+// linking by `-l` with an rpath is how Unix compilers do it; MSVC links
+// through an import library instead
+#[cfg(unix)]
+#[test]
+fn test_c_program() {
+    use std::process::Command;
+
+    let manifest = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    // this test runs from <target>/<profile>/deps, next to the library
+    let exe = env::current_exe().unwrap();
+    let lib_dir = exe.parent().and_then(|deps| deps.parent()).unwrap();
+    let lib = format!(
+        "{}aoc_ffi{}",
+        env::consts::DLL_PREFIX,
+        env::consts::DLL_SUFFIX
+    );
+    assert!(
+        lib_dir.join(&lib).exists(),
+        "no {} in {}",
+        lib,
+        lib_dir.display()
+    );
+    let program = exe.with_file_name("aoc_ffi_c_test");
+    let compiler = env::var("CC").unwrap_or_else(|_| String::from("cc"));
+    let status = Command::new(&compiler)
+        .args(["-std=c99", "-Wall", "-Wextra", "-Werror"])
+        .arg("-I")
+        .arg(manifest.join("include"))
+        .arg(manifest.join("tests/c/test.c"))
+        .arg("-o")
+        .arg(&program)
+        .arg("-L")
+        .arg(lib_dir)
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .arg("-laoc_ffi")
+        .status()
+        .unwrap_or_else(|e| panic!("failed to run {}: {}", compiler, e));
+    assert!(status.success(), "the C test program did not compile");
+    let output = Command::new(&program).output().unwrap();
+    assert!(
+        output.status.success(),
+        "the C test program failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
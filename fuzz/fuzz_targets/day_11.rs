@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &str| {
+    if let Ok(input) = day_11::parse(data) {
+        let _ = day_11::part_1(&input);
+        let _ = day_11::part_2(&input);
+    }
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &str| {
+    // the number of routes grows exponentially with the number of edges
+    if data.lines().count() > 12 {
+        return;
+    }
+    if let Ok(input) = day_12::parse(data) {
+        let _ = day_12::part_1(&input);
+        let _ = day_12::part_2(&input);
+    }
+});
@@ -0,0 +1,42 @@
+//! Sonar sweep depths: a random walk that mostly heads deeper.
+use rand::Rng;
+
+pub struct Params {
+    pub depths: usize,
+    pub start: u32,
+    /// Largest change between two readings, either way.
+    pub max_step: u32,
+}
+
+impl Params {
+    pub fn sized(depths: usize) -> Self {
+        Params {
+            depths,
+            ..Params::default()
+        }
+    }
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            depths: 2000,
+            start: 150,
+            max_step: 20,
+        }
+    }
+}
+
+pub fn generate<R: Rng>(rng: &mut R, params: &Params) -> String {
+    let mut depth = params.start;
+    super::lines((0..params.depths).map(|_| {
+        let current = depth;
+        // twice as likely to go down as up, and never above the surface
+        if rng.gen_ratio(2, 3) {
+            depth = depth.saturating_add(rng.gen_range(0..=params.max_step));
+        } else {
+            depth = depth.saturating_sub(rng.gen_range(0..=params.max_step));
+        }
+        current
+    }))
+}
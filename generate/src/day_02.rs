@@ -0,0 +1,47 @@
+//! Submarine course commands.
+use rand::Rng;
+
+pub struct Params {
+    pub commands: usize,
+    pub max_amount: i32,
+    /// The aim never goes beyond this, so part 2 depths stay realistic.
+    pub max_aim: i32,
+}
+
+impl Params {
+    pub fn sized(commands: usize) -> Self {
+        Params {
+            commands,
+            ..Params::default()
+        }
+    }
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            commands: 1000,
+            max_amount: 9,
+            max_aim: 1000,
+        }
+    }
+}
+
+pub fn generate<R: Rng>(rng: &mut R, params: &Params) -> String {
+    let mut aim = 0;
+    super::lines((0..params.commands).map(|_| {
+        let amount = rng.gen_range(1..=params.max_amount);
+        match rng.gen_range(0..3) {
+            0 => format!("forward {}", amount),
+            _ if aim + amount <= params.max_aim && (aim < amount || rng.gen_bool(0.6)) => {
+                aim += amount;
+                format!("down {}", amount)
+            }
+            _ if aim >= amount => {
+                aim -= amount;
+                format!("up {}", amount)
+            }
+            _ => format!("forward {}", amount),
+        }
+    }))
+}
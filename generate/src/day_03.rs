@@ -0,0 +1,42 @@
+//! Diagnostic report: distinct binary numbers of one width.
+use rand::seq::index;
+use rand::Rng;
+
+pub struct Params {
+    /// Number of report lines; capped at `2^width`.
+    pub lines: usize,
+    pub width: usize,
+}
+
+impl Params {
+    /// Widens the numbers as needed so `lines` distinct values exist.
+    pub fn sized(lines: usize) -> Self {
+        let needed = (usize::BITS - lines.leading_zeros()) as usize;
+        Params {
+            lines,
+            width: needed.max(Params::default().width),
+        }
+    }
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            lines: 1000,
+            width: 12,
+        }
+    }
+}
+
+pub fn generate<R: Rng>(rng: &mut R, params: &Params) -> String {
+    // distinct values keep the part 2 ratings from ending in a tie
+    let values = 1usize
+        .checked_shl(params.width as u32)
+        .unwrap_or(usize::MAX);
+    let count = params.lines.clamp(1, values);
+    super::lines(
+        index::sample(rng, values, count)
+            .into_iter()
+            .map(|v| format!("{:0width$b}", v, width = params.width)),
+    )
+}
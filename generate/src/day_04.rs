@@ -0,0 +1,49 @@
+//! Bingo: a draw order and a deck of 5x5 boards.
+use rand::seq::{index, SliceRandom};
+use rand::Rng;
+
+pub struct Params {
+    pub boards: usize,
+    /// Numbers are drawn from `0..numbers`; at least 25.
+    pub numbers: usize,
+}
+
+impl Params {
+    pub fn sized(boards: usize) -> Self {
+        Params {
+            boards,
+            ..Params::default()
+        }
+    }
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            boards: 100,
+            numbers: 100,
+        }
+    }
+}
+
+pub fn generate<R: Rng>(rng: &mut R, params: &Params) -> String {
+    let numbers = params.numbers.max(25);
+    // every number gets drawn, so every board wins eventually
+    let mut draws = (0..numbers).collect::<Vec<_>>();
+    draws.shuffle(rng);
+    let draws = draws
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    let boards = (0..params.boards.max(1)).map(|_| {
+        let squares = index::sample(rng, numbers, 25).into_vec();
+        super::lines(squares.chunks(5).map(|row| {
+            row.iter()
+                .map(|n| format!("{:>2}", n))
+                .collect::<Vec<_>>()
+                .join(" ")
+        }))
+    });
+    format!("{}\n\n{}", draws, boards.collect::<Vec<_>>().join("\n\n"))
+}
@@ -0,0 +1,54 @@
+//! Hydrothermal vent lines, horizontal, vertical and at 45 degrees.
+use rand::Rng;
+
+pub struct Params {
+    pub lines: usize,
+    /// Coordinates lie in `0..extent` on both axes.
+    pub extent: usize,
+    /// Chance of a line being diagonal rather than axis-aligned.
+    pub diagonal_ratio: f64,
+}
+
+impl Params {
+    pub fn sized(lines: usize) -> Self {
+        Params {
+            lines,
+            ..Params::default()
+        }
+    }
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            lines: 500,
+            extent: 1000,
+            diagonal_ratio: 0.35,
+        }
+    }
+}
+
+pub fn generate<R: Rng>(rng: &mut R, params: &Params) -> String {
+    let extent = params.extent.max(1);
+    super::lines((0..params.lines).map(|_| {
+        let (x1, y1) = (rng.gen_range(0..extent), rng.gen_range(0..extent));
+        let (x2, y2) = if rng.gen_bool(params.diagonal_ratio.clamp(0.0, 1.0)) {
+            let (sx, sy): (isize, isize) = (
+                if rng.gen() { 1 } else { -1 },
+                if rng.gen() { 1 } else { -1 },
+            );
+            // how far the line can run in that direction before leaving the floor
+            let room = |at: usize, step: isize| if step > 0 { extent - 1 - at } else { at };
+            let len = rng.gen_range(0..=room(x1, sx).min(room(y1, sy))) as isize;
+            (
+                (x1 as isize + sx * len) as usize,
+                (y1 as isize + sy * len) as usize,
+            )
+        } else if rng.gen() {
+            (rng.gen_range(0..extent), y1)
+        } else {
+            (x1, rng.gen_range(0..extent))
+        };
+        format!("{},{} -> {},{}", x1, y1, x2, y2)
+    }))
+}
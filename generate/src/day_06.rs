@@ -0,0 +1,34 @@
+//! Lanternfish timers.
+use rand::Rng;
+
+pub struct Params {
+    pub fish: usize,
+    /// Timers are drawn from `1..=max_timer`; at most 8.
+    pub max_timer: u8,
+}
+
+impl Params {
+    pub fn sized(fish: usize) -> Self {
+        Params {
+            fish,
+            ..Params::default()
+        }
+    }
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            fish: 300,
+            max_timer: 5,
+        }
+    }
+}
+
+pub fn generate<R: Rng>(rng: &mut R, params: &Params) -> String {
+    let max_timer = params.max_timer.clamp(1, 8);
+    (0..params.fish.max(1))
+        .map(|_| rng.gen_range(1..=max_timer).to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
@@ -0,0 +1,38 @@
+//! Crab positions, bunched towards the low end like the real input.
+use rand::Rng;
+
+pub struct Params {
+    pub crabs: usize,
+    /// Positions lie in `0..=max_position`.
+    pub max_position: u32,
+}
+
+impl Params {
+    pub fn sized(crabs: usize) -> Self {
+        Params {
+            crabs,
+            ..Params::default()
+        }
+    }
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            crabs: 1000,
+            max_position: 2000,
+        }
+    }
+}
+
+pub fn generate<R: Rng>(rng: &mut R, params: &Params) -> String {
+    (0..params.crabs.max(1))
+        .map(|_| {
+            // the smaller of two draws skews towards zero
+            let a = rng.gen_range(0..=params.max_position);
+            let b = rng.gen_range(0..=params.max_position);
+            a.min(b).to_string()
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
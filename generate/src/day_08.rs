@@ -0,0 +1,46 @@
+//! Scrambled seven-segment displays.
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// The lit segments of each digit on an unscrambled display.
+const DIGITS: [&str; 10] = [
+    "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+];
+
+pub struct Params {
+    pub entries: usize,
+}
+
+impl Params {
+    pub fn sized(entries: usize) -> Self {
+        Params { entries }
+    }
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { entries: 200 }
+    }
+}
+
+pub fn generate<R: Rng>(rng: &mut R, params: &Params) -> String {
+    super::lines((0..params.entries).map(|_| {
+        // each display has its own wiring, and lists its patterns in any order
+        let mut wiring = *b"abcdefg";
+        wiring.shuffle(rng);
+        let mut order = (0..10).collect::<Vec<_>>();
+        order.shuffle(rng);
+        let shown = (0..4).map(|_| rng.gen_range(0..10)).collect::<Vec<_>>();
+        let mut scramble = |digit: usize| {
+            let mut pattern = DIGITS[digit]
+                .bytes()
+                .map(|segment| char::from(wiring[usize::from(segment - b'a')]))
+                .collect::<Vec<_>>();
+            pattern.shuffle(rng);
+            pattern.into_iter().collect::<String>()
+        };
+        let signal = order.into_iter().map(&mut scramble).collect::<Vec<_>>();
+        let output = shown.into_iter().map(scramble).collect::<Vec<_>>();
+        format!("{} | {}", signal.join(" "), output.join(" "))
+    }))
+}
@@ -0,0 +1,42 @@
+//! Height maps: random heights split into basins by ridges of nines.
+use rand::Rng;
+
+pub struct Params {
+    pub width: usize,
+    pub height: usize,
+    /// Chance of a location being part of a ridge.
+    pub ridge_ratio: f64,
+}
+
+impl Params {
+    pub fn sized(side: usize) -> Self {
+        Params {
+            width: side,
+            height: side,
+            ..Params::default()
+        }
+    }
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            width: 100,
+            height: 100,
+            ridge_ratio: 0.25,
+        }
+    }
+}
+
+pub fn generate<R: Rng>(rng: &mut R, params: &Params) -> String {
+    let (width, height) = (params.width.max(2), params.height.max(2));
+    let ridge_ratio = params.ridge_ratio.clamp(0.0, 1.0);
+    super::lines((0..height).map(|_| {
+        (0..width)
+            .map(|_| match rng.gen_bool(ridge_ratio) {
+                true => '9',
+                false => char::from(b'0' + rng.gen_range(0..9)),
+            })
+            .collect::<String>()
+    }))
+}
@@ -0,0 +1,70 @@
+//! Navigation subsystem lines, each either corrupted or incomplete.
+use rand::Rng;
+
+const PAIRS: [(char, char); 4] = [('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')];
+
+pub struct Params {
+    pub lines: usize,
+    pub max_len: usize,
+    /// Most brackets open at once. Completion scores grow fivefold with each
+    /// one left open, so much past 27 overflows them.
+    pub max_depth: usize,
+    /// Chance of a line being corrupted rather than incomplete.
+    pub corrupt_ratio: f64,
+}
+
+impl Params {
+    pub fn sized(lines: usize) -> Self {
+        Params {
+            lines,
+            ..Params::default()
+        }
+    }
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            lines: 100,
+            max_len: 110,
+            max_depth: 16,
+            corrupt_ratio: 0.5,
+        }
+    }
+}
+
+pub fn generate<R: Rng>(rng: &mut R, params: &Params) -> String {
+    let max_len = params.max_len.max(2);
+    let max_depth = params.max_depth.max(1);
+    let corrupt_ratio = params.corrupt_ratio.clamp(0.0, 1.0);
+    super::lines((0..params.lines.max(1)).map(|i| {
+        let len = rng.gen_range(2..=max_len);
+        // the first line is always incomplete so part 2 has something to score
+        let corrupt = i > 0 && rng.gen_bool(corrupt_ratio);
+        let mut line = String::with_capacity(len + 1);
+        let mut open = Vec::new();
+        while line.len() < len {
+            if open.is_empty() || (open.len() < max_depth && rng.gen_bool(0.55)) {
+                let pair = PAIRS[rng.gen_range(0..4)];
+                line.push(pair.0);
+                open.push(pair);
+            } else {
+                line.push(open.pop().unwrap().1);
+            }
+        }
+        if corrupt {
+            // close with a bracket that doesn't match the innermost open one
+            let expected = open.last().map(|pair| pair.1);
+            let wrong = PAIRS
+                .iter()
+                .map(|pair| pair.1)
+                .filter(|&c| Some(c) != expected)
+                .nth(rng.gen_range(0..3))
+                .unwrap();
+            line.push(wrong);
+        } else if open.is_empty() {
+            line.push(PAIRS[rng.gen_range(0..4)].0);
+        }
+        line
+    }))
+}
@@ -0,0 +1,41 @@
+//! Octopus energy levels.
+use rand::Rng;
+
+pub struct Params {
+    pub width: usize,
+    pub height: usize,
+    /// Levels are drawn from `lowest..=lowest + spread` for a random
+    /// `lowest`. With the full spread of 9 the octopi may never all flash at
+    /// once, which part 2 waits for; a spread of at most 1 guarantees they do.
+    pub spread: u32,
+}
+
+impl Params {
+    pub fn sized(side: usize) -> Self {
+        Params {
+            width: side,
+            height: side,
+            ..Params::default()
+        }
+    }
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            width: 10,
+            height: 10,
+            spread: 9,
+        }
+    }
+}
+
+pub fn generate<R: Rng>(rng: &mut R, params: &Params) -> String {
+    let spread = params.spread.min(9);
+    let lowest = rng.gen_range(0..=9 - spread);
+    super::lines((0..params.height.max(1)).map(|_| {
+        (0..params.width.max(1))
+            .map(|_| char::from_digit(lowest + rng.gen_range(0..=spread), 10).unwrap())
+            .collect::<String>()
+    }))
+}
@@ -0,0 +1,106 @@
+//! Cave systems: a connected graph of big and small caves.
+//!
+//! Two big caves are never joined directly, since a route could bounce
+//! between them forever, so every generated system has finitely many routes.
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashSet;
+
+pub struct Params {
+    /// Caves besides `start` and `end`.
+    pub caves: usize,
+    /// Chance of a cave being big.
+    pub big_ratio: f64,
+    /// Edges added on top of the ones connecting every cave.
+    pub extra_edges: usize,
+}
+
+impl Params {
+    pub fn sized(caves: usize) -> Self {
+        Params {
+            caves,
+            extra_edges: caves / 2,
+            ..Params::default()
+        }
+    }
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            caves: 12,
+            big_ratio: 0.25,
+            extra_edges: 10,
+        }
+    }
+}
+
+/// A unique name for the `i`th cave: two or more letters, upper case for big
+/// caves. Names that would collide with `start` or `end` are skipped.
+fn name(mut i: usize, is_big: bool) -> String {
+    let mut letters = Vec::new();
+    i += 26;
+    while i > 0 {
+        letters.push(b'a' + (i % 26) as u8);
+        i /= 26;
+    }
+    let name = letters
+        .into_iter()
+        .rev()
+        .map(char::from)
+        .collect::<String>();
+    match is_big {
+        true => name.to_uppercase(),
+        false => name,
+    }
+}
+
+pub fn generate<R: Rng>(rng: &mut R, params: &Params) -> String {
+    let big_ratio = params.big_ratio.clamp(0.0, 1.0);
+    let mut caves = vec![(String::from("start"), false)];
+    let mut index = 0;
+    while caves.len() <= params.caves {
+        let is_big = rng.gen_bool(big_ratio);
+        let name = name(index, is_big);
+        index += 1;
+        if name != "end" {
+            caves.push((name, is_big));
+        }
+    }
+    caves.push((String::from("end"), false));
+
+    let mut edges = Vec::new();
+    let mut joined = HashSet::new();
+    let mut join = |a: usize, b: usize, edges: &mut Vec<(usize, usize)>| {
+        let both_big = caves[a].1 && caves[b].1;
+        if a == b || both_big || !joined.insert((a.min(b), a.max(b))) {
+            return false;
+        }
+        edges.push((a, b));
+        true
+    };
+    // join each cave to one seen before it, so everything is reachable;
+    // `start` is small, so there is always somewhere to fall back to
+    for i in 1..caves.len() {
+        let joined_earlier = (0..8).any(|_| join(rng.gen_range(0..i), i, &mut edges));
+        if !joined_earlier {
+            join(0, i, &mut edges);
+        }
+    }
+    let mut added = 0;
+    for _ in 0..params.extra_edges * 10 {
+        if added == params.extra_edges {
+            break;
+        }
+        let (a, b) = (rng.gen_range(0..caves.len()), rng.gen_range(0..caves.len()));
+        if join(a, b, &mut edges) {
+            added += 1;
+        }
+    }
+    edges.shuffle(rng);
+    super::lines(
+        edges
+            .into_iter()
+            .map(|(a, b)| format!("{}-{}", caves[a].0, caves[b].0)),
+    )
+}
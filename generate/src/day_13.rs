@@ -0,0 +1,82 @@
+//! Transparent paper: dots and the folds that line them up.
+use rand::Rng;
+
+pub struct Params {
+    pub dots: usize,
+    /// Size of the paper once every fold is done.
+    pub folded_width: usize,
+    pub folded_height: usize,
+    pub x_folds: usize,
+    pub y_folds: usize,
+    /// Share of the folded paper that ends up marked.
+    pub lit_ratio: f64,
+}
+
+impl Params {
+    pub fn sized(dots: usize) -> Self {
+        Params {
+            dots,
+            ..Params::default()
+        }
+    }
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            dots: 800,
+            folded_width: 40,
+            folded_height: 6,
+            x_folds: 5,
+            y_folds: 7,
+            lit_ratio: 0.4,
+        }
+    }
+}
+
+pub fn generate<R: Rng>(rng: &mut R, params: &Params) -> String {
+    // the marks the folded paper should show, like the letters of a code
+    let (folded_width, folded_height) = (params.folded_width.max(1), params.folded_height.max(1));
+    let lit_ratio = params.lit_ratio.clamp(0.0, 1.0);
+    let mut lit = (0..folded_height)
+        .flat_map(|y| (0..folded_width).map(move |x| (x, y)))
+        .filter(|_| rng.gen_bool(lit_ratio))
+        .collect::<Vec<_>>();
+    if lit.is_empty() {
+        lit.push((0, 0));
+    }
+    let (mut width, mut height) = (folded_width, folded_height);
+    // work outwards from the folded paper; each fold halves one side
+    let (mut x_folds, mut y_folds) = (params.x_folds, params.y_folds);
+    let mut folds = Vec::new();
+    while x_folds + y_folds > 0 {
+        if x_folds > 0 && (y_folds == 0 || rng.gen()) {
+            folds.push(('x', width));
+            width = 2 * width + 1;
+            x_folds -= 1;
+        } else {
+            folds.push(('y', height));
+            height = 2 * height + 1;
+            y_folds -= 1;
+        }
+    }
+    folds.reverse();
+
+    let dots = (0..params.dots.max(1)).map(|_| {
+        // start on the folded paper and unfold, so no dot sits on a fold line
+        let (mut x, mut y) = lit[rng.gen_range(0..lit.len())];
+        for &(axis, at) in folds.iter().rev() {
+            if rng.gen() {
+                match axis {
+                    'x' => x = 2 * at - x,
+                    _ => y = 2 * at - y,
+                }
+            }
+        }
+        format!("{},{}", x, y)
+    });
+    let folds = folds
+        .iter()
+        .map(|(axis, at)| format!("fold along {}={}", axis, at));
+    format!("{}\n\n{}", super::lines(dots), super::lines(folds))
+}
@@ -0,0 +1,45 @@
+//! Polymer templates with an insertion rule for every pair of elements.
+use rand::seq::index;
+use rand::Rng;
+
+pub struct Params {
+    pub template_len: usize,
+    /// Number of distinct elements, at most 26.
+    pub elements: usize,
+}
+
+impl Params {
+    pub fn sized(template_len: usize) -> Self {
+        Params {
+            template_len,
+            ..Params::default()
+        }
+    }
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            template_len: 20,
+            elements: 10,
+        }
+    }
+}
+
+pub fn generate<R: Rng>(rng: &mut R, params: &Params) -> String {
+    let elements = index::sample(rng, 26, params.elements.clamp(1, 26))
+        .into_iter()
+        .map(|i| char::from(b'A' + i as u8))
+        .collect::<Vec<_>>();
+    let pick = |rng: &mut R| elements[rng.gen_range(0..elements.len())];
+    let template = (0..params.template_len.max(1))
+        .map(|_| pick(rng))
+        .collect::<String>();
+    let mut rules = Vec::new();
+    for &left in elements.iter() {
+        for &right in elements.iter() {
+            rules.push(format!("{}{} -> {}", left, right, pick(rng)));
+        }
+    }
+    format!("{}\n\n{}", template, super::lines(rules))
+}
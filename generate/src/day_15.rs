@@ -0,0 +1,30 @@
+//! Chiton risk levels.
+use rand::Rng;
+
+pub struct Params {
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Params {
+    pub fn sized(side: usize) -> Self {
+        Params {
+            width: side,
+            height: side,
+        }
+    }
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params::sized(100)
+    }
+}
+
+pub fn generate<R: Rng>(rng: &mut R, params: &Params) -> String {
+    super::lines((0..params.height.max(1)).map(|_| {
+        (0..params.width.max(1))
+            .map(|_| char::from(b'0' + rng.gen_range(1..10)))
+            .collect::<String>()
+    }))
+}
@@ -0,0 +1,137 @@
+//! Seeded generators for synthetic puzzle inputs.
+//!
+//! Every day has a module with a `Params` struct describing the shape of the
+//! input and a `generate` function that writes it in exactly the format the
+//! day's parser reads. The same seed and params always give the same text.
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+pub mod day_01;
+pub mod day_02;
+pub mod day_03;
+pub mod day_04;
+pub mod day_05;
+pub mod day_06;
+pub mod day_07;
+pub mod day_08;
+pub mod day_09;
+pub mod day_10;
+pub mod day_11;
+pub mod day_12;
+pub mod day_13;
+pub mod day_14;
+pub mod day_15;
+
+/// The random number generator every generator draws from.
+pub fn rng(seed: u64) -> ChaCha8Rng {
+    ChaCha8Rng::seed_from_u64(seed)
+}
+
+/// Generates an input for `day`. `size` scales the input (the number of
+/// lines, entries or the side of a grid, depending on the day); `None` gives
+/// an input about as large as the real one.
+pub fn generate(day: u8, seed: u64, size: Option<usize>) -> Option<String> {
+    macro_rules! with_params {
+        ($module:ident) => {{
+            let params = size.map_or_else($module::Params::default, $module::Params::sized);
+            $module::generate(&mut rng(seed), &params)
+        }};
+    }
+    let text = match day {
+        1 => with_params!(day_01),
+        2 => with_params!(day_02),
+        3 => with_params!(day_03),
+        4 => with_params!(day_04),
+        5 => with_params!(day_05),
+        6 => with_params!(day_06),
+        7 => with_params!(day_07),
+        8 => with_params!(day_08),
+        9 => with_params!(day_09),
+        10 => with_params!(day_10),
+        11 => with_params!(day_11),
+        12 => with_params!(day_12),
+        13 => with_params!(day_13),
+        14 => with_params!(day_14),
+        15 => with_params!(day_15),
+        _ => return None,
+    };
+    Some(text)
+}
+
+/// Joins generated lines the way the puzzle inputs are laid out.
+fn lines<I>(lines: I) -> String
+where
+    I: IntoIterator,
+    I::Item: ToString,
+{
+    lines
+        .into_iter()
+        .map(|line| line.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    macro_rules! solves {
+        ($krate:ident, $text:expr) => {{
+            let input = ::$krate::parse(&$text).unwrap();
+            ::$krate::part_1(&input);
+            ::$krate::part_2(&input);
+        }};
+    }
+
+    #[test]
+    fn test_deterministic() {
+        for day in 1..=15 {
+            assert_eq!(generate(day, 7, Some(10)), generate(day, 7, Some(10)));
+            assert_ne!(generate(day, 7, Some(10)), generate(day, 8, Some(10)));
+        }
+        assert_eq!(generate(16, 7, None), None);
+    }
+
+    #[test]
+    fn test_parses_and_solves() {
+        for seed in 0..5 {
+            let text = |day| generate(day, seed, Some(12)).unwrap();
+            solves!(day_1, text(1));
+            solves!(day_2, text(2));
+            solves!(day_3, text(3));
+            solves!(day_4, text(4));
+            solves!(day_5, text(5));
+            solves!(day_6, text(6));
+            solves!(day_7, text(7));
+            solves!(day_8, text(8));
+            solves!(day_9, text(9));
+            solves!(day_10, text(10));
+            let octopi = day_11::Params {
+                spread: 1,
+                ..day_11::Params::sized(12)
+            };
+            solves!(day_11, day_11::generate(&mut rng(seed), &octopi));
+            solves!(day_12, text(12));
+            solves!(day_13, text(13));
+            solves!(day_14, text(14));
+            solves!(day_15, text(15));
+        }
+    }
+
+    #[test]
+    fn test_caves_are_finite() {
+        let params = day_12::Params {
+            big_ratio: 0.9,
+            ..day_12::Params::sized(40)
+        };
+        for seed in 0..20 {
+            let text = day_12::generate(&mut rng(seed), &params);
+            let is_big = |name: &str| name.chars().all(|c| c.is_ascii_uppercase());
+            for edge in text.lines() {
+                let (a, b) = edge.split_once('-').unwrap();
+                assert!(!(is_big(a) && is_big(b)), "{}", edge);
+            }
+            ::day_12::parse(&text).unwrap().count_routes(true);
+        }
+    }
+}
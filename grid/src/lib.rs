@@ -0,0 +1,421 @@
+//! A dense, row-major 2D grid shared by the puzzles whose input is a block
+//! of digits (day 9, 11 and 15).
+//!
+//! Cells are addressed either by `(x, y)` or by their flat index
+//! `y * row_len + x`, which is what most of the solvers carry around.
+use common::ParseError;
+use std::fmt;
+use std::iter::StepBy;
+use std::ops::{Index, IndexMut};
+use std::slice::{Chunks, Iter, IterMut};
+
+const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+const ALL_AROUND: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridErrorKind {
+    Empty,
+    InvalidChar,
+    RaggedRow { expected: usize, found: usize },
+}
+
+impl fmt::Display for GridErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridErrorKind::Empty => write!(f, "empty grid"),
+            GridErrorKind::InvalidChar => write!(f, "invalid char in grid"),
+            GridErrorKind::RaggedRow { expected, found } => write!(
+                f,
+                "invalid row length: expected {} found {}",
+                expected, found
+            ),
+        }
+    }
+}
+
+pub type ParseGridError = ParseError<GridErrorKind>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    row_len: usize,
+    col_len: usize,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from row-major cells. Returns `None` if `cells` does not
+    /// hold exactly `row_len * col_len` values, or the grid would be empty.
+    pub fn from_vec(cells: Vec<T>, row_len: usize, col_len: usize) -> Option<Self> {
+        if row_len == 0 || col_len == 0 || cells.len() != row_len * col_len {
+            return None;
+        }
+        Some(Grid {
+            cells,
+            row_len,
+            col_len,
+        })
+    }
+
+    /// Builds a grid with `f(x, y)` in each cell. Returns `None` if the grid
+    /// would be empty, since an empty grid has no rows to index by.
+    pub fn from_fn<F>(row_len: usize, col_len: usize, mut f: F) -> Option<Self>
+    where
+        F: FnMut(usize, usize) -> T,
+    {
+        if row_len == 0 || col_len == 0 {
+            return None;
+        }
+        let cells = (0..col_len)
+            .flat_map(|y| (0..row_len).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect();
+        Some(Grid {
+            cells,
+            row_len,
+            col_len,
+        })
+    }
+
+    /// Parses one row per line, turning each char into a cell with `f`.
+    /// Every row must be as long as the first one.
+    pub fn parse_with<F>(s: &str, f: F) -> Result<Self, ParseGridError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut problems = Vec::new();
+        match Grid::read(s, f, &mut problems) {
+            Some(grid) => Ok(grid),
+            None => Err(problems.swap_remove(0)),
+        }
+    }
+
+    /// Every problem [`Grid::parse_with`] would stop at the first of: the
+    /// first invalid char of each row, and each row that isn't as long as
+    /// the first one.
+    pub fn check_with<F>(s: &str, f: F) -> Vec<ParseGridError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut problems = Vec::new();
+        Grid::read(s, f, &mut problems);
+        problems
+    }
+
+    /// The grid, if there were no problems to add to `problems`.
+    fn read<F>(s: &str, mut f: F, problems: &mut Vec<ParseGridError>) -> Option<Self>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut lines = s.lines().peekable();
+        let row_len = match lines.peek() {
+            Some(line) if !line.is_empty() => line.chars().count(),
+            _ => {
+                problems.push(ParseError::whole_input(GridErrorKind::Empty));
+                return None;
+            }
+        };
+        let mut cells = Vec::new();
+        let mut col_len = 0;
+        for line in lines {
+            let mut found = 0;
+            let mut invalid = None;
+            for (i, c) in line.char_indices() {
+                found += 1;
+                match f(c) {
+                    Some(cell) => cells.push(cell),
+                    None => {
+                        invalid.get_or_insert(&line[i..i + c.len_utf8()]);
+                    }
+                }
+            }
+            if let Some(c) = invalid {
+                problems.push(ParseError::locate(GridErrorKind::InvalidChar, s, c));
+            }
+            if found != row_len {
+                let kind = GridErrorKind::RaggedRow {
+                    expected: row_len,
+                    found,
+                };
+                problems.push(ParseError::locate(kind, s, line));
+            }
+            col_len += 1;
+        }
+        problems.is_empty().then_some(Grid {
+            cells,
+            row_len,
+            col_len,
+        })
+    }
+
+    /// Number of cells in a row, i.e. the width.
+    pub fn row_len(&self) -> usize {
+        self.row_len
+    }
+
+    /// Number of cells in a column, i.e. the height.
+    pub fn col_len(&self) -> usize {
+        self.col_len
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn to_index(&self, x: usize, y: usize) -> Option<usize> {
+        if x < self.row_len && y < self.col_len {
+            Some(y * self.row_len + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_xy(&self, index: usize) -> (usize, usize) {
+        (index % self.row_len, index / self.row_len)
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.to_index(x, y).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.to_index(x, y).map(move |i| &mut self.cells[i])
+    }
+
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn into_cells(self) -> Vec<T> {
+        self.cells
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn rows(&self) -> Chunks<'_, T> {
+        self.cells.chunks(self.row_len.max(1))
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        if y < self.col_len {
+            Some(&self.cells[y * self.row_len..(y + 1) * self.row_len])
+        } else {
+            None
+        }
+    }
+
+    pub fn column(&self, x: usize) -> Option<StepBy<Iter<'_, T>>> {
+        if x < self.row_len {
+            Some(self.cells[x..].iter().step_by(self.row_len))
+        } else {
+            None
+        }
+    }
+
+    /// The up to 4 cells sharing an edge with `index`.
+    pub fn neighbors4(&self, index: usize) -> Neighbors {
+        self.neighbors(index, &ORTHOGONAL)
+    }
+
+    /// The up to 8 cells sharing an edge or a corner with `index`.
+    pub fn neighbors8(&self, index: usize) -> Neighbors {
+        self.neighbors(index, &ALL_AROUND)
+    }
+
+    fn neighbors(&self, index: usize, offsets: &'static [(isize, isize)]) -> Neighbors {
+        let (x, y) = self.to_xy(index);
+        Neighbors {
+            x,
+            y,
+            row_len: self.row_len,
+            col_len: self.col_len,
+            offsets: offsets.iter(),
+        }
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            row_len: self.row_len,
+            col_len: self.col_len,
+        }
+    }
+}
+
+impl Grid<u32> {
+    /// Parses a block of decimal digits, one row per line.
+    pub fn parse_digits(s: &str) -> Result<Self, ParseGridError> {
+        Grid::parse_with(s, |c| c.to_digit(10))
+    }
+
+    /// Every problem [`Grid::parse_digits`] would stop at the first of.
+    pub fn check_digits(s: &str) -> Vec<ParseGridError> {
+        Grid::check_with(s, |c| c.to_digit(10))
+    }
+}
+
+/// Flat indices of the cells around a position, see [`Grid::neighbors4`] and
+/// [`Grid::neighbors8`].
+#[derive(Debug, Clone)]
+pub struct Neighbors {
+    x: usize,
+    y: usize,
+    row_len: usize,
+    col_len: usize,
+    offsets: Iter<'static, (isize, isize)>,
+}
+
+impl Iterator for Neighbors {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        for &(dx, dy) in self.offsets.by_ref() {
+            let x = self.x.checked_add_signed(dx);
+            let y = self.y.checked_add_signed(dy);
+            if let (Some(x), Some(y)) = (x, y) {
+                if x < self.row_len && y < self.col_len {
+                    return Some(y * self.row_len + x);
+                }
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.offsets.len()))
+    }
+}
+
+impl<T> Index<usize> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.cells[index]
+    }
+}
+
+impl<T> IndexMut<usize> for Grid<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.cells[index]
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y).expect("grid position out of bounds")
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        self.get_mut(x, y).expect("grid position out of bounds")
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_index() {
+        let grid = Grid::parse_digits("123\n456").unwrap();
+        assert_eq!((grid.row_len(), grid.col_len()), (3, 2));
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.to_index(1, 1), Some(4));
+        assert_eq!(grid.to_xy(4), (1, 1));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.row(1), Some(&[4, 5, 6][..]));
+        assert_eq!(grid.column(1).unwrap().copied().collect::<Vec<_>>(), [2, 5]);
+        assert_eq!(grid.to_string(), "123\n456");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Grid::parse_digits("").unwrap_err();
+        assert_eq!(err.kind, GridErrorKind::Empty);
+        let err = Grid::parse_digits("12\n1x").unwrap_err();
+        assert_eq!(err.kind, GridErrorKind::InvalidChar);
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 2, "x"));
+        let err = Grid::parse_digits("12\n123").unwrap_err();
+        assert_eq!(
+            err.kind,
+            GridErrorKind::RaggedRow {
+                expected: 2,
+                found: 3
+            }
+        );
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+
+    #[test]
+    fn test_empty() {
+        assert_eq!(Grid::<u8>::from_vec(vec![], 0, 3), None);
+        assert_eq!(Grid::<u8>::from_vec(vec![], 3, 0), None);
+        assert_eq!(Grid::from_fn(0, 2, |_, _| 0), None);
+        assert_eq!(Grid::from_fn(2, 0, |_, _| 0), None);
+        assert_eq!(
+            Grid::from_vec(vec![1, 2], 2, 1).map(|g| g.to_xy(1)),
+            Some((1, 0))
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let problems = Grid::check_digits("123\n4x6\n78\n9y");
+        let found: Vec<_> = problems.iter().map(|e| (e.line, e.column)).collect();
+        assert_eq!(found, vec![(2, 2), (3, 1), (4, 2), (4, 1)]);
+        assert!(Grid::check_digits("12\n34").is_empty());
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::from_fn(3, 3, |x, y| (x, y)).unwrap();
+        let mut corner = grid.neighbors4(0).collect::<Vec<_>>();
+        corner.sort_unstable();
+        assert_eq!(corner, [1, 3]);
+        assert_eq!(grid.neighbors4(4).count(), 4);
+        let mut corner = grid.neighbors8(8).collect::<Vec<_>>();
+        corner.sort_unstable();
+        assert_eq!(corner, [4, 5, 7]);
+        assert_eq!(grid.neighbors8(4).count(), 8);
+        // the right edge must not wrap onto the next row
+        assert!(!grid.neighbors8(2).any(|i| i == 3));
+    }
+}
@@ -0,0 +1,84 @@
+//! Bingo: every board as numbers are called, with called squares marked and
+//! winning boards picked out.
+use crate::frame::{Cell, Frame};
+use crossterm::style::Color;
+use day_4::{Bingo, Board, CallNumber};
+use std::iter;
+
+/// Boards drawn side by side on each line.
+const BOARDS_ACROSS: usize = 5;
+
+/// The boards as each number is called, until they have all won.
+pub fn frames(bingo: &Bingo) -> impl Iterator<Item = Frame> {
+    let mut boards = bingo.boards.clone();
+    let first = draw(&boards, String::from("no numbers called"));
+    let total = bingo.numbers.len();
+    let mut called = bingo.numbers.clone().into_iter().enumerate();
+    let mut all_won = false;
+    let rest = iter::from_fn(move || {
+        if all_won {
+            return None;
+        }
+        let (i, number) = called.next()?;
+        for board in boards.iter_mut() {
+            board.call_number(number);
+        }
+        let won = boards.iter().filter(|b| b.is_winner()).count();
+        all_won = won == boards.len();
+        let caption = format!(
+            "called {} ({} of {}), {} of {} boards won",
+            number,
+            i + 1,
+            total,
+            won,
+            boards.len()
+        );
+        Some(draw(&boards, caption))
+    });
+    iter::once(first).chain(rest)
+}
+
+fn draw(boards: &[Board], caption: String) -> Frame {
+    let mut rows = Vec::new();
+    for group in boards.chunks(BOARDS_ACROSS) {
+        let lines = group.iter().map(|b| b.rows().count()).max().unwrap_or(0);
+        for line in 0..lines {
+            let mut row = Vec::new();
+            for board in group {
+                let squares = board.rows().nth(line).unwrap_or_default();
+                for square in squares {
+                    let color = match (square.is_checked, board.is_winner()) {
+                        (true, true) => Color::Green,
+                        (true, false) => Color::Yellow,
+                        (false, _) => Color::DarkGrey,
+                    };
+                    let text = format!("{:>3}", square.number);
+                    row.extend(text.chars().map(|ch| Cell::new(ch, color)));
+                }
+                row.extend([Cell::blank(); 3]);
+            }
+            rows.push(row);
+        }
+        rows.push(Vec::new());
+    }
+    Frame::new(caption, rows)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_frames() {
+        let bingo = day_4::parse(include_str!("../../day-4/src/test.txt")).unwrap();
+        let frames: Vec<_> = frames(&bingo).collect();
+        // the last board wins on the 15th number, 13
+        assert_eq!(frames.len(), 16);
+        assert_eq!(
+            frames[15].caption,
+            "called 13 (15 of 27), 3 of 3 boards won"
+        );
+        assert_eq!(frames[0].rows.len(), 6);
+        assert!(frames[0].plain().contains(" 22 13 17 11  0"));
+    }
+}
@@ -0,0 +1,33 @@
+//! Hydrothermal vents: a heatmap of how many lines cover each point.
+use crate::image::{Image, Palette};
+use day_5::{Line, OceanFloor};
+
+/// Draws the floor with every line, or without the diagonal ones.
+pub fn image(lines: &[Line], include_diag: bool, palette: Palette) -> Image {
+    let floor = OceanFloor::new(lines, include_diag);
+    let (width, height) = floor.size();
+    let most = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .map(|(x, y)| floor.vents_at(x, y))
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    Image::from_fn(width, height, |x, y| {
+        palette.at(f64::from(floor.vents_at(x, y)) / f64::from(most))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_image() {
+        let lines = day_5::parse(include_str!("../../day-5/src/test.txt")).unwrap();
+        let image = image(&lines, false, Palette::Gray);
+        assert_eq!((image.width(), image.height()), (10, 10));
+        // two lines cross at 0,9, the most anywhere without diagonals
+        assert_eq!(image.pixel(0, 9), Some(crate::image::WHITE));
+        assert_eq!(image.pixel(0, 0), Some(crate::image::BLACK));
+    }
+}
@@ -0,0 +1,103 @@
+//! Smoke basins: the height map, then each basin flooding out from its low
+//! point in the order the fill reaches it.
+use crate::frame::{self, Cell, Frame};
+use crate::image::{Image, Palette, BLACK};
+use crossterm::style::Color;
+use day_9::Cave;
+use grid::Grid;
+use std::iter;
+
+/// Frames per basin at most; bigger basins fill several cells per frame.
+const FRAMES_PER_BASIN: usize = 4;
+
+/// The height map, then each basin filling in turn.
+pub fn frames(cave: &Cave) -> impl Iterator<Item = Frame> {
+    let cave = cave.clone();
+    let mut colors: Vec<_> = cave.floor().iter().map(|&h| frame::heat(h, 9)).collect();
+    let first = draw(cave.floor(), &colors, String::from("height map"));
+    let low_points = cave.get_low_points();
+    // the basin being filled, and how much of it is
+    let (mut number, mut basin, mut filled) = (0, Vec::new(), 0);
+    let rest = iter::from_fn(move || {
+        while filled == basin.len() {
+            basin = cave.get_basin(*low_points.get(number)?);
+            filled = 0;
+            number += 1;
+        }
+        let per_frame = basin.len().div_ceil(FRAMES_PER_BASIN);
+        let end = (filled + per_frame).min(basin.len());
+        for &cell in &basin[filled..end] {
+            colors[cell] = frame::distinct(number - 1);
+        }
+        filled = end;
+        let caption = format!(
+            "basin {} of {}, {} of {} locations",
+            number,
+            low_points.len(),
+            filled,
+            basin.len()
+        );
+        Some(draw(cave.floor(), &colors, caption))
+    });
+    iter::once(first).chain(rest)
+}
+
+fn draw(floor: &Grid<u32>, colors: &[Color], caption: String) -> Frame {
+    let rows = floor
+        .rows()
+        .zip(colors.chunks(floor.row_len().max(1)))
+        .map(|(heights, colors)| {
+            heights
+                .iter()
+                .zip(colors)
+                .map(|(&h, &color)| Cell::new(char::from_digit(h, 10).unwrap_or('?'), color))
+                .collect()
+        })
+        .collect();
+    Frame::new(caption, rows)
+}
+
+/// Each basin in its own colour from `palette`, with the ridges of 9s black.
+pub fn image(cave: &Cave, palette: Palette) -> Image {
+    let floor = cave.floor();
+    let mut colors = vec![BLACK; floor.len()];
+    for (i, &low) in cave.get_low_points().iter().enumerate() {
+        // golden ratio steps keep neighbouring basins apart on the ramp
+        let t = 0.25 + 0.75 * (i as f64 * 0.618_034).fract();
+        for cell in cave.get_basin(low) {
+            colors[cell] = palette.at(t);
+        }
+    }
+    Image::from_fn(floor.row_len(), floor.col_len(), |x, y| {
+        colors[y * floor.row_len() + x]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_frames() {
+        let cave = day_9::parse(include_str!("../../day-9/data/test.txt")).unwrap();
+        let frames: Vec<_> = frames(&cave).collect();
+        assert_eq!(frames[0].plain().lines().nth(1), Some("2199943210"));
+        assert_eq!(frames[1].caption, "basin 1 of 4, 1 of 3 locations");
+        assert_eq!(
+            frames.last().unwrap().caption,
+            "basin 4 of 4, 9 of 9 locations"
+        );
+        assert_ne!(frames[0].rows, frames.last().unwrap().rows);
+    }
+
+    #[test]
+    fn test_image() {
+        let cave = day_9::parse(include_str!("../../day-9/data/test.txt")).unwrap();
+        let image = image(&cave, Palette::Gray);
+        assert_eq!((image.width(), image.height()), (10, 5));
+        assert_eq!(image.pixel(2, 0), Some(BLACK));
+        // the top left and top right basins are told apart
+        assert_ne!(image.pixel(0, 0), Some(BLACK));
+        assert_ne!(image.pixel(0, 0), image.pixel(9, 0));
+    }
+}
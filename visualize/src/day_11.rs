@@ -0,0 +1,101 @@
+//! Dumbo octopi: energy levels step by step, with each step's flashes
+//! spreading across the grid before it settles.
+use crate::frame::{self, Cell, Frame};
+use crate::image::{Image, Palette};
+use crossterm::style::Color;
+use day_11::Octopi;
+use std::iter;
+
+/// Steps shown when the octopi never all flash together.
+const MAX_STEPS: u64 = 1000;
+/// Frames shown while a step's flashes spread.
+const FRAMES_PER_CASCADE: usize = 4;
+
+/// Each step as it settles, with a few frames of its cascade before, until
+/// the octopi all flash together. Only one step's frames are held at once.
+pub fn frames(octopi: &Octopi) -> impl Iterator<Item = Frame> {
+    let mut octopi = octopi.clone();
+    let first = draw(&octopi, String::from("step 0"));
+    let per_frame = octopi.len().div_ceil(FRAMES_PER_CASCADE).max(1);
+    let mut synced = false;
+    let steps = (1..=MAX_STEPS).map_while(move |step| {
+        if synced {
+            return None;
+        }
+        let mut frames = Vec::new();
+        let mut flashed = 0;
+        let flashes = octopi.step_with(|octopi| {
+            flashed += 1;
+            if flashed % per_frame == 0 {
+                let caption = format!("step {}, {} flashed so far", step, flashed);
+                frames.push(draw(octopi, caption));
+            }
+        });
+        let caption = format!("step {}, {} flashed", step, flashes);
+        frames.push(draw(&octopi, caption));
+        synced = flashes == octopi.len() as u64;
+        Some(frames)
+    });
+    iter::once(first).chain(steps.flatten())
+}
+
+fn draw(octopi: &Octopi, caption: String) -> Frame {
+    let energy = octopi.energy_grid();
+    let rows = energy
+        .rows()
+        .map(|row| {
+            row.iter()
+                .map(|&e| match e {
+                    // just flashed
+                    0 => Cell::new('*', Color::White),
+                    _ => Cell::new(char::from_digit(e, 10).unwrap_or('?'), frame::heat(e, 9)),
+                })
+                .collect()
+        })
+        .collect();
+    Frame::new(caption, rows)
+}
+
+/// The energy levels after `steps` steps, 0 at the bottom of `palette`
+/// and 9 at the top.
+pub fn image(octopi: &Octopi, steps: u64, palette: Palette) -> Image {
+    let mut octopi = octopi.clone();
+    for _ in 0..steps {
+        octopi.step();
+    }
+    let energy = octopi.energy_grid();
+    Image::from_fn(energy.row_len(), energy.col_len(), |x, y| {
+        palette.at(f64::from(energy.get(x, y).copied().unwrap_or(0)) / 9.0)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_frames() {
+        let octopi = day_11::parse("11111\n19991\n19191\n19991\n11111").unwrap();
+        let frames: Vec<_> = frames(&octopi).take(3).collect();
+        assert_eq!(
+            frames[0].plain(),
+            "step 0\n11111\n19991\n19191\n19991\n11111"
+        );
+        // 9 flashes over 25 octopi gives one frame mid cascade, at 7 flashes
+        assert_eq!(frames[1].caption, "step 1, 7 flashed so far");
+        assert_eq!(
+            frames[2].plain(),
+            "step 1, 9 flashed\n34543\n4***4\n5***5\n4***4\n34543"
+        );
+    }
+
+    #[test]
+    fn test_image() {
+        let octopi = day_11::parse("11111\n19991\n19191\n19991\n11111").unwrap();
+        let before = image(&octopi, 0, Palette::Gray);
+        assert_eq!(before.pixel(1, 1), Some(crate::image::WHITE));
+        let after = image(&octopi, 1, Palette::Gray);
+        assert_eq!(after.pixel(1, 1), Some(crate::image::BLACK));
+        assert_eq!(after.pixel(0, 0), Some(Palette::Gray.at(3.0 / 9.0)));
+    }
+}
@@ -0,0 +1,98 @@
+//! Transparent origami: the paper before each fold with the fold line
+//! marked, down to the folded code.
+use crate::frame::{Cell, Frame};
+use crate::image::{Image, Palette};
+use crossterm::style::Color;
+use day_13::{FoldInstruction, Paper};
+use std::iter;
+
+/// The paper before each fold, then once it is folded.
+pub fn frames(paper: &Paper) -> impl Iterator<Item = Frame> {
+    let mut paper = paper.clone();
+    let total = paper.instructions.len();
+    let mut done = 0;
+    let mut folded = false;
+    iter::from_fn(move || {
+        if folded {
+            return None;
+        }
+        let next = paper.instructions.front().copied();
+        let caption = match next {
+            Some(FoldInstruction::FoldY(y)) => {
+                format!("fold {} of {}: up along y={}", done + 1, total, y)
+            }
+            Some(FoldInstruction::FoldX(x)) => {
+                format!("fold {} of {}: left along x={}", done + 1, total, x)
+            }
+            None => {
+                folded = true;
+                let caption = format!("folded {} times, {} dots", total, paper.count_dots());
+                return Some(draw(&paper, None, caption));
+            }
+        };
+        let frame = draw(&paper, next, caption);
+        paper.fold();
+        done += 1;
+        Some(frame)
+    })
+}
+
+fn draw(paper: &Paper, fold: Option<FoldInstruction>, caption: String) -> Frame {
+    let rows = paper
+        .rows
+        .iter()
+        .enumerate()
+        .map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(|(x, &spot)| match (spot, fold) {
+                    (_, Some(FoldInstruction::FoldY(at))) if at == y => Cell::new('-', Color::Red),
+                    (_, Some(FoldInstruction::FoldX(at))) if at == x => Cell::new('|', Color::Red),
+                    ('#', _) => Cell::new('#', Color::Yellow),
+                    _ => Cell::new('.', Color::DarkGrey),
+                })
+                .collect()
+        })
+        .collect();
+    Frame::new(caption, rows)
+}
+
+/// The paper after `folds` folds, dots at the top of `palette` on the
+/// bottom of it.
+pub fn image(paper: &Paper, folds: usize, palette: Palette) -> Image {
+    let mut paper = paper.clone();
+    for _ in 0..folds.min(paper.instructions.len()) {
+        paper.fold();
+    }
+    let width = paper.rows.iter().map(Vec::len).max().unwrap_or(0);
+    Image::from_fn(width, paper.rows.len(), |x, y| match paper.rows[y].get(x) {
+        Some('#') => palette.at(1.0),
+        _ => palette.at(0.0),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_frames() {
+        let paper = day_13::parse(include_str!("../../day-13/data/test.txt")).unwrap();
+        let frames: Vec<_> = frames(&paper).collect();
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[0].caption, "fold 1 of 2: up along y=7");
+        assert_eq!(frames[0].plain().lines().nth(8), Some("-----------"));
+        assert_eq!(frames[1].plain().lines().nth(1), Some("#.##.|#..#."));
+        assert_eq!(frames[2].caption, "folded 2 times, 16 dots");
+    }
+
+    #[test]
+    fn test_image() {
+        let paper = day_13::parse(include_str!("../../day-13/data/test.txt")).unwrap();
+        let image = image(&paper, usize::MAX, Palette::Gray);
+        assert_eq!((image.width(), image.height()), (5, 7));
+        assert_eq!(image.pixel(0, 0), Some(crate::image::WHITE));
+        assert_eq!(image.pixel(1, 1), Some(crate::image::BLACK));
+        assert_eq!(super::image(&paper, 1, Palette::Gray).width(), 11);
+    }
+}
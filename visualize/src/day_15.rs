@@ -0,0 +1,113 @@
+//! Chiton: the cave's risk levels, then the search settling positions in
+//! order of their lowest total risk until it reaches the far corner.
+use crate::frame::{self, Cell, Frame};
+use crate::image::{Image, Palette, WHITE};
+use crossterm::style::Color;
+use day_15::Cave;
+use grid::Grid;
+use std::iter;
+
+/// Frames shown while the search spreads across the cave.
+const FRAMES: usize = 100;
+
+/// The risk levels, then the search settling positions a batch at a time,
+/// then the lowest total risk. The search runs first, since the colours
+/// depend on the highest risk it reaches.
+pub fn frames(cave: &Cave) -> impl Iterator<Item = Frame> {
+    let floor = cave.floor().clone();
+    let mut settled = vec![None; floor.len()];
+    let mut visits = Vec::new();
+    let risk = cave.dijkstra_with(0, |pos, cost| visits.push((pos, cost)));
+    let highest = visits.last().map_or(0, |&(_, cost)| cost);
+    let first = draw(&floor, &settled, highest, String::from("risk levels"));
+    let per_frame = visits.len().div_ceil(FRAMES).max(1);
+    let mut done = 0;
+    let batches = iter::from_fn(move || {
+        let batch = &visits[done..(done + per_frame).min(visits.len())];
+        let &(_, cost) = batch.last()?;
+        for &(pos, cost) in batch {
+            settled[pos] = Some(cost);
+        }
+        done += batch.len();
+        let caption = format!(
+            "{} of {} positions settled, total risk {}",
+            done,
+            floor.len(),
+            cost
+        );
+        let frame = draw(&floor, &settled, highest, caption);
+        // the last frame shows the answer over the settled cave
+        let last = match (done == visits.len(), risk) {
+            (true, Some(risk)) => {
+                let caption = format!("lowest total risk {}", risk);
+                Some(draw(&floor, &settled, highest, caption))
+            }
+            _ => None,
+        };
+        Some(iter::once(frame).chain(last))
+    });
+    iter::once(first).chain(batches.flatten())
+}
+
+fn draw(floor: &Grid<u32>, settled: &[Option<u32>], highest: u32, caption: String) -> Frame {
+    let rows = floor
+        .rows()
+        .zip(settled.chunks(floor.row_len().max(1)))
+        .map(|(levels, settled)| {
+            levels
+                .iter()
+                .zip(settled)
+                .map(|(&level, cost)| {
+                    let ch = char::from_digit(level, 10).unwrap_or('?');
+                    match cost {
+                        Some(cost) => Cell::new(ch, frame::heat(*cost, highest)),
+                        None => Cell::new(ch, Color::DarkGrey),
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    Frame::new(caption, rows)
+}
+
+/// The risk levels from `palette`, kept off its top so the lowest risk path
+/// stands out in white.
+pub fn image(cave: &Cave, palette: Palette) -> Image {
+    let floor = cave.floor();
+    let mut colors: Vec<_> = floor
+        .iter()
+        .map(|&level| palette.at(f64::from(level.saturating_sub(1)) / 8.0 * 0.75))
+        .collect();
+    for pos in cave.lowest_risk_path().unwrap_or_default() {
+        colors[pos] = WHITE;
+    }
+    Image::from_fn(floor.row_len(), floor.col_len(), |x, y| {
+        colors[y * floor.row_len() + x]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_frames() {
+        let cave = day_15::parse(include_str!("../../day-15/data/test.txt")).unwrap();
+        let frames: Vec<_> = frames(&cave).collect();
+        assert_eq!(frames[0].plain().lines().nth(1), Some("1163751742"));
+        assert_eq!(frames.last().unwrap().caption, "lowest total risk 40");
+        assert!(frames.len() <= FRAMES + 2);
+        assert_ne!(frames[0].rows, frames[1].rows);
+    }
+
+    #[test]
+    fn test_image() {
+        let cave = day_15::parse(include_str!("../../day-15/data/test.txt")).unwrap();
+        let image = image(&cave, Palette::Gray);
+        assert_eq!((image.width(), image.height()), (10, 10));
+        assert_eq!(image.pixel(0, 0), Some(WHITE));
+        assert_eq!(image.pixel(9, 9), Some(WHITE));
+        // off the path along the top
+        assert_eq!(image.pixel(9, 0), Some(Palette::Gray.at(1.0 / 8.0 * 0.75)));
+    }
+}
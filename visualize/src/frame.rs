@@ -0,0 +1,97 @@
+use crossterm::style::Color;
+
+/// One character on screen and the colour to draw it in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub ch: char,
+    pub color: Color,
+}
+
+impl Cell {
+    pub fn new(ch: char, color: Color) -> Self {
+        Cell { ch, color }
+    }
+
+    pub fn blank() -> Self {
+        Cell::new(' ', Color::Reset)
+    }
+}
+
+/// A single picture of a simulation, with a caption saying what it shows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub caption: String,
+    pub rows: Vec<Vec<Cell>>,
+}
+
+impl Frame {
+    pub fn new(caption: impl Into<String>, rows: Vec<Vec<Cell>>) -> Self {
+        Frame {
+            caption: caption.into(),
+            rows,
+        }
+    }
+
+    /// The frame without colours: the caption, then one line per row.
+    pub fn plain(&self) -> String {
+        let mut text = self.caption.clone();
+        for row in self.rows.iter() {
+            text.push('\n');
+            text.extend(row.iter().map(|cell| cell.ch));
+            text.truncate(text.trim_end_matches(' ').len());
+        }
+        text
+    }
+}
+
+/// Cool to hot colours, from dark blue through green and yellow to red.
+const RAMP: [u8; 16] = [
+    17, 18, 19, 20, 26, 32, 38, 44, 43, 42, 41, 76, 148, 184, 214, 196,
+];
+
+/// A colour for `level` out of `max`, cooler for lower levels.
+pub fn heat(level: u32, max: u32) -> Color {
+    let step = match max {
+        0 => 0,
+        _ => level.min(max) as usize * (RAMP.len() - 1) / max as usize,
+    };
+    Color::AnsiValue(RAMP[step])
+}
+
+/// A colour that tells one region apart from its neighbours.
+pub fn distinct(index: usize) -> Color {
+    const PALETTE: [Color; 6] = [
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+    ];
+    PALETTE[index % PALETTE.len()]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_plain() {
+        let frame = Frame::new(
+            "step 1",
+            vec![
+                vec![Cell::new('#', Color::Red), Cell::blank()],
+                vec![Cell::blank(), Cell::new('#', Color::Red)],
+            ],
+        );
+        assert_eq!(frame.plain(), "step 1\n#\n #");
+    }
+
+    #[test]
+    fn test_heat() {
+        assert_eq!(heat(0, 9), Color::AnsiValue(17));
+        assert_eq!(heat(9, 9), Color::AnsiValue(196));
+        assert_eq!(heat(20, 9), heat(9, 9));
+        assert_eq!(heat(3, 0), Color::AnsiValue(17));
+    }
+}
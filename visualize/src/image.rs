@@ -0,0 +1,184 @@
+use anyhow::{anyhow, Context, Result};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+
+/// Colour ramps for mapping a value between 0 and 1 to a colour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Palette {
+    /// Black through red and yellow to white.
+    #[default]
+    Heat,
+    /// Black to white.
+    Gray,
+    /// Dark purple through blue and green to yellow.
+    Viridis,
+}
+
+impl FromStr for Palette {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "heat" => Ok(Palette::Heat),
+            "gray" | "grey" => Ok(Palette::Gray),
+            "viridis" => Ok(Palette::Viridis),
+            _ => Err(format!(
+                "unknown palette {:?}, expected heat, gray or viridis",
+                s
+            )),
+        }
+    }
+}
+
+impl Palette {
+    fn stops(self) -> &'static [Rgb] {
+        match self {
+            Palette::Heat => &[BLACK, [160, 0, 0], [255, 120, 0], [255, 230, 0], WHITE],
+            Palette::Gray => &[BLACK, WHITE],
+            Palette::Viridis => &[
+                [68, 1, 84],
+                [59, 82, 139],
+                [33, 145, 140],
+                [94, 201, 98],
+                [253, 231, 37],
+            ],
+        }
+    }
+
+    /// The colour at `t`, which is clamped to between 0 and 1.
+    pub fn at(self, t: f64) -> Rgb {
+        let stops = self.stops();
+        let t = t.clamp(0.0, 1.0) * (stops.len() - 1) as f64;
+        let i = (t.floor() as usize).min(stops.len() - 2);
+        let f = t - i as f64;
+        let mix = |a: u8, b: u8| (f64::from(a) + (f64::from(b) - f64::from(a)) * f).round() as u8;
+        let (a, b) = (stops[i], stops[i + 1]);
+        [mix(a[0], b[0]), mix(a[1], b[1]), mix(a[2], b[2])]
+    }
+}
+
+/// An RGB image, one pixel per grid cell until it is scaled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn from_fn<F>(width: usize, height: usize, mut f: F) -> Self
+    where
+        F: FnMut(usize, usize) -> Rgb,
+    {
+        let pixels = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect();
+        Image {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Option<Rgb> {
+        (x < self.width && y < self.height).then(|| self.pixels[y * self.width + x])
+    }
+
+    /// Every pixel blown up to a `factor` by `factor` square.
+    pub fn scaled(&self, factor: usize) -> Image {
+        let factor = factor.max(1);
+        Image::from_fn(self.width * factor, self.height * factor, |x, y| {
+            self.pixels[(y / factor) * self.width + x / factor]
+        })
+    }
+
+    /// Writes a binary PPM (P6).
+    pub fn write_ppm<W: Write>(&self, mut out: W) -> Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        out.write_all(&self.pixels.concat())?;
+        Ok(())
+    }
+
+    pub fn write_png<W: Write>(&self, out: W) -> Result<()> {
+        let mut encoder = png::Encoder::new(out, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels.concat())?;
+        writer.finish()?;
+        Ok(())
+    }
+
+    /// Writes the image as a PNG or PPM, going by the extension of `path`.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase);
+        let write = match extension.as_deref() {
+            Some("png") => Image::write_png::<BufWriter<File>>,
+            Some("ppm") => Image::write_ppm::<BufWriter<File>>,
+            _ => return Err(anyhow!("{} must end in .png or .ppm", path.display())),
+        };
+        let file =
+            File::create(path).with_context(|| format!("failed to create {}", path.display()))?;
+        write(self, BufWriter::new(file))
+            .with_context(|| format!("failed to write {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_palette() {
+        assert_eq!(Palette::Gray.at(0.0), BLACK);
+        assert_eq!(Palette::Gray.at(0.5), [128, 128, 128]);
+        assert_eq!(Palette::Gray.at(2.0), WHITE);
+        assert_eq!(Palette::Heat.at(1.0), WHITE);
+        assert_eq!(Palette::Viridis.at(0.0), [68, 1, 84]);
+        assert_eq!("grey".parse(), Ok(Palette::Gray));
+        assert!("rainbow".parse::<Palette>().is_err());
+    }
+
+    #[test]
+    fn test_ppm() {
+        let image = Image::from_fn(2, 1, |x, _| [x as u8, 0, 255]).scaled(2);
+        assert_eq!((image.width(), image.height()), (4, 2));
+        assert_eq!(image.pixel(3, 1), Some([1, 0, 255]));
+        assert_eq!(image.pixel(4, 1), None);
+        let mut out = Vec::new();
+        image.write_ppm(&mut out).unwrap();
+        assert!(out.starts_with(b"P6\n4 2\n255\n"));
+        assert_eq!(out.len(), 11 + 4 * 2 * 3);
+    }
+
+    #[test]
+    fn test_png() {
+        let image = Image::from_fn(3, 2, |x, y| [x as u8 * 100, y as u8 * 100, 7]);
+        let mut out = Vec::new();
+        image.write_png(&mut out).unwrap();
+        let mut reader = png::Decoder::new(&out[..]).read_info().unwrap();
+        let mut buf = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buf).unwrap();
+        assert_eq!((info.width, info.height), (3, 2));
+        assert_eq!(&buf[..info.buffer_size()], &image.pixels.concat()[..]);
+    }
+}
@@ -0,0 +1,139 @@
+//! Frame by frame terminal animations of the days that are simulations.
+//!
+//! Each animated day has a module turning its parsed input into an iterator
+//! of [`Frame`]s, each made when the player asks for it; [`player::play`]
+//! shows them with colours and lets the viewer pause, step and change the
+//! speed.
+//!
+//! The days with a grid-shaped state can also be drawn as an [`Image`] and
+//! saved as a PNG or PPM with [`image()`].
+use anyhow::{anyhow, Result};
+use common::puzzle::MAX_REPEAT;
+use common::Rendered;
+
+pub mod day_04;
+pub mod day_05;
+pub mod day_09;
+pub mod day_11;
+pub mod day_13;
+pub mod day_15;
+pub mod frame;
+pub mod image;
+pub mod player;
+
+pub use frame::{Cell, Frame};
+pub use image::{Image, Palette};
+pub use player::{parse_fps, play, Controls};
+
+/// The days that have an animation.
+pub const DAYS: [u8; 5] = [4, 9, 11, 13, 15];
+
+/// The days that can be drawn as an image.
+pub const IMAGE_DAYS: [u8; 5] = [5, 9, 11, 13, 15];
+
+/// How to draw a day as an image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImageOptions {
+    /// Pixels per grid cell along each side.
+    pub scale: usize,
+    pub palette: Palette,
+    /// Day 5 leaves out diagonal vents and day 13 stops after one fold for
+    /// part 1.
+    pub part: u8,
+    /// Steps the day 11 octopi take first, at most [`MAX_REPEAT`].
+    pub steps: u64,
+}
+
+impl Default for ImageOptions {
+    fn default() -> Self {
+        ImageOptions {
+            scale: 4,
+            palette: Palette::default(),
+            part: 2,
+            steps: 100,
+        }
+    }
+}
+
+/// Parses `data` as the input for `day` and starts its animation.
+pub fn frames(day: u8, data: &str) -> Result<Box<dyn Iterator<Item = Frame>>> {
+    let frames: Box<dyn Iterator<Item = Frame>> = match day {
+        4 => Box::new(day_04::frames(&::day_4::parse(data).rendered(data)?)),
+        9 => Box::new(day_09::frames(&::day_9::parse(data).rendered(data)?)),
+        11 => Box::new(day_11::frames(&::day_11::parse(data).rendered(data)?)),
+        13 => Box::new(day_13::frames(&::day_13::parse(data).rendered(data)?)),
+        15 => Box::new(day_15::frames(&::day_15::parse(data).rendered(data)?)),
+        _ => return Err(unsupported("animation", day, &DAYS)),
+    };
+    Ok(frames)
+}
+
+/// Parses `data` as the input for `day` and draws its state as an image.
+pub fn image(day: u8, data: &str, options: &ImageOptions) -> Result<Image> {
+    if options.steps > MAX_REPEAT {
+        return Err(anyhow!(
+            "expected at most {} steps, got {}",
+            MAX_REPEAT,
+            options.steps
+        ));
+    }
+    let palette = options.palette;
+    let part_1 = options.part == 1;
+    let image = match day {
+        5 => day_05::image(&::day_5::parse(data).rendered(data)?, !part_1, palette),
+        9 => day_09::image(&::day_9::parse(data).rendered(data)?, palette),
+        11 => day_11::image(
+            &::day_11::parse(data).rendered(data)?,
+            options.steps,
+            palette,
+        ),
+        13 => {
+            let folds = if part_1 { 1 } else { usize::MAX };
+            day_13::image(&::day_13::parse(data).rendered(data)?, folds, palette)
+        }
+        15 => day_15::image(&::day_15::parse(data).rendered(data)?, palette),
+        _ => return Err(unsupported("image", day, &IMAGE_DAYS)),
+    };
+    Ok(image.scaled(options.scale))
+}
+
+fn unsupported(what: &str, day: u8, supported: &[u8]) -> anyhow::Error {
+    let days: Vec<_> = supported.iter().map(u8::to_string).collect();
+    anyhow!(
+        "no {} for day {}, only for days {}",
+        what,
+        day,
+        days.join(", ")
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_image() {
+        let data = include_str!("../../day-13/data/test.txt");
+        let options = ImageOptions {
+            scale: 3,
+            part: 1,
+            ..ImageOptions::default()
+        };
+        let folded = image(13, data, &options).unwrap();
+        assert_eq!((folded.width(), folded.height()), (33, 21));
+        let err = image(4, data, &options).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "no image for day 4, only for days 5, 9, 11, 13, 15"
+        );
+        let options = ImageOptions {
+            steps: u64::MAX,
+            ..ImageOptions::default()
+        };
+        let err = image(11, "11111\n19991", &options).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("expected at most 1000000 steps, got {}", u64::MAX)
+        );
+    }
+}
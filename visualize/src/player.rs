@@ -0,0 +1,243 @@
+use crate::frame::Frame;
+use anyhow::Result;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Color, Print, ResetColor, SetForegroundColor};
+use crossterm::{cursor, queue, terminal};
+use std::io::{self, IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+const MIN_FPS: f64 = 0.25;
+const MAX_FPS: f64 = 240.0;
+/// The speed playback starts at unless asked otherwise.
+pub const DEFAULT_FPS: f64 = 10.0;
+
+/// Playback speed and whether playback is paused, changed by the keys.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Controls {
+    pub fps: f64,
+    pub paused: bool,
+}
+
+/// What a key press asks the player to do next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Continue,
+    Step,
+    Quit,
+}
+
+impl Controls {
+    /// Starts at `fps`, kept within the speeds the keys can reach. A NaN
+    /// speed starts at the default instead.
+    pub fn new(fps: f64, paused: bool) -> Self {
+        let fps = if fps.is_nan() { DEFAULT_FPS } else { fps };
+        Controls {
+            fps: fps.clamp(MIN_FPS, MAX_FPS),
+            paused,
+        }
+    }
+
+    /// Space pauses, `n` or the right arrow steps one frame (pausing first),
+    /// `+` and `-` double or halve the speed, `q` or escape quits.
+    pub fn handle(&mut self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::Quit,
+            KeyCode::Char('q') | KeyCode::Esc => Action::Quit,
+            KeyCode::Char(' ') => {
+                self.paused = !self.paused;
+                Action::Continue
+            }
+            KeyCode::Char('n') | KeyCode::Right => {
+                self.paused = true;
+                Action::Step
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') | KeyCode::Up => {
+                self.fps = (self.fps * 2.0).min(MAX_FPS);
+                Action::Continue
+            }
+            KeyCode::Char('-') | KeyCode::Down => {
+                self.fps = (self.fps / 2.0).max(MIN_FPS);
+                Action::Continue
+            }
+            _ => Action::Continue,
+        }
+    }
+
+    fn delay(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.fps)
+    }
+}
+
+/// Reads a frame rate argument, which must be a finite number.
+pub fn parse_fps(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(fps) if fps.is_finite() => Ok(fps),
+        Ok(_) => Err(format!("frame rate {:?} is not a finite number", s)),
+        Err(e) => Err(format!("invalid frame rate {:?}: {}", s, e)),
+    }
+}
+
+/// Puts the terminal back the way it was, even if playback fails.
+struct Screen;
+
+impl Screen {
+    fn enter() -> Result<Self> {
+        terminal::enable_raw_mode()?;
+        let mut out = io::stdout();
+        if let Err(err) = crossterm::execute!(out, terminal::EnterAlternateScreen, cursor::Hide) {
+            let _ = terminal::disable_raw_mode();
+            return Err(err.into());
+        }
+        Ok(Screen)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let mut out = io::stdout();
+        let _ = crossterm::execute!(
+            out,
+            ResetColor,
+            cursor::Show,
+            terminal::LeaveAlternateScreen
+        );
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Plays `frames` in the terminal. When stdout is not a terminal every frame
+/// is written out as plain text instead.
+pub fn play<I>(frames: I, controls: Controls) -> Result<()>
+where
+    I: IntoIterator<Item = Frame>,
+{
+    if !io::stdout().is_terminal() {
+        let mut out = io::stdout().lock();
+        for frame in frames {
+            writeln!(out, "{}\n", frame.plain())?;
+        }
+        return Ok(());
+    }
+    let _screen = Screen::enter()?;
+    let mut controls = controls;
+    let mut frames = frames.into_iter().enumerate().peekable();
+    let Some((mut number, mut frame)) = frames.next() else {
+        return Ok(());
+    };
+    loop {
+        let done = frames.peek().is_none();
+        draw(&frame, number, &controls, done)?;
+        let shown = Instant::now();
+        let mut advance = false;
+        while !advance {
+            let timeout = match controls.paused || done {
+                true => None,
+                false => Some(controls.delay().saturating_sub(shown.elapsed())),
+            };
+            match timeout {
+                Some(left) if !event::poll(left)? => advance = true,
+                _ => {
+                    if let Event::Key(key) = event::read()? {
+                        if key.kind == KeyEventKind::Release {
+                            continue;
+                        }
+                        match controls.handle(key) {
+                            Action::Quit => return Ok(()),
+                            Action::Step => advance = true,
+                            Action::Continue => {}
+                        }
+                        draw(&frame, number, &controls, done)?;
+                    }
+                }
+            }
+            if advance && done {
+                advance = false;
+            }
+        }
+        (number, frame) = frames.next().expect("checked there is another frame");
+    }
+}
+
+fn draw(frame: &Frame, number: usize, controls: &Controls, done: bool) -> Result<()> {
+    let (width, height) = terminal::size()?;
+    let (width, height) = (usize::from(width), usize::from(height));
+    let mut out = io::stdout().lock();
+    let mut color = Color::Reset;
+    queue!(out, cursor::MoveTo(0, 0), ResetColor)?;
+    // the last line is kept for the status
+    for (y, row) in frame.rows.iter().take(height.saturating_sub(1)).enumerate() {
+        queue!(out, cursor::MoveTo(0, y as u16))?;
+        for cell in row.iter().take(width) {
+            if cell.color != color {
+                color = cell.color;
+                queue!(out, SetForegroundColor(color))?;
+            }
+            queue!(out, Print(cell.ch))?;
+        }
+        queue!(out, terminal::Clear(terminal::ClearType::UntilNewLine))?;
+    }
+    queue!(out, terminal::Clear(terminal::ClearType::FromCursorDown))?;
+    let state = match (done, controls.paused) {
+        (true, _) => "done",
+        (false, true) => "paused",
+        (false, false) => "playing",
+    };
+    let status = format!(
+        "{} | frame {} | {} at {} fps | space pause, n step, +/- speed, q quit",
+        frame.caption,
+        number + 1,
+        state,
+        controls.fps
+    );
+    let status: String = status.chars().take(width).collect();
+    queue!(
+        out,
+        cursor::MoveTo(0, height.saturating_sub(1) as u16),
+        ResetColor,
+        Print(status),
+        terminal::Clear(terminal::ClearType::UntilNewLine)
+    )?;
+    out.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn press(controls: &mut Controls, code: KeyCode) -> Action {
+        controls.handle(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn test_controls() {
+        let mut controls = Controls::new(10.0, false);
+        assert_eq!(press(&mut controls, KeyCode::Char(' ')), Action::Continue);
+        assert!(controls.paused);
+        assert_eq!(press(&mut controls, KeyCode::Char(' ')), Action::Continue);
+        assert_eq!(press(&mut controls, KeyCode::Right), Action::Step);
+        assert!(controls.paused);
+        press(&mut controls, KeyCode::Char('+'));
+        assert_eq!(controls.fps, 20.0);
+        for _ in 0..10 {
+            press(&mut controls, KeyCode::Char('-'));
+        }
+        assert_eq!(controls.fps, MIN_FPS);
+        assert_eq!(press(&mut controls, KeyCode::Char('q')), Action::Quit);
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(controls.handle(ctrl_c), Action::Quit);
+        assert_eq!(Controls::new(1000.0, false).fps, MAX_FPS);
+    }
+
+    #[test]
+    fn test_non_finite_fps() {
+        assert_eq!(Controls::new(f64::NAN, false).fps, DEFAULT_FPS);
+        assert_eq!(Controls::new(f64::INFINITY, false).fps, MAX_FPS);
+        assert_eq!(Controls::new(f64::NEG_INFINITY, false).fps, MIN_FPS);
+        assert!(Controls::new(f64::NAN, false).delay() > Duration::ZERO);
+        assert_eq!(parse_fps("2.5"), Ok(2.5));
+        assert!(parse_fps("nan").is_err());
+        assert!(parse_fps("inf").is_err());
+        assert!(parse_fps("fast").is_err());
+    }
+}